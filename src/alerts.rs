@@ -0,0 +1,292 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::server_context::ServerContext;
+
+/// Severities that trigger an alert when they appear on a watched project.
+const ALERT_SEVERITIES: &str = "BLOCKER,CRITICAL";
+
+/// Default payload template; placeholders are substituted before posting.
+const DEFAULT_TEMPLATE: &str =
+    r#"{"text":"{count} new {severities} issue(s) in {project}: {keys}"}"#;
+
+/// Runs the outbound alert poller. Each cycle searches every watched
+/// project for BLOCKER/CRITICAL issues created since the previous cycle
+/// and posts the templated payload to the configured webhook, so quality
+/// regressions reach incident tooling (PagerDuty, Opsgenie, Slack) without
+/// a client connected.
+pub async fn run_poller(ctx: Arc<ServerContext>) {
+    let Some(url) = ctx.config.alert_webhook_url.clone() else {
+        return;
+    };
+    // The outbound allowlist covers notifiers too: when one is configured,
+    // the webhook host must be on it, or alerting stays off rather than
+    // punching a hole in the attestation the allowlist provides.
+    if !ctx.config.outbound_allowlist.is_empty() {
+        if let Err(err) = ctx.client.check_outbound(&url) {
+            tracing::error!(
+                "alert webhook disabled: {err}; add the webhook host to --outbound-allowlist"
+            );
+            return;
+        }
+    }
+    let template = ctx
+        .config
+        .alert_template
+        .clone()
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string());
+    let http = reqwest::Client::new();
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        ctx.config.alert_poll_seconds.max(10),
+    ));
+    let mut since = now_unix();
+    loop {
+        interval.tick().await;
+        let cycle_start = now_unix();
+        for project in ctx.watchlist.projects() {
+            if let Err(err) = check_project(&ctx, &http, &url, &template, &project, since).await {
+                tracing::warn!("alert poll for {project} failed: {err}");
+            }
+        }
+        since = cycle_start;
+    }
+}
+
+async fn check_project(
+    ctx: &ServerContext,
+    http: &reqwest::Client,
+    url: &str,
+    template: &str,
+    project: &str,
+    since: u64,
+) -> crate::error::Result<()> {
+    let query = vec![
+        ("componentKeys", project.to_string()),
+        ("severities", ALERT_SEVERITIES.to_string()),
+        ("createdAfter", iso_utc(since)),
+        ("ps", "100".to_string()),
+    ];
+    let response: Value = ctx.client.get("/api/issues/search", &query).await?;
+    let issues = response["issues"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    if issues.is_empty() {
+        return Ok(());
+    }
+    // Most severe first, so truncated incident views lead with BLOCKERs.
+    let mut ranked: Vec<(&str, usize)> = issues
+        .iter()
+        .filter_map(|i| {
+            Some((i["key"].as_str()?, severity_rank(i["severity"].as_str().unwrap_or(""))))
+        })
+        .collect();
+    ranked.sort_by_key(|(_, rank)| *rank);
+    let keys: Vec<&str> = ranked.iter().map(|(key, _)| *key).collect();
+
+    // The metadata placeholders cost an extra round trip, so only resolve
+    // them when the template actually uses them.
+    let metadata = if template_needs_metadata(template) {
+        project_metadata(ctx, project).await
+    } else {
+        ProjectMetadata::default()
+    };
+    let vars = TemplateVars {
+        project,
+        count: keys.len(),
+        keys: &keys.join(", "),
+        name: &metadata.name,
+        last_analysis: &metadata.last_analysis,
+        tags: &metadata.tags,
+        dashboard_url: &format!(
+            "{}/dashboard?id={project}",
+            ctx.config.sonarqube_url.trim_end_matches('/')
+        ),
+    };
+    let payload = render_template(template, &vars);
+    tracing::info!(
+        "alerting on {} new high-severity issue(s) in {project}",
+        keys.len()
+    );
+    let result = http
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload)
+        .send()
+        .await?;
+    if !result.status().is_success() {
+        tracing::warn!("alert webhook returned HTTP {}", result.status());
+    }
+    Ok(())
+}
+
+/// Everything a payload template may reference.
+struct TemplateVars<'a> {
+    project: &'a str,
+    count: usize,
+    keys: &'a str,
+    name: &'a str,
+    last_analysis: &'a str,
+    tags: &'a str,
+    dashboard_url: &'a str,
+}
+
+/// Project metadata placeholders, resolved from `/api/components/show`.
+#[derive(Default)]
+struct ProjectMetadata {
+    name: String,
+    last_analysis: String,
+    tags: String,
+}
+
+/// Placeholders that require the extra metadata lookup.
+const METADATA_PLACEHOLDERS: &[&str] = &["{name}", "{last_analysis}", "{tags}"];
+
+fn template_needs_metadata(template: &str) -> bool {
+    METADATA_PLACEHOLDERS
+        .iter()
+        .any(|placeholder| template.contains(placeholder))
+}
+
+/// Best-effort metadata fetch; an unreachable lookup leaves the
+/// placeholders empty rather than suppressing the alert.
+async fn project_metadata(ctx: &ServerContext, project: &str) -> ProjectMetadata {
+    let query = vec![("component", project.to_string())];
+    match ctx.client.get::<Value>("/api/components/show", &query).await {
+        Ok(response) => {
+            let component = &response["component"];
+            ProjectMetadata {
+                name: component["name"].as_str().unwrap_or(project).to_string(),
+                last_analysis: component["analysisDate"].as_str().unwrap_or("").to_string(),
+                tags: component["tags"]
+                    .as_array()
+                    .map(|tags| {
+                        tags.iter()
+                            .filter_map(Value::as_str)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default(),
+            }
+        }
+        Err(err) => {
+            tracing::warn!("metadata lookup for alert on {project} failed: {err}");
+            ProjectMetadata::default()
+        }
+    }
+}
+
+/// Ranks classic severities most-severe-first; unknown values sort last.
+fn severity_rank(severity: &str) -> usize {
+    ["BLOCKER", "CRITICAL", "MAJOR", "MINOR", "INFO"]
+        .iter()
+        .position(|s| *s == severity)
+        .unwrap_or(usize::MAX)
+}
+
+/// Substitutes the supported placeholders into the payload template. The
+/// free-text values are JSON-escaped; the rest are plain identifiers.
+fn render_template(template: &str, vars: &TemplateVars) -> String {
+    template
+        .replace("{project}", vars.project)
+        .replace("{count}", &vars.count.to_string())
+        .replace("{severities}", ALERT_SEVERITIES)
+        .replace("{keys}", &escape(vars.keys))
+        .replace("{name}", &escape(vars.name))
+        .replace("{last_analysis}", vars.last_analysis)
+        .replace("{tags}", &escape(vars.tags))
+        .replace("{dashboard_url}", vars.dashboard_url)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats a Unix timestamp as an ISO-8601 UTC instant, the format
+/// `createdAfter` expects. Inverse of the civil-date conversion used by the
+/// Grafana endpoint (Howard Hinnant's civil_from_days).
+fn iso_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = if month <= 2 { y + 1 } else { y };
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}+0000",
+        rem / 3_600,
+        (rem % 3_600) / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_unix_timestamps_as_iso_utc() {
+        assert_eq!(iso_utc(0), "1970-01-01T00:00:00+0000");
+        assert_eq!(iso_utc(1_714_564_800), "2024-05-01T12:00:00+0000");
+    }
+
+    fn demo_vars<'a>(keys: &'a str) -> TemplateVars<'a> {
+        TemplateVars {
+            project: "demo",
+            count: 2,
+            keys,
+            name: "Demo \"app\"",
+            last_analysis: "2024-05-01T12:00:00+0000",
+            tags: "backend, payments",
+            dashboard_url: "http://localhost:9000/dashboard?id=demo",
+        }
+    }
+
+    #[test]
+    fn renders_placeholders_and_escapes_keys() {
+        let payload = render_template(DEFAULT_TEMPLATE, &demo_vars("AYx1, AYx2"));
+        assert_eq!(
+            payload,
+            r#"{"text":"2 new BLOCKER,CRITICAL issue(s) in demo: AYx1, AYx2"}"#
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert!(parsed["text"].as_str().unwrap().contains("demo"));
+    }
+
+    #[test]
+    fn renders_metadata_placeholders_with_escaping() {
+        let template = r#"{"text":"{name} ({tags}) last analysed {last_analysis}: {dashboard_url}"}"#;
+        let payload = render_template(template, &demo_vars(""));
+        let parsed: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(
+            parsed["text"],
+            "Demo \"app\" (backend, payments) last analysed 2024-05-01T12:00:00+0000: http://localhost:9000/dashboard?id=demo"
+        );
+    }
+
+    #[test]
+    fn metadata_is_only_needed_when_referenced() {
+        assert!(!template_needs_metadata(DEFAULT_TEMPLATE));
+        assert!(template_needs_metadata("{name}: {count}"));
+        assert!(template_needs_metadata("tags are {tags}"));
+    }
+
+    #[test]
+    fn ranks_classic_severities_most_severe_first() {
+        assert!(severity_rank("BLOCKER") < severity_rank("CRITICAL"));
+        assert!(severity_rank("CRITICAL") < severity_rank("INFO"));
+        assert!(severity_rank("INFO") < severity_rank("SOMETHING_NEW"));
+    }
+}
@@ -0,0 +1,127 @@
+//! Structured audit log of tool invocations. Every `tools/call` appends
+//! one JSON object to the file named by `--audit-log`: timestamp, tool
+//! name, sanitized arguments (token-like values are masked), the caller
+//! reported at initialization, duration and outcome. Compliance teams
+//! typically require this trail before write tools may be enabled.
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+
+/// Substrings marking an argument key as secret-bearing; matching values
+/// are masked in audit records.
+const SENSITIVE_KEYS: [&str; 4] = ["token", "password", "secret", "credentials"];
+
+/// Append-only JSONL sink for audit records.
+pub struct AuditLog {
+    file: Mutex<std::fs::File>,
+}
+
+impl AuditLog {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| {
+                Error::Config(format!("cannot open audit log {}: {err}", path.display()))
+            })?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Appends one record. A failed write is reported in the server log
+    /// rather than failing the tool call it describes.
+    pub fn record(
+        &self,
+        tool: &str,
+        args: Value,
+        caller: Option<String>,
+        duration_ms: u128,
+        error: Option<String>,
+    ) {
+        let record = json!({
+            "time_unix": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            "tool": tool,
+            "args": args,
+            "caller": caller,
+            "duration_ms": duration_ms,
+            "outcome": if error.is_none() { "success" } else { "error" },
+            "error": error,
+        });
+        let mut file = self.file.lock().expect("audit log poisoned");
+        if let Err(err) = writeln!(file, "{record}") {
+            tracing::warn!("audit log write failed: {err}");
+        }
+    }
+}
+
+/// Deep copy of tool arguments with secret-bearing values masked, so
+/// credentials passed per call never reach the audit trail.
+pub fn sanitize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let lowered = key.to_ascii_lowercase();
+                    if SENSITIVE_KEYS.iter().any(|mark| lowered.contains(mark)) {
+                        (key.clone(), Value::String("<redacted>".to_string()))
+                    } else {
+                        (key.clone(), sanitize(value))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(sanitize).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_masks_secret_bearing_keys_at_any_depth() {
+        let args = json!({
+            "project": "demo",
+            "credentials": {"url": "https://x", "token": "squ_abc"},
+            "nested": {"api_token": "t", "list": [{"password": "p"}]},
+        });
+        let clean = sanitize(&args);
+        assert_eq!(clean["project"], "demo");
+        assert_eq!(clean["credentials"], "<redacted>");
+        assert_eq!(clean["nested"]["api_token"], "<redacted>");
+        assert_eq!(clean["nested"]["list"][0]["password"], "<redacted>");
+    }
+
+    #[test]
+    fn records_are_appended_as_one_json_object_per_line() {
+        let path = std::env::temp_dir().join("sonarqube-mcp-audit-test.jsonl");
+        let _ = std::fs::remove_file(&path);
+        let log = AuditLog::open(&path).expect("audit log");
+        log.record("sonarqube_get_issues", json!({"project": "demo"}), None, 12, None);
+        log.record("sonarqube_set_setting", json!({}), Some("vscode".to_string()), 3,
+            Some("admin operations are disabled".to_string()));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["tool"], "sonarqube_get_issues");
+        assert_eq!(lines[0]["outcome"], "success");
+        assert_eq!(lines[1]["caller"], "vscode");
+        assert_eq!(lines[1]["outcome"], "error");
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,288 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+/// How credentials for the SonarQube Web API are acquired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum AuthProviderKind {
+    /// The token configured via --sonarqube-token.
+    #[default]
+    Static,
+    /// Read the token from a file on every request (supports rotation).
+    File,
+    /// Run a command and use its trimmed stdout as the token.
+    Command,
+    /// OAuth2 client-credentials grant against a gateway token endpoint.
+    Oauth,
+    /// Read the token from the OS credential store; see the store-token
+    /// subcommand for saving it there.
+    Keyring,
+}
+
+/// Source of the bearer credential presented to SonarQube (or a gateway in
+/// front of it). Implementations are consulted on every request so that
+/// short-lived credentials stay fresh.
+#[async_trait::async_trait]
+pub trait AuthProvider: Send + Sync {
+    async fn token(&self) -> Result<String>;
+}
+
+/// Builds the provider selected by the configuration.
+pub fn from_config(config: &Config) -> Result<Box<dyn AuthProvider>> {
+    match config.auth_provider {
+        AuthProviderKind::Static => {
+            // A mounted secret is enough on its own: --token-file (or
+            // SONARQUBE_TOKEN_FILE) selects the file provider without also
+            // having to switch --auth-provider.
+            if let Some(path) = config.token_file.clone() {
+                return Ok(Box::new(FileTokenProvider { path }));
+            }
+            Ok(Box::new(StaticTokenProvider {
+                token: config.sonarqube_token.clone(),
+            }))
+        }
+        AuthProviderKind::File => {
+            let path = config.token_file.clone().ok_or_else(|| {
+                Error::Config("--token-file is required with --auth-provider file".to_string())
+            })?;
+            Ok(Box::new(FileTokenProvider { path }))
+        }
+        AuthProviderKind::Command => {
+            let command = config.token_command.clone().ok_or_else(|| {
+                Error::Config(
+                    "--token-command is required with --auth-provider command".to_string(),
+                )
+            })?;
+            Ok(Box::new(CommandTokenProvider { command }))
+        }
+        AuthProviderKind::Keyring => Ok(Box::new(KeyringTokenProvider {
+            account: config.sonarqube_url.clone(),
+        })),
+        AuthProviderKind::Oauth => {
+            let token_url = config.oauth_token_url.clone().ok_or_else(|| {
+                Error::Config(
+                    "--oauth-token-url is required with --auth-provider oauth".to_string(),
+                )
+            })?;
+            let client_id = config.oauth_client_id.clone().ok_or_else(|| {
+                Error::Config(
+                    "--oauth-client-id is required with --auth-provider oauth".to_string(),
+                )
+            })?;
+            let client_secret = config.oauth_client_secret.clone().ok_or_else(|| {
+                Error::Config(
+                    "--oauth-client-secret is required with --auth-provider oauth".to_string(),
+                )
+            })?;
+            Ok(Box::new(OAuthClientCredentialsProvider {
+                http: reqwest::Client::new(),
+                token_url,
+                client_id,
+                client_secret,
+                cached: Mutex::new(None),
+            }))
+        }
+    }
+}
+
+pub struct StaticTokenProvider {
+    pub token: String,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for StaticTokenProvider {
+    async fn token(&self) -> Result<String> {
+        Ok(self.token.clone())
+    }
+}
+
+pub struct FileTokenProvider {
+    pub path: PathBuf,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for FileTokenProvider {
+    async fn token(&self) -> Result<String> {
+        let contents = tokio::fs::read_to_string(&self.path).await?;
+        let token = contents.trim();
+        if token.is_empty() {
+            return Err(Error::Config(format!(
+                "token file {} is empty",
+                self.path.display()
+            )));
+        }
+        Ok(token.to_string())
+    }
+}
+
+pub struct CommandTokenProvider {
+    pub command: String,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for CommandTokenProvider {
+    async fn token(&self) -> Result<String> {
+        let output = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(Error::Config(format!(
+                "token command exited with {}",
+                output.status
+            )));
+        }
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if token.is_empty() {
+            return Err(Error::Config("token command produced no output".to_string()));
+        }
+        Ok(token)
+    }
+}
+
+/// Service name the credential store files tokens under.
+const KEYRING_SERVICE: &str = "sonarqube-mcp-server";
+
+/// Entry holding the token for one SonarQube server. The account is the
+/// server URL, so tokens for several servers coexist.
+fn keyring_entry(account: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, account)
+        .map_err(|err| Error::Config(format!("cannot access OS credential store: {err}")))
+}
+
+/// Saves the token for the configured server in the OS credential store;
+/// backs the store-token subcommand so tokens never sit in TOML files or
+/// shell profiles.
+pub fn store_token(config: &Config, token: &str) -> Result<()> {
+    keyring_entry(&config.sonarqube_url)?
+        .set_password(token)
+        .map_err(|err| Error::Config(format!("cannot store token: {err}")))
+}
+
+pub struct KeyringTokenProvider {
+    /// Credential-store account, i.e. the SonarQube URL.
+    pub account: String,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for KeyringTokenProvider {
+    async fn token(&self) -> Result<String> {
+        keyring_entry(&self.account)?.get_password().map_err(|err| {
+            Error::Config(format!(
+                "no token in the OS credential store for {}: {err}; save one with the store-token subcommand",
+                self.account
+            ))
+        })
+    }
+}
+
+/// Margin subtracted from `expires_in` so a token is refreshed before the
+/// gateway actually rejects it.
+const OAUTH_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+pub struct OAuthClientCredentialsProvider {
+    http: reqwest::Client,
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    cached: Mutex<Option<(String, Instant)>>,
+}
+
+#[derive(Deserialize)]
+struct OAuthTokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+#[async_trait::async_trait]
+impl AuthProvider for OAuthClientCredentialsProvider {
+    async fn token(&self) -> Result<String> {
+        if let Some((token, expires_at)) = self.cached.lock().expect("lock poisoned").clone() {
+            if Instant::now() < expires_at {
+                return Ok(token);
+            }
+        }
+        let response = self
+            .http
+            .post(&self.token_url)
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", &self.client_id),
+                ("client_secret", &self.client_secret),
+            ])
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Api {
+                status: status.as_u16(),
+                message: "OAuth token endpoint refused client credentials".to_string(),
+            });
+        }
+        let body: OAuthTokenResponse = response.json().await?;
+        let ttl = Duration::from_secs(body.expires_in.unwrap_or(300));
+        let expires_at = Instant::now() + ttl.saturating_sub(OAUTH_EXPIRY_MARGIN);
+        *self.cached.lock().expect("lock poisoned") =
+            Some((body.access_token.clone(), expires_at));
+        Ok(body.access_token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn static_provider_returns_configured_token() {
+        let provider = StaticTokenProvider {
+            token: "squ_abc".to_string(),
+        };
+        assert_eq!(provider.token().await.unwrap(), "squ_abc");
+    }
+
+    #[tokio::test]
+    async fn file_provider_trims_and_rejects_empty() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("sonarqube-mcp-auth-test-token");
+        tokio::fs::write(&path, "  squ_from_file\n").await.unwrap();
+        let provider = FileTokenProvider { path: path.clone() };
+        assert_eq!(provider.token().await.unwrap(), "squ_from_file");
+
+        tokio::fs::write(&path, "\n").await.unwrap();
+        assert!(provider.token().await.is_err());
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+
+    #[tokio::test]
+    async fn a_token_file_alone_selects_the_file_provider() {
+        use clap::Parser;
+        let path = std::env::temp_dir().join("sonarqube-mcp-auth-test-secret");
+        tokio::fs::write(&path, "squ_mounted\n").await.unwrap();
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+            "--token-file",
+            path.to_str().unwrap(),
+        ]);
+        let provider = from_config(&config).unwrap();
+        assert_eq!(provider.token().await.unwrap(), "squ_mounted");
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+
+    #[tokio::test]
+    async fn command_provider_uses_stdout() {
+        let provider = CommandTokenProvider {
+            command: "printf ' squ_from_cmd '".to_string(),
+        };
+        assert_eq!(provider.token().await.unwrap(), "squ_from_cmd");
+    }
+}
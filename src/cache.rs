@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::config::Config;
+
+/// TTL cache for GET responses, keyed by endpoint plus query string.
+/// Chatty clients tend to repeat identical tool calls in one conversation;
+/// caching the slow-moving data classes keeps that from hammering
+/// SonarQube. Only endpoints with a configured TTL are cached at all, and
+/// callers can bypass the cache per request (`cache: false`).
+pub struct ResponseCache {
+    entries: Mutex<HashMap<String, Entry>>,
+    ttl_projects: Duration,
+    ttl_metrics: Duration,
+    ttl_rules: Duration,
+}
+
+struct Entry {
+    value: Value,
+    inserted_at: Instant,
+}
+
+impl ResponseCache {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            ttl_projects: Duration::from_secs(config.cache_ttl_projects_seconds),
+            ttl_metrics: Duration::from_secs(config.cache_ttl_metrics_seconds),
+            ttl_rules: Duration::from_secs(config.cache_ttl_rules_seconds),
+        }
+    }
+
+    /// TTL for an endpoint, or None when the endpoint must not be cached.
+    /// A configured TTL of zero disables the data class.
+    pub fn ttl_for(&self, path: &str) -> Option<Duration> {
+        let ttl = match path {
+            "/api/projects/search" => self.ttl_projects,
+            "/api/measures/component" => self.ttl_metrics,
+            "/api/rules/search" | "/api/rules/show" | "/api/languages/list" => self.ttl_rules,
+            _ => return None,
+        };
+        (!ttl.is_zero()).then_some(ttl)
+    }
+
+    /// Stable cache key for an endpoint and its query parameters.
+    pub fn key(path: &str, query: &[(&str, String)]) -> String {
+        let mut key = path.to_string();
+        for (name, value) in query {
+            key.push('\u{1f}');
+            key.push_str(name);
+            key.push('=');
+            key.push_str(value);
+        }
+        key
+    }
+
+    /// Returns the cached value when present and younger than the TTL.
+    pub fn get(&self, key: &str, ttl: Duration) -> Option<Value> {
+        let entries = self.entries.lock().expect("cache poisoned");
+        entries
+            .get(key)
+            .filter(|entry| entry.inserted_at.elapsed() < ttl)
+            .map(|entry| entry.value.clone())
+    }
+
+    pub fn put(&self, key: String, value: Value) {
+        let mut entries = self.entries.lock().expect("cache poisoned");
+        // Opportunistically drop entries past the longest TTL so the map
+        // cannot grow without bound.
+        let horizon = self.ttl_projects.max(self.ttl_metrics).max(self.ttl_rules);
+        entries.retain(|_, entry| entry.inserted_at.elapsed() < horizon);
+        entries.insert(key, Entry { value, inserted_at: Instant::now() });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().expect("cache poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn test_cache() -> ResponseCache {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ]);
+        ResponseCache::from_config(&config)
+    }
+
+    #[test]
+    fn serves_fresh_entries_and_expires_stale_ones() {
+        let cache = test_cache();
+        let key = ResponseCache::key("/api/projects/search", &[("p", "1".to_string())]);
+        cache.put(key.clone(), serde_json::json!({"total": 3}));
+        assert_eq!(
+            cache.get(&key, Duration::from_secs(60)),
+            Some(serde_json::json!({"total": 3}))
+        );
+        assert_eq!(cache.get(&key, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn keys_distinguish_query_parameters() {
+        let a = ResponseCache::key("/api/projects/search", &[("p", "1".to_string())]);
+        let b = ResponseCache::key("/api/projects/search", &[("p", "2".to_string())]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn ttl_policy_follows_data_classes_and_zero_disables() {
+        let cache = test_cache();
+        assert_eq!(cache.ttl_for("/api/projects/search"), Some(Duration::from_secs(300)));
+        assert_eq!(cache.ttl_for("/api/measures/component"), Some(Duration::from_secs(60)));
+        assert_eq!(cache.ttl_for("/api/rules/search"), Some(Duration::from_secs(3600)));
+        assert_eq!(cache.ttl_for("/api/issues/search"), None);
+
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+            "--cache-ttl-projects-seconds",
+            "0",
+        ]);
+        let disabled = ResponseCache::from_config(&config);
+        assert_eq!(disabled.ttl_for("/api/projects/search"), None);
+    }
+}
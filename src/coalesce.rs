@@ -0,0 +1,104 @@
+//! Singleflight-style deduplication of identical in-flight requests.
+//!
+//! Agent clients routinely fan out tool calls, and several of them often
+//! resolve to the same endpoint with the same parameters at the same
+//! moment. Coalescing those into one upstream HTTP request cuts load on
+//! SonarQube and keeps a fan-out from burning through the rate-limit
+//! quota. Keys match the response cache (path plus ordered query), so two
+//! calls coalesce exactly when they would share a cache entry.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+
+/// One in-flight fetch. The first caller to run `get_or_try_init` performs
+/// the request; everyone else awaiting the cell shares its result. On
+/// failure the cell stays empty, so a waiter retries rather than all
+/// callers inheriting one transient error.
+pub type Flight = Arc<tokio::sync::OnceCell<Value>>;
+
+/// Map of request key to in-flight fetch.
+#[derive(Default)]
+pub struct RequestCoalescer {
+    in_flight: Mutex<HashMap<String, Flight>>,
+}
+
+impl RequestCoalescer {
+    /// Joins the in-flight request for `key`, starting one if none exists.
+    pub fn join(&self, key: &str) -> Flight {
+        let mut map = self.in_flight.lock().expect("coalescer poisoned");
+        Arc::clone(map.entry(key.to_string()).or_default())
+    }
+
+    /// Marks a flight as landed so later calls fetch fresh data. Only the
+    /// given flight is removed; a newer flight under the same key stays.
+    pub fn complete(&self, key: &str, flight: &Flight) {
+        let mut map = self.in_flight.lock().expect("coalescer poisoned");
+        if map.get(key).is_some_and(|current| Arc::ptr_eq(current, flight)) {
+            map.remove(key);
+        }
+    }
+
+    /// Number of requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().expect("coalescer poisoned").len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_joins_share_one_flight_until_it_completes() {
+        let coalescer = RequestCoalescer::default();
+        let first = coalescer.join("/api/projects/search\u{1f}ps=100");
+        let second = coalescer.join("/api/projects/search\u{1f}ps=100");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(coalescer.in_flight(), 1);
+
+        coalescer.complete("/api/projects/search\u{1f}ps=100", &first);
+        assert_eq!(coalescer.in_flight(), 0);
+        let third = coalescer.join("/api/projects/search\u{1f}ps=100");
+        assert!(!Arc::ptr_eq(&first, &third));
+    }
+
+    #[test]
+    fn completing_a_stale_flight_leaves_the_current_one_alone() {
+        let coalescer = RequestCoalescer::default();
+        let stale = coalescer.join("key");
+        coalescer.complete("key", &stale);
+        let current = coalescer.join("key");
+        // A caller that joined before the first flight landed completes
+        // late; the replacement flight must survive it.
+        coalescer.complete("key", &stale);
+        assert!(Arc::ptr_eq(&coalescer.join("key"), &current));
+    }
+
+    #[tokio::test]
+    async fn followers_reuse_the_leader_result() {
+        let coalescer = Arc::new(RequestCoalescer::default());
+        let flight = coalescer.join("key");
+        let fetches = Arc::new(std::sync::atomic::AtomicU32::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let flight = Arc::clone(&flight);
+            let fetches = Arc::clone(&fetches);
+            handles.push(tokio::spawn(async move {
+                flight
+                    .get_or_init(|| async {
+                        fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                        Value::from("payload")
+                    })
+                    .await
+                    .clone()
+            }));
+        }
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), Value::from("payload"));
+        }
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// How many failed requests are kept for support bundles.
+const MAX_RECENT_FAILURES: usize = 50;
+
+/// One failed SonarQube request, kept for support bundles. Only the path and
+/// error text are stored — never query values, tokens or response bodies.
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureRecord {
+    pub at_unix: u64,
+    pub path: String,
+    pub error: String,
+}
+
+/// Lightweight request accounting shared between the client and the support
+/// bundle tool.
+pub struct Diagnostics {
+    started: Instant,
+    requests: AtomicU64,
+    failures: AtomicU64,
+    recent_failures: Mutex<VecDeque<FailureRecord>>,
+    deprecations: Mutex<BTreeSet<String>>,
+}
+
+impl Default for Diagnostics {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            requests: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            recent_failures: Mutex::new(VecDeque::new()),
+            deprecations: Mutex::new(BTreeSet::new()),
+        }
+    }
+}
+
+impl Diagnostics {
+    pub fn record_success(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_failure(&self, path: &str, error: &str) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        let record = FailureRecord {
+            at_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            path: path.to_string(),
+            error: error.to_string(),
+        };
+        let mut recent = self.recent_failures.lock().expect("lock poisoned");
+        if recent.len() == MAX_RECENT_FAILURES {
+            recent.pop_front();
+        }
+        recent.push_back(record);
+    }
+
+    /// Records a deprecation notice seen on a SonarQube response. Returns
+    /// true the first time a given notice is seen, so callers can warn once
+    /// instead of on every request.
+    pub fn record_deprecation(&self, notice: &str) -> bool {
+        self.deprecations
+            .lock()
+            .expect("lock poisoned")
+            .insert(notice.to_string())
+    }
+
+    pub fn snapshot(&self) -> Value {
+        let recent: Vec<FailureRecord> = self
+            .recent_failures
+            .lock()
+            .expect("lock poisoned")
+            .iter()
+            .cloned()
+            .collect();
+        let deprecations: Vec<String> = self
+            .deprecations
+            .lock()
+            .expect("lock poisoned")
+            .iter()
+            .cloned()
+            .collect();
+        json!({
+            "uptime_seconds": self.started.elapsed().as_secs(),
+            "sonarqube_requests_total": self.requests.load(Ordering::Relaxed),
+            "sonarqube_request_failures_total": self.failures.load(Ordering::Relaxed),
+            "recent_failures": recent,
+            "deprecations": deprecations,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_and_bounds_failures() {
+        let diagnostics = Diagnostics::default();
+        diagnostics.record_success();
+        for i in 0..(MAX_RECENT_FAILURES + 5) {
+            diagnostics.record_failure("/api/issues/search", &format!("error {i}"));
+        }
+        let snapshot = diagnostics.snapshot();
+        assert_eq!(
+            snapshot["sonarqube_requests_total"].as_u64().unwrap(),
+            (MAX_RECENT_FAILURES + 6) as u64
+        );
+        let recent = snapshot["recent_failures"].as_array().unwrap();
+        assert_eq!(recent.len(), MAX_RECENT_FAILURES);
+        // Oldest entries are evicted first.
+        assert_eq!(recent[0]["error"], "error 5");
+    }
+
+    #[test]
+    fn deduplicates_deprecation_notices() {
+        let diagnostics = Diagnostics::default();
+        assert!(diagnostics.record_deprecation("/api/issues/search: componentKeys"));
+        assert!(!diagnostics.record_deprecation("/api/issues/search: componentKeys"));
+        let snapshot = diagnostics.snapshot();
+        assert_eq!(snapshot["deprecations"].as_array().unwrap().len(), 1);
+    }
+}
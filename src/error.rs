@@ -0,0 +1,39 @@
+/// Errors surfaced by the server, both from the SonarQube Web API and from
+/// local processing of tool calls.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("HTTP request failed: {}", crate::redaction::redact_secrets(&.0.to_string()))]
+    Http(#[from] reqwest::Error),
+
+    #[error("SonarQube API error (HTTP {status}): {}", crate::redaction::redact_secrets(message))]
+    Api { status: u16, message: String },
+
+    #[error("project not found: {0}")]
+    ProjectNotFound(String),
+
+    #[error("invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    #[error("unknown tool: {0}")]
+    UnknownTool(String),
+
+    #[error("outbound request blocked by allowlist: {}", crate::redaction::redact_secrets(.0))]
+    OutboundBlocked(String),
+
+    #[error("configuration error: {}", crate::redaction::redact_secrets(.0))]
+    Config(String),
+
+    #[error("administrative write operations are disabled; restart the server with --allow-admin-operations to enable them")]
+    AdminOperationsDisabled,
+
+    #[error("SonarQube appears to be unreachable; the circuit breaker is short-circuiting requests for another {0}s")]
+    CircuitOpen(u64),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,118 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+
+use crate::error::Result;
+use crate::server_context::ServerContext;
+
+/// Metrics published per watched project, as (SonarQube metric key,
+/// Prometheus gauge name, help text).
+const EXPORTED_MEASURES: &[(&str, &str, &str)] = &[
+    ("coverage", "sonarqube_coverage_percent", "Test coverage of the project"),
+    ("bugs", "sonarqube_bugs", "Number of open bugs"),
+    ("vulnerabilities", "sonarqube_vulnerabilities", "Number of open vulnerabilities"),
+    ("code_smells", "sonarqube_code_smells", "Number of open code smells"),
+];
+
+const GATE_METRIC: &str = "sonarqube_quality_gate_passing";
+
+/// Runs the optional Prometheus exporter. Each scrape of `/metrics` fetches
+/// the current measures and quality gate status for every watched project,
+/// so existing Grafana dashboards can consume code-quality data without a
+/// separate collector.
+pub async fn serve(ctx: Arc<ServerContext>, addr: SocketAddr) -> Result<()> {
+    let app = Router::new().route("/metrics", get(scrape)).with_state(ctx);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("metrics exporter bound on {addr}");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn scrape(State(ctx): State<Arc<ServerContext>>) -> impl IntoResponse {
+    let body = render(&ctx).await;
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        body,
+    )
+}
+
+/// Renders the exposition text for all watched projects. Projects that fail
+/// to resolve are skipped with a warning so one broken project does not
+/// empty the whole scrape.
+async fn render(ctx: &ServerContext) -> String {
+    let mut out = String::new();
+    for (_, name, help) in EXPORTED_MEASURES {
+        out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} gauge\n"));
+    }
+    out.push_str(&format!(
+        "# HELP {GATE_METRIC} Whether the project's quality gate is passing (1) or failing (0)\n\
+         # TYPE {GATE_METRIC} gauge\n"
+    ));
+
+    let metric_keys: Vec<String> = EXPORTED_MEASURES
+        .iter()
+        .map(|(key, _, _)| key.to_string())
+        .collect();
+    for project in ctx.watchlist.projects() {
+        match ctx.client.get_measures(&project, &metric_keys).await {
+            Ok(response) => {
+                for measure in &response.component.measures {
+                    let Some((_, name, _)) = EXPORTED_MEASURES
+                        .iter()
+                        .find(|(key, _, _)| *key == measure.metric)
+                    else {
+                        continue;
+                    };
+                    if let Some(value) = measure.value.as_deref().and_then(|v| v.parse::<f64>().ok())
+                    {
+                        out.push_str(&sample(name, &project, value));
+                    }
+                }
+            }
+            Err(err) => {
+                tracing::warn!("metrics exporter: skipping measures for {project}: {err}");
+                continue;
+            }
+        }
+        match ctx.client.quality_gate_status(&project).await {
+            Ok(response) => {
+                let passing = if response.project_status.status == "OK" { 1.0 } else { 0.0 };
+                out.push_str(&sample(GATE_METRIC, &project, passing));
+            }
+            Err(err) => {
+                tracing::warn!("metrics exporter: skipping gate status for {project}: {err}");
+            }
+        }
+    }
+    out
+}
+
+/// One exposition line, with the project key escaped per the Prometheus
+/// text format.
+fn sample(name: &str, project: &str, value: f64) -> String {
+    let escaped = project.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("{name}{{project=\"{escaped}\"}} {value}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_lines_follow_the_text_format() {
+        assert_eq!(
+            sample("sonarqube_bugs", "demo", 3.0),
+            "sonarqube_bugs{project=\"demo\"} 3\n"
+        );
+        assert_eq!(
+            sample("sonarqube_bugs", "we\"ird", 0.5),
+            "sonarqube_bugs{project=\"we\\\"ird\"} 0.5\n"
+        );
+    }
+}
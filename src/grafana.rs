@@ -0,0 +1,291 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::server_context::ServerContext;
+
+/// Metrics offered to Grafana for each watched project.
+const OFFERED_METRICS: &[&str] = &["coverage", "bugs", "vulnerabilities", "code_smells", "ncloc"];
+
+/// Runs the optional Grafana datasource endpoint, implementing the simple
+/// JSON datasource contract (`/`, `/search`, `/query`, `/annotations`).
+/// Targets are `project_key:metric` pairs backed by the measures history
+/// API, so dashboards can chart metric trends without direct SonarQube
+/// access. The data routes honor the same --api-key gate as the MCP
+/// network transports and only serve watched projects, so the listener
+/// exposes no more than `/search` advertises.
+pub async fn serve(ctx: Arc<ServerContext>, addr: SocketAddr) -> Result<()> {
+    let app = Router::new()
+        .route("/", get(|| async { "OK" }))
+        .route("/search", post(search))
+        .route("/query", post(query))
+        .route("/annotations", post(annotations))
+        .with_state(ctx);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("grafana datasource endpoint bound on {addr}");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+/// The API-key gate shared with the MCP network transports; without it
+/// anyone who can reach the port could read measures for any project
+/// visible to the server's token.
+fn authorize(ctx: &ServerContext, headers: &HeaderMap) -> std::result::Result<(), StatusCode> {
+    if crate::mcp::access::authorized(&ctx.config, headers) {
+        Ok(())
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
+/// Lists the selectable targets: every watched project crossed with the
+/// offered metrics.
+async fn search(
+    State(ctx): State<Arc<ServerContext>>,
+    headers: HeaderMap,
+) -> std::result::Result<Json<Value>, StatusCode> {
+    authorize(&ctx, &headers)?;
+    let mut targets = Vec::new();
+    for project in ctx.watchlist.projects() {
+        for metric in OFFERED_METRICS {
+            targets.push(format!("{project}:{metric}"));
+        }
+    }
+    Ok(Json(json!(targets)))
+}
+
+/// Serves time series for the requested targets from
+/// `/api/measures/search_history`.
+async fn query(
+    State(ctx): State<Arc<ServerContext>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> std::result::Result<Json<Value>, StatusCode> {
+    authorize(&ctx, &headers)?;
+    let from = body["range"]["from"].as_str().unwrap_or_default().to_string();
+    let to = body["range"]["to"].as_str().unwrap_or_default().to_string();
+    let watched = ctx.watchlist.projects();
+    let mut series = Vec::new();
+    for target in body["targets"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+        let Some(target) = target["target"].as_str() else {
+            continue;
+        };
+        let Some((project, metric)) = target.split_once(':') else {
+            tracing::warn!("grafana query target {target:?} is not project:metric");
+            continue;
+        };
+        // Only what /search advertises is served, so the listener cannot
+        // be used to read arbitrary projects the server's token can see.
+        if !watched.iter().any(|watched| watched == project) {
+            tracing::warn!("grafana query for unwatched project {project:?} refused");
+            continue;
+        }
+        let mut query = vec![
+            ("component", project.to_string()),
+            ("metrics", metric.to_string()),
+            ("ps", "1000".to_string()),
+        ];
+        if !from.is_empty() {
+            query.push(("from", from.clone()));
+        }
+        if !to.is_empty() {
+            query.push(("to", to.clone()));
+        }
+        let history: Value = match ctx
+            .client
+            .get("/api/measures/search_history", &query)
+            .await
+        {
+            Ok(history) => history,
+            Err(err) => {
+                tracing::warn!("grafana query for {target} failed: {err}");
+                continue;
+            }
+        };
+        let datapoints: Vec<Value> = history["measures"][0]["history"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|point| {
+                let value = point["value"].as_str()?.parse::<f64>().ok()?;
+                let time = epoch_ms(point["date"].as_str()?)?;
+                Some(json!([value, time]))
+            })
+            .collect();
+        series.push(json!({"target": target, "datapoints": datapoints}));
+    }
+    Ok(Json(json!(series)))
+}
+
+/// Serves quality gate events as annotations. The annotation query is the
+/// project key.
+async fn annotations(
+    State(ctx): State<Arc<ServerContext>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> std::result::Result<Json<Value>, StatusCode> {
+    authorize(&ctx, &headers)?;
+    let annotation = body["annotation"].clone();
+    let Some(project) = annotation["query"].as_str().filter(|q| !q.is_empty()) else {
+        return Ok(Json(json!([])));
+    };
+    if !ctx.watchlist.projects().iter().any(|watched| watched == project) {
+        tracing::warn!("grafana annotations for unwatched project {project:?} refused");
+        return Ok(Json(json!([])));
+    }
+    let mut query = vec![
+        ("project", project.to_string()),
+        ("category", "QUALITY_GATE".to_string()),
+        ("ps", "100".to_string()),
+    ];
+    if let Some(from) = body["range"]["from"].as_str() {
+        query.push(("from", from.to_string()));
+    }
+    let analyses: Value = match ctx
+        .client
+        .get("/api/project_analyses/search", &query)
+        .await
+    {
+        Ok(analyses) => analyses,
+        Err(err) => {
+            tracing::warn!("grafana annotations for {project} failed: {err}");
+            return Ok(Json(json!([])));
+        }
+    };
+    let events: Vec<Value> = analyses["analyses"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|analysis| {
+            let time = epoch_ms(analysis["date"].as_str()?)?;
+            let event = analysis["events"]
+                .as_array()?
+                .iter()
+                .find(|e| e["category"] == "QUALITY_GATE")?;
+            Some(json!({
+                "annotation": annotation,
+                "time": time,
+                "title": event["name"],
+                "text": format!("Quality gate event on {project}"),
+            }))
+        })
+        .collect();
+    Ok(Json(json!(events)))
+}
+
+/// Parses a SonarQube/ISO timestamp ("2024-05-01T12:00:00+0200",
+/// "...Z", or with fractional seconds) into epoch milliseconds. Avoids a
+/// date-time dependency for the one place that needs numeric timestamps.
+fn epoch_ms(date: &str) -> Option<i64> {
+    let (date_part, rest) = date.split_once('T')?;
+    let mut numbers = date_part.splitn(3, '-');
+    let year: i64 = numbers.next()?.parse().ok()?;
+    let month: i64 = numbers.next()?.parse().ok()?;
+    let day: i64 = numbers.next()?.parse().ok()?;
+
+    // Split the time from an optional zone suffix (Z, +hhmm, +hh:mm).
+    let (time_part, offset_minutes) = match rest.find(['Z', 'z', '+']) {
+        Some(i) => {
+            let (time, zone) = rest.split_at(i);
+            (time, zone_offset_minutes(zone)?)
+        }
+        None => match rest.rfind('-') {
+            Some(i) => {
+                let (time, zone) = rest.split_at(i);
+                (time, zone_offset_minutes(zone)?)
+            }
+            None => (rest, 0),
+        },
+    };
+    let mut parts = time_part.splitn(3, ':');
+    let hour: i64 = parts.next()?.parse().ok()?;
+    let minute: i64 = parts.next()?.parse().ok()?;
+    let second: f64 = parts.next().unwrap_or("0").parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let seconds = days * 86_400 + hour * 3_600 + minute * 60 - i64::from(offset_minutes) * 60;
+    Some(seconds * 1_000 + (second * 1_000.0) as i64)
+}
+
+fn zone_offset_minutes(zone: &str) -> Option<i32> {
+    match zone {
+        "Z" | "z" | "" => Some(0),
+        _ => {
+            let (sign, digits) = zone.split_at(1);
+            let digits = digits.replace(':', "");
+            if digits.len() != 4 {
+                return None;
+            }
+            let hours: i32 = digits[..2].parse().ok()?;
+            let minutes: i32 = digits[2..].parse().ok()?;
+            let total = hours * 60 + minutes;
+            Some(if sign == "-" { -total } else { total })
+        }
+    }
+}
+
+/// Days since the Unix epoch for a proleptic Gregorian date
+/// (Howard Hinnant's days_from_civil).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    fn context(args: &[&str]) -> Arc<ServerContext> {
+        let mut full = vec!["sonarqube-mcp-server", "--sonarqube-url", "http://localhost:9000"];
+        full.extend_from_slice(args);
+        let config = Config::parse_from(full);
+        Arc::new(ServerContext::new(config).expect("context"))
+    }
+
+    #[tokio::test]
+    async fn data_routes_require_the_configured_api_key() {
+        let ctx = context(&["--api-key", "sk-team"]);
+        let refused = search(State(Arc::clone(&ctx)), HeaderMap::new()).await;
+        assert_eq!(refused.unwrap_err(), StatusCode::UNAUTHORIZED);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", "sk-team".parse().unwrap());
+        assert!(search(State(ctx), headers).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn annotations_only_serve_watched_projects() {
+        let ctx = context(&["--watch-project", "demo"]);
+        let body = json!({"annotation": {"query": "secret-project"}, "range": {}});
+        let events = annotations(State(ctx), HeaderMap::new(), Json(body))
+            .await
+            .expect("authorized without a configured key");
+        assert_eq!(events.0, json!([]));
+    }
+
+    #[test]
+    fn parses_utc_offset_and_fractional_timestamps() {
+        assert_eq!(epoch_ms("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(epoch_ms("2024-05-01T12:00:00Z"), Some(1_714_564_800_000));
+        // +0200 is two hours earlier in UTC.
+        assert_eq!(epoch_ms("2024-05-01T14:00:00+0200"), Some(1_714_564_800_000));
+        assert_eq!(epoch_ms("2024-05-01T12:00:00.500Z"), Some(1_714_564_800_500));
+        assert_eq!(epoch_ms("2024-05-01T10:00:00-02:00"), Some(1_714_564_800_000));
+        assert_eq!(epoch_ms("not a date"), None);
+    }
+}
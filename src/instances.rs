@@ -0,0 +1,164 @@
+//! Named SonarQube instances for deployments that talk to more than one
+//! server — typically SonarCloud next to an on-prem installation. A TOML
+//! file (`--instances-config`) declares one `[sonarqube.<name>]` table per
+//! instance with its own URL, token and organization; every tool then
+//! accepts an `instance` argument selecting one of them, falling back to
+//! `--default-instance` or the primary configuration.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::server_context::{ServerContext, SessionCredentials};
+
+/// One named instance's connection settings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstanceDef {
+    pub url: String,
+    pub token: String,
+    /// Organization key, for SonarCloud instances.
+    pub organization: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstancesFile {
+    #[serde(default)]
+    sonarqube: BTreeMap<String, InstanceDef>,
+}
+
+/// All configured instances. Contexts are derived lazily on first use and
+/// cached, so unused instances cost nothing and repeated calls share one
+/// client (and its cache) per instance.
+pub struct InstanceRegistry {
+    definitions: BTreeMap<String, InstanceDef>,
+    contexts: Mutex<BTreeMap<String, Arc<ServerContext>>>,
+}
+
+impl InstanceRegistry {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path).map_err(|err| {
+            Error::Config(format!(
+                "cannot read instances config {}: {err}",
+                path.display()
+            ))
+        })?;
+        Self::from_toml_str(&raw)
+            .map_err(|err| Error::Config(format!("in {}: {err}", path.display())))
+    }
+
+    fn from_toml_str(raw: &str) -> std::result::Result<Self, String> {
+        let file: InstancesFile = toml::from_str(raw).map_err(|err| err.to_string())?;
+        if file.sonarqube.is_empty() {
+            return Err("instances config declares no [sonarqube.<name>] tables".to_string());
+        }
+        for (name, instance) in &file.sonarqube {
+            if instance.url.trim().is_empty() || instance.token.trim().is_empty() {
+                return Err(format!("instance {name:?} must set both url and token"));
+            }
+        }
+        Ok(Self {
+            definitions: file.sonarqube,
+            contexts: Mutex::new(BTreeMap::new()),
+        })
+    }
+
+    /// Instance names, for diagnostics and error messages. Tokens stay
+    /// private.
+    pub fn names(&self) -> Vec<&str> {
+        self.definitions.keys().map(|name| name.as_str()).collect()
+    }
+
+    /// The context speaking to the named instance, derived from the host
+    /// context on first use. Unknown names are an argument error listing
+    /// what is configured.
+    pub fn resolve(&self, host: &ServerContext, name: &str) -> Result<Arc<ServerContext>> {
+        if let Some(ctx) = self
+            .contexts
+            .lock()
+            .expect("instances lock poisoned")
+            .get(name)
+        {
+            return Ok(Arc::clone(ctx));
+        }
+        let definition = self.definitions.get(name).ok_or_else(|| {
+            Error::InvalidArguments(format!(
+                "unknown instance {name:?}; configured: {}",
+                self.names().join(", ")
+            ))
+        })?;
+        let derived = host.with_session_credentials(&SessionCredentials {
+            url: Some(definition.url.clone()),
+            token: Some(definition.token.clone()),
+            organization: definition.organization.clone(),
+        })?;
+        let derived = Arc::new(derived);
+        self.contexts
+            .lock()
+            .expect("instances lock poisoned")
+            .insert(name.to_string(), Arc::clone(&derived));
+        Ok(derived)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    const SAMPLE: &str = r#"
+[sonarqube.prod]
+url = "https://sonarcloud.io"
+token = "squ_prod"
+organization = "acme"
+
+[sonarqube.staging]
+url = "http://sonarqube.staging.internal:9000"
+token = "squ_staging"
+"#;
+
+    fn host() -> ServerContext {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ]);
+        ServerContext::new(config).expect("context")
+    }
+
+    #[test]
+    fn resolves_named_instances_and_caches_their_contexts() {
+        let registry = InstanceRegistry::from_toml_str(SAMPLE).unwrap();
+        assert_eq!(registry.names(), vec!["prod", "staging"]);
+
+        let host = host();
+        let prod = registry.resolve(&host, "prod").unwrap();
+        assert_eq!(prod.config.sonarqube_url, "https://sonarcloud.io");
+        assert_eq!(prod.config.sonarqube_token, "squ_prod");
+        assert_eq!(prod.config.organization.as_deref(), Some("acme"));
+        let again = registry.resolve(&host, "prod").unwrap();
+        assert!(Arc::ptr_eq(&prod, &again));
+
+        let unknown = match registry.resolve(&host, "qa") {
+            Err(err) => err,
+            Ok(_) => panic!("unknown instance must not resolve"),
+        };
+        assert!(unknown.to_string().contains("prod, staging"));
+    }
+
+    #[test]
+    fn rejects_empty_files_and_missing_credentials() {
+        assert!(InstanceRegistry::from_toml_str("").is_err());
+        let missing = "[sonarqube.prod]\nurl = \"\"\ntoken = \"t\"\n";
+        let message = match InstanceRegistry::from_toml_str(missing) {
+            Err(message) => message,
+            Ok(_) => panic!("missing url must not parse"),
+        };
+        assert!(message.contains("must set both"));
+    }
+}
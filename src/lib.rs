@@ -0,0 +1,24 @@
+pub mod alerts;
+pub mod audit;
+pub mod auth;
+pub mod cache;
+pub mod coalesce;
+pub mod config;
+pub mod diagnostics;
+pub mod error;
+pub mod exporter;
+pub mod grafana;
+pub mod instances;
+pub mod mcp;
+pub mod prompts;
+pub mod redaction;
+pub mod resources;
+pub mod scoring;
+pub mod server_context;
+pub mod severity_map;
+pub mod sonarqube;
+pub mod tenants;
+pub mod text;
+pub mod tools;
+pub mod watch;
+pub mod webhook;
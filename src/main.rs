@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use clap::Parser;
+
+use sonarqube_mcp_server::config::{Command, Config, Transport};
+use sonarqube_mcp_server::mcp::server::McpServer;
+use sonarqube_mcp_server::server_context::ServerContext;
+use sonarqube_mcp_server::webhook;
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let config = Config::parse();
+
+    if let Some(Command::StoreToken) = config.command {
+        let token = if config.sonarqube_token.is_empty() {
+            let mut line = String::new();
+            if let Err(err) = std::io::stdin().read_line(&mut line) {
+                tracing::error!("cannot read token from stdin: {err}");
+                std::process::exit(1);
+            }
+            line.trim().to_string()
+        } else {
+            config.sonarqube_token.clone()
+        };
+        if token.is_empty() {
+            tracing::error!("no token given; pass --sonarqube-token or pipe it on stdin");
+            std::process::exit(1);
+        }
+        match sonarqube_mcp_server::auth::store_token(&config, &token) {
+            Ok(()) => {
+                tracing::info!("token stored for {}", config.sonarqube_url);
+                return;
+            }
+            Err(err) => {
+                tracing::error!("{err}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let ctx = match ServerContext::new(config) {
+        Ok(ctx) => Arc::new(ctx),
+        Err(err) => {
+            tracing::error!("failed to initialize: {err}");
+            std::process::exit(1);
+        }
+    };
+
+    if ctx.config.validate_auth_on_start {
+        match sonarqube_mcp_server::tools::validate_auth::check(&ctx).await {
+            Ok(report) => tracing::info!(
+                "authenticated against SonarQube as {}",
+                report["login"].as_str().unwrap_or("<unknown>")
+            ),
+            Err(err) => {
+                tracing::error!("authentication check failed: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if ctx.watchlist.is_dynamic() {
+        tokio::spawn(sonarqube_mcp_server::watch::run_refresher(Arc::clone(&ctx)));
+    }
+
+    if ctx.config.alert_webhook_url.is_some() {
+        tokio::spawn(sonarqube_mcp_server::alerts::run_poller(Arc::clone(&ctx)));
+    }
+
+    if let Some(addr) = ctx.config.grafana_listen {
+        let grafana_ctx = Arc::clone(&ctx);
+        tokio::spawn(async move {
+            if let Err(err) = sonarqube_mcp_server::grafana::serve(grafana_ctx, addr).await {
+                tracing::error!("grafana datasource endpoint failed: {err}");
+            }
+        });
+    }
+
+    if let Some(addr) = ctx.config.metrics_listen {
+        let exporter_ctx = Arc::clone(&ctx);
+        tokio::spawn(async move {
+            if let Err(err) = sonarqube_mcp_server::exporter::serve(exporter_ctx, addr).await {
+                tracing::error!("metrics exporter failed: {err}");
+            }
+        });
+    }
+
+    if let Some(addr) = ctx.config.webhook_listen {
+        let webhook_ctx = Arc::clone(&ctx);
+        tokio::spawn(async move {
+            if let Err(err) = webhook::serve(webhook_ctx, addr).await {
+                tracing::error!("webhook listener failed: {err}");
+            }
+        });
+    }
+
+    match ctx.config.transport {
+        Transport::Stdio => {
+            tracing::info!("starting sonarqube-mcp-server on stdio");
+            if let Err(err) = McpServer::new(ctx).run_stdio().await {
+                tracing::error!("server terminated with error: {err}");
+                std::process::exit(1);
+            }
+        }
+        Transport::Unix => {
+            let Some(path) = ctx.config.socket.clone() else {
+                tracing::error!("--transport unix requires --socket");
+                std::process::exit(1);
+            };
+            tracing::info!("starting sonarqube-mcp-server on unix socket");
+            if let Err(err) = sonarqube_mcp_server::mcp::unix::serve(ctx, path).await {
+                tracing::error!("server terminated with error: {err}");
+                std::process::exit(1);
+            }
+        }
+        Transport::Http | Transport::Ws => {
+            let Some(addr) = ctx.config.listen else {
+                tracing::error!("network transports require --listen");
+                std::process::exit(1);
+            };
+            let result = match ctx.config.transport {
+                Transport::Http => {
+                    tracing::info!("starting sonarqube-mcp-server on http");
+                    sonarqube_mcp_server::mcp::http::serve(ctx, addr).await
+                }
+                _ => {
+                    tracing::info!("starting sonarqube-mcp-server on websocket");
+                    sonarqube_mcp_server::mcp::ws::serve(ctx, addr).await
+                }
+            };
+            if let Err(err) = result {
+                tracing::error!("server terminated with error: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use axum::http::HeaderMap;
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+use crate::server_context::ServerContext;
+
+/// Access control for the network transports. A team-shared server would
+/// otherwise accept MCP requests from anyone who can reach the port.
+///
+/// Clients present the configured key either as `Authorization: Bearer
+/// <key>` or in an `X-Api-Key` header. When no key is configured the
+/// transports stay open, which is only sensible on a loopback listener.
+pub(crate) fn authorized(config: &Config, headers: &HeaderMap) -> bool {
+    let Some(expected) = &config.api_key else {
+        return true;
+    };
+    presented_key(headers)
+        .map(|presented| constant_time_eq(presented.as_bytes(), expected.as_bytes()))
+        .unwrap_or(false)
+}
+
+/// The context a network client operates in, or None to refuse the
+/// request. Without a tenant registry this is the plain `authorized`
+/// check and the shared host context. With `--tenants-config`, the
+/// presented key selects the tenant and yields that tenant's isolated
+/// context; unknown or missing keys are refused outright, so a tenant
+/// deployment never falls back to the server-wide credential.
+pub(crate) fn client_context(
+    host: &Arc<ServerContext>,
+    headers: &HeaderMap,
+) -> Option<Arc<ServerContext>> {
+    let Some(registry) = &host.tenants else {
+        return authorized(&host.config, headers).then(|| Arc::clone(host));
+    };
+    let presented = presented_key(headers)?;
+    // Scanned with the same constant-time comparison as the global key,
+    // rather than looked up by the secret directly.
+    let tenant = registry
+        .tenants()
+        .find(|tenant| constant_time_eq(presented.as_bytes(), tenant.api_key.as_bytes()))?;
+    match registry.context_for(host, tenant) {
+        Ok(ctx) => Some(ctx),
+        Err(err) => {
+            tracing::error!("cannot derive a context for tenant {:?}: {err}", tenant.name);
+            None
+        }
+    }
+}
+
+/// The key offered by the request, from whichever header carries it.
+fn presented_key(headers: &HeaderMap) -> Option<&str> {
+    if let Some(bearer) = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    {
+        return Some(bearer);
+    }
+    headers.get("x-api-key").and_then(|v| v.to_str().ok())
+}
+
+/// Compares the SHA-256 digests of both values byte by byte without an
+/// early exit, so response timing reveals nothing about how much of the
+/// key matched (or how long it is).
+fn constant_time_eq(presented: &[u8], expected: &[u8]) -> bool {
+    let presented = Sha256::digest(presented);
+    let expected = Sha256::digest(expected);
+    presented
+        .iter()
+        .zip(expected.iter())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn config(args: &[&str]) -> Config {
+        let mut full = vec!["sonarqube-mcp-server", "--sonarqube-url", "http://localhost:9000"];
+        full.extend_from_slice(args);
+        Config::parse_from(full)
+    }
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn accepts_the_key_as_bearer_or_api_key_header() {
+        let config = config(&["--api-key", "sk-team"]);
+        assert!(authorized(&config, &headers("authorization", "Bearer sk-team")));
+        assert!(authorized(&config, &headers("x-api-key", "sk-team")));
+    }
+
+    #[test]
+    fn refuses_missing_wrong_and_malformed_keys() {
+        let config = config(&["--api-key", "sk-team"]);
+        assert!(!authorized(&config, &HeaderMap::new()));
+        assert!(!authorized(&config, &headers("x-api-key", "sk-other")));
+        assert!(!authorized(&config, &headers("authorization", "sk-team")));
+        assert!(!authorized(&config, &headers("authorization", "Bearer sk-teamer")));
+    }
+
+    #[test]
+    fn stays_open_when_no_key_is_configured() {
+        assert!(authorized(&config(&[]), &HeaderMap::new()));
+    }
+
+    #[test]
+    fn without_tenants_client_context_is_the_shared_host_context() {
+        let host = Arc::new(ServerContext::new(config(&[])).expect("context"));
+        let ctx = client_context(&host, &HeaderMap::new()).expect("open listener admits");
+        assert!(Arc::ptr_eq(&host, &ctx));
+    }
+
+    #[test]
+    fn tenant_keys_select_isolated_contexts_and_unknown_keys_are_refused() {
+        let path = std::env::temp_dir().join("sonarqube-mcp-access-tenants.toml");
+        std::fs::write(
+            &path,
+            "[[tenant]]\nname = \"payments\"\napi_key = \"key-payments\"\nsonarqube_token = \"squ_aaa\"\n",
+        )
+        .unwrap();
+        let config = config(&["--tenants-config", path.to_str().unwrap()]);
+        let host = Arc::new(ServerContext::new(config).expect("context"));
+
+        let ctx = client_context(&host, &headers("x-api-key", "key-payments"))
+            .expect("tenant key admits");
+        assert_eq!(ctx.config.sonarqube_token, "squ_aaa");
+        assert_eq!(ctx.tenant.as_ref().unwrap().name, "payments");
+
+        // Unknown or missing keys never fall back to the host context.
+        assert!(client_context(&host, &headers("x-api-key", "key-unknown")).is_none());
+        assert!(client_context(&host, &HeaderMap::new()).is_none());
+        let _ = std::fs::remove_file(&path);
+    }
+}
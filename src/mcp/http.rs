@@ -0,0 +1,247 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use futures::stream::Stream;
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::error::Result;
+use crate::mcp::protocol::{JsonRpcResponse, INVALID_PARAMS, PARSE_ERROR};
+use crate::mcp::sessions::SessionManager;
+use crate::server_context::{ServerContext, SessionCredentials};
+
+/// Header carrying the session id assigned at initialization, per the MCP
+/// Streamable HTTP transport.
+const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Headers a session may use to present its own SonarQube credentials at
+/// initialization, for clients that cannot extend the init params.
+const URL_HEADER: &str = "x-sonarqube-url";
+const TOKEN_HEADER: &str = "x-sonarqube-token";
+const ORGANIZATION_HEADER: &str = "x-sonarqube-organization";
+
+/// Runs the MCP Streamable HTTP transport on `addr`, so one shared instance
+/// can serve a whole team instead of one stdio process per developer.
+///
+/// Clients POST JSON-RPC messages to `/mcp` and receive the response in the
+/// HTTP response body; an `initialize` request opens a session whose id
+/// comes back in the Mcp-Session-Id header and must accompany every later
+/// message. GET `/mcp` opens the server-to-client SSE stream that carries
+/// notifications; the notifier supports a single binding, so the most
+/// recently opened stream receives them. DELETE `/mcp` ends the session.
+pub async fn serve(ctx: Arc<ServerContext>, addr: SocketAddr) -> Result<()> {
+    let idle = Duration::from_secs(ctx.config.session_idle_seconds);
+    let sessions = SessionManager::new(ctx, idle);
+    sessions.spawn_sweeper();
+    let ctx = Arc::clone(sessions.context());
+    let app = Router::new()
+        .route("/mcp", get(stream).post(message).delete(goodbye))
+        .with_state(sessions);
+    tracing::info!("mcp http transport binding on {addr}");
+    crate::mcp::tls::serve_app(&ctx, addr, app).await
+}
+
+async fn message(
+    State(sessions): State<Arc<SessionManager>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let Some(ctx) = crate::mcp::access::client_context(sessions.context(), &headers) else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    let (status, session_id, body) = respond(&sessions, &ctx, &headers, &body).await;
+    let mut response = match body {
+        Some(body) => (status, [("content-type", "application/json")], body).into_response(),
+        None => status.into_response(),
+    };
+    if let Some(id) = session_id {
+        if let Ok(value) = id.parse() {
+            response.headers_mut().insert(SESSION_HEADER, value);
+        }
+    }
+    response
+}
+
+/// Handles one POSTed message. `initialize` opens a fresh session bound to
+/// the caller's context (the tenant context when a registry is configured)
+/// and returns its id; everything else must present a known session id —
+/// expired sessions are a 404, a missing header a 400. Notifications and
+/// client responses are accepted without a body.
+async fn respond(
+    sessions: &SessionManager,
+    ctx: &Arc<ServerContext>,
+    headers: &HeaderMap,
+    body: &str,
+) -> (StatusCode, Option<String>, Option<String>) {
+    let Ok(value) = serde_json::from_str::<Value>(body) else {
+        let error = JsonRpcResponse::error(Value::Null, PARSE_ERROR, "body is not valid JSON");
+        let body = serde_json::to_string(&error).ok();
+        return (StatusCode::BAD_REQUEST, None, body);
+    };
+    let session = if value.get("method").and_then(Value::as_str) == Some("initialize") {
+        let session = sessions.create_for(Arc::clone(ctx));
+        // Header credentials apply first, so a `sonarqube` member in the
+        // init params still wins when both are present.
+        if let Err(message) = session.adopt(&credentials_from_headers(headers)) {
+            sessions.remove(&session.id);
+            let id = value.get("id").cloned().unwrap_or(Value::Null);
+            let error = JsonRpcResponse::error(id, INVALID_PARAMS, message);
+            return (StatusCode::BAD_REQUEST, None, serde_json::to_string(&error).ok());
+        }
+        session
+    } else {
+        let Some(id) = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok()) else {
+            return (StatusCode::BAD_REQUEST, None, None);
+        };
+        let Some(session) = sessions.get(id) else {
+            return (StatusCode::NOT_FOUND, None, None);
+        };
+        session
+    };
+    let session_id = Some(session.id.clone());
+    match session.handle_line(body).await {
+        Some(response) => (StatusCode::OK, session_id, Some(response)),
+        None => (StatusCode::ACCEPTED, session_id, None),
+    }
+}
+
+/// Per-session SonarQube credentials presented as headers; absent headers
+/// keep the server-wide configuration.
+fn credentials_from_headers(headers: &HeaderMap) -> SessionCredentials {
+    let value = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    SessionCredentials {
+        url: value(URL_HEADER),
+        token: value(TOKEN_HEADER),
+        organization: value(ORGANIZATION_HEADER),
+    }
+}
+
+/// DELETE /mcp ends the presented session.
+async fn goodbye(State(sessions): State<Arc<SessionManager>>, headers: HeaderMap) -> StatusCode {
+    if crate::mcp::access::client_context(sessions.context(), &headers).is_none() {
+        return StatusCode::UNAUTHORIZED;
+    }
+    let Some(id) = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok()) else {
+        return StatusCode::BAD_REQUEST;
+    };
+    if sessions.remove(id) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// Opens the SSE stream carrying server-initiated notifications. Each
+/// notification is delivered as one event whose data is the JSON-RPC line.
+async fn stream(
+    State(sessions): State<Arc<SessionManager>>,
+    headers: HeaderMap,
+) -> std::result::Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>, StatusCode>
+{
+    if crate::mcp::access::client_context(sessions.context(), &headers).is_none() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    let (tx, rx) = mpsc::unbounded_channel::<String>();
+    sessions.context().notifier.bind(tx);
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv()
+            .await
+            .map(|line| (Ok(Event::default().data(line)), rx))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    fn manager() -> Arc<SessionManager> {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ]);
+        let ctx = Arc::new(ServerContext::new(config).expect("context"));
+        SessionManager::new(ctx, Duration::from_secs(60))
+    }
+
+    fn with_session(id: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SESSION_HEADER, id.parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn initialize_opens_a_session_that_later_requests_may_use() {
+        let sessions = manager();
+        let (status, id, body) = respond(
+            &sessions,
+            sessions.context(),
+            &HeaderMap::new(),
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.unwrap().contains("protocolVersion"));
+        let id = id.expect("initialization assigns a session id");
+
+        let (status, _, body) = respond(
+            &sessions,
+            sessions.context(),
+            &with_session(&id),
+            r#"{"jsonrpc":"2.0","id":2,"method":"ping","params":{}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.is_some());
+    }
+
+    #[tokio::test]
+    async fn messages_without_a_known_session_are_refused() {
+        let sessions = manager();
+        let ping = r#"{"jsonrpc":"2.0","id":1,"method":"ping","params":{}}"#;
+        let (status, _, _) = respond(&sessions, sessions.context(), &HeaderMap::new(), ping).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _, _) = respond(&sessions, sessions.context(), &with_session("stranger"), ping).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn notifications_are_accepted_without_a_body() {
+        let sessions = manager();
+        let session = sessions.create();
+        let (status, _, body) = respond(
+            &sessions,
+            sessions.context(),
+            &with_session(&session.id),
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert!(body.is_none());
+    }
+
+    #[tokio::test]
+    async fn malformed_bodies_are_a_400_with_a_parse_error() {
+        let sessions = manager();
+        let (status, _, body) = respond(&sessions, sessions.context(), &HeaderMap::new(), "not json").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.unwrap().contains("-32700"));
+    }
+}
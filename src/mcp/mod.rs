@@ -0,0 +1,10 @@
+pub(crate) mod access;
+pub mod http;
+pub mod notifier;
+pub mod protocol;
+pub mod render;
+pub mod server;
+pub mod sessions;
+pub(crate) mod tls;
+pub mod unix;
+pub mod ws;
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::mcp::protocol::JsonRpcNotification;
+
+/// Outbound notification channel shared with tools.
+///
+/// The sender is bound once the transport is up; notifications emitted before
+/// that (or after the client disconnects) are silently dropped.
+#[derive(Default)]
+pub struct Notifier {
+    tx: RwLock<Option<UnboundedSender<String>>>,
+    /// Coalesced notifications awaiting the next flush, keyed by subject
+    /// (e.g. project). The latest state per key wins.
+    pending: Mutex<BTreeMap<String, (String, Value)>>,
+    flush_scheduled: AtomicBool,
+}
+
+impl Notifier {
+    pub fn bind(&self, tx: UnboundedSender<String>) {
+        *self.tx.write().expect("notifier lock poisoned") = Some(tx);
+    }
+
+    pub fn notify(&self, method: &str, params: Value) {
+        let guard = self.tx.read().expect("notifier lock poisoned");
+        if let Some(tx) = guard.as_ref() {
+            let notification = JsonRpcNotification::new(method, params);
+            if let Ok(line) = serde_json::to_string(&notification) {
+                let _ = tx.send(line);
+            }
+        }
+    }
+
+    /// Queues a notification about `key`, coalescing with any notification
+    /// about the same key still waiting: the latest state wins. Everything
+    /// pending is flushed in one batch after `delay`, so a burst from a
+    /// background subsystem (a large watchlist refresh, a webhook storm)
+    /// cannot flood a slow client. A zero delay sends immediately.
+    pub fn notify_coalesced(self: &Arc<Self>, key: &str, method: &str, params: Value, delay: Duration) {
+        if delay.is_zero() {
+            self.notify(method, params);
+            return;
+        }
+        self.pending
+            .lock()
+            .expect("notifier lock poisoned")
+            .insert(key.to_string(), (method.to_string(), params));
+        if !self.flush_scheduled.swap(true, Ordering::SeqCst) {
+            let notifier = Arc::clone(self);
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                notifier.flush_pending();
+            });
+        }
+    }
+
+    /// Drains the pending batch in key order.
+    fn flush_pending(&self) {
+        let pending = std::mem::take(
+            &mut *self.pending.lock().expect("notifier lock poisoned"),
+        );
+        self.flush_scheduled.store(false, Ordering::SeqCst);
+        for (_, (method, params)) in pending {
+            self.notify(&method, params);
+        }
+    }
+
+    /// Sends a server-initiated ping request. The client's pong response is
+    /// discarded by the transport loop; the traffic itself is the point,
+    /// keeping idle connections alive through NAT and proxy timeouts.
+    pub fn keepalive_ping(&self, sequence: u64) {
+        let guard = self.tx.read().expect("notifier lock poisoned");
+        if let Some(tx) = guard.as_ref() {
+            let request = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": format!("keepalive-{sequence}"),
+                "method": "ping",
+            });
+            let _ = tx.send(request.to_string());
+        }
+    }
+
+    /// Emits an MCP progress notification for the given progress token.
+    pub fn progress(&self, token: &Value, progress: u64, total: Option<u64>, message: &str) {
+        let mut params = serde_json::json!({
+            "progressToken": token,
+            "progress": progress,
+            "message": message,
+        });
+        if let Some(total) = total {
+            params["total"] = total.into();
+        }
+        self.notify("notifications/progress", params);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn coalesces_bursts_to_the_latest_state_per_key() {
+        let notifier = Arc::new(Notifier::default());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        notifier.bind(tx);
+
+        let delay = Duration::from_millis(20);
+        for revision in 1..=3 {
+            notifier.notify_coalesced(
+                "alpha",
+                "notifications/message",
+                serde_json::json!({"project": "alpha", "revision": revision}),
+                delay,
+            );
+        }
+        notifier.notify_coalesced(
+            "beta",
+            "notifications/message",
+            serde_json::json!({"project": "beta", "revision": 1}),
+            delay,
+        );
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        let mut lines = Vec::new();
+        while let Ok(line) = rx.try_recv() {
+            lines.push(line);
+        }
+        // One flush: two keys, each carrying its latest revision.
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("\"alpha\"") && lines[0].contains("\"revision\":3"));
+        assert!(lines[1].contains("\"beta\""));
+    }
+
+    #[tokio::test]
+    async fn zero_delay_bypasses_batching() {
+        let notifier = Arc::new(Notifier::default());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        notifier.bind(tx);
+        notifier.notify_coalesced(
+            "alpha",
+            "notifications/message",
+            serde_json::json!({}),
+            Duration::ZERO,
+        );
+        assert!(rx.try_recv().is_ok());
+    }
+}
@@ -0,0 +1,157 @@
+//! Markdown rendering for tool results.
+//!
+//! Chat clients display Markdown tables far better than raw JSON blobs, so
+//! the main read tools accept `format: "markdown"` and funnel their (already
+//! redacted and annotated) result value through the renderers here.
+
+use serde_json::Value;
+
+/// Renders an issues search result: a headline with the totals and a table
+/// of the returned issues.
+pub fn issues(value: &Value) -> String {
+    let total = value["paging"]["total"].as_u64().unwrap_or(0);
+    let issues = value["issues"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let mut out = format!("**{total} issue(s)**, showing {}\n\n", issues.len());
+    out.push_str(&table(
+        &["Key", "Severity", "Type", "Rule", "Message", "Component"],
+        issues
+            .iter()
+            .map(|issue| {
+                vec![
+                    cell(&issue["key"]),
+                    cell(&issue["severity"]),
+                    cell(&issue["type"]),
+                    cell(&issue["rule"]),
+                    cell(&issue["message"]),
+                    cell(&issue["component"]),
+                ]
+            })
+            .collect(),
+    ));
+    out
+}
+
+/// Renders a measures result: one row per metric, with the gate annotation
+/// when the metrics tool attached one.
+pub fn measures(value: &Value) -> String {
+    let component = value["component"].as_str().unwrap_or("?");
+    let measures = value["measures"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let mut out = format!("**Measures for {component}**\n\n");
+    out.push_str(&table(
+        &["Metric", "Value", "Gate"],
+        measures
+            .iter()
+            .map(|measure| {
+                let gate = match measure.get("quality_gate") {
+                    Some(gate) => format!(
+                        "{} (threshold {})",
+                        gate["status"].as_str().unwrap_or("?"),
+                        gate["threshold"].as_str().unwrap_or("?"),
+                    ),
+                    None => String::new(),
+                };
+                vec![cell(&measure["metric"]), cell(&measure["value"]), escape(&gate)]
+            })
+            .collect(),
+    ));
+    out
+}
+
+/// Renders a quality gate status: the verdict plus a conditions table.
+pub fn quality_gate(value: &Value) -> String {
+    let status = value["projectStatus"]["status"].as_str().unwrap_or("NONE");
+    let verdict = match status {
+        "OK" => "passing",
+        "ERROR" => "failing",
+        other => other,
+    };
+    let conditions = value["projectStatus"]["conditions"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let mut out = format!("**Quality gate: {verdict}** ({status})\n\n");
+    out.push_str(&table(
+        &["Condition", "Status", "Actual", "Threshold"],
+        conditions
+            .iter()
+            .map(|condition| {
+                vec![
+                    cell(&condition["metricKey"]),
+                    cell(&condition["status"]),
+                    cell(&condition["actualValue"]),
+                    cell(&condition["errorThreshold"]),
+                ]
+            })
+            .collect(),
+    ));
+    out
+}
+
+/// Builds a Markdown table. Cells are assumed already escaped.
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut out = format!("| {} |\n", headers.join(" | "));
+    out.push_str(&format!("|{}\n", " --- |".repeat(headers.len())));
+    for row in rows {
+        out.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+    out
+}
+
+/// A table cell for a JSON value: strings verbatim, everything else via its
+/// JSON form, with table-breaking characters escaped.
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(text) => escape(text),
+        Value::Null => String::new(),
+        other => escape(&other.to_string()),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn renders_issues_as_a_table_with_escaped_cells() {
+        let value = json!({
+            "paging": {"pageIndex": 1, "pageSize": 100, "total": 2},
+            "issues": [
+                {"key": "A", "severity": "MAJOR", "type": "BUG",
+                 "rule": "java:S1", "message": "a | b\nc", "component": "demo:src/A.java"},
+            ],
+        });
+        let markdown = issues(&value);
+        assert!(markdown.starts_with("**2 issue(s)**, showing 1"));
+        assert!(markdown.contains("| Key | Severity |"));
+        assert!(markdown.contains("a \\| b c"));
+    }
+
+    #[test]
+    fn renders_the_gate_verdict_and_conditions() {
+        let value = json!({"projectStatus": {"status": "ERROR", "conditions": [
+            {"metricKey": "new_coverage", "status": "ERROR",
+             "actualValue": "61.0", "errorThreshold": "80"},
+        ]}});
+        let markdown = quality_gate(&value);
+        assert!(markdown.contains("**Quality gate: failing** (ERROR)"));
+        assert!(markdown.contains("| new_coverage | ERROR | 61.0 | 80 |"));
+    }
+
+    #[test]
+    fn renders_measures_with_optional_gate_annotations() {
+        let value = json!({"component": "demo", "measures": [
+            {"metric": "coverage", "value": "73.5",
+             "quality_gate": {"status": "ERROR", "threshold": "80"}},
+            {"metric": "ncloc", "value": "1200"},
+        ]});
+        let markdown = measures(&value);
+        assert!(markdown.contains("| coverage | 73.5 | ERROR (threshold 80) |"));
+        assert!(markdown.contains("| ncloc | 1200 |  |"));
+    }
+}
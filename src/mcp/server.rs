@@ -0,0 +1,340 @@
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+use crate::error::Result;
+use crate::mcp::protocol::{
+    JsonRpcRequest, JsonRpcResponse, INTERNAL_ERROR, INVALID_PARAMS, METHOD_NOT_FOUND, PARSE_ERROR,
+    PROTOCOL_VERSION,
+};
+use crate::server_context::ServerContext;
+use crate::tools;
+
+pub struct McpServer {
+    ctx: Arc<ServerContext>,
+}
+
+impl McpServer {
+    pub fn new(ctx: Arc<ServerContext>) -> Self {
+        Self { ctx }
+    }
+
+    /// Serves MCP over stdin/stdout, one JSON-RPC message per line.
+    pub async fn run_stdio(&self) -> Result<()> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+        self.ctx.notifier.bind(tx.clone());
+
+        let writer = tokio::spawn(async move {
+            let mut stdout = tokio::io::stdout();
+            while let Some(line) = rx.recv().await {
+                if stdout.write_all(line.as_bytes()).await.is_err() {
+                    break;
+                }
+                if stdout.write_all(b"\n").await.is_err() {
+                    break;
+                }
+                let _ = stdout.flush().await;
+            }
+        });
+
+        let keepalive = self.spawn_keepalive();
+
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            // Responses to server-initiated requests (keep-alive pongs) have
+            // no method; acknowledge them by discarding, not with an error.
+            if is_client_response(&line) {
+                tracing::debug!("discarding client response: {line}");
+                continue;
+            }
+            let request: JsonRpcRequest = match serde_json::from_str(&line) {
+                Ok(request) => request,
+                Err(err) => {
+                    let response =
+                        JsonRpcResponse::error(Value::Null, PARSE_ERROR, err.to_string());
+                    let _ = tx.send(serde_json::to_string(&response)?);
+                    continue;
+                }
+            };
+            let id = request.id.clone();
+            if let Some(response) = self.handle(request).await {
+                let _ = tx.send(serde_json::to_string(&response)?);
+            } else if id.is_some() {
+                // A request (not a notification) must always get a response.
+                let response = JsonRpcResponse::error(
+                    id.unwrap_or(Value::Null),
+                    INTERNAL_ERROR,
+                    "no response produced",
+                );
+                let _ = tx.send(serde_json::to_string(&response)?);
+            }
+        }
+
+        if let Some(keepalive) = keepalive {
+            keepalive.abort();
+        }
+        drop(tx);
+        let _ = writer.await;
+        Ok(())
+    }
+
+    /// Starts the keep-alive pinger when `--keepalive-seconds` is set, so
+    /// idle sessions keep generating traffic through proxies and NAT.
+    fn spawn_keepalive(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let seconds = self.ctx.config.keepalive_seconds;
+        if seconds == 0 {
+            return None;
+        }
+        let notifier = Arc::clone(&self.ctx.notifier);
+        Some(tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(seconds.max(5)));
+            // The first tick fires immediately; a ping before the client
+            // even initialized would be noise.
+            interval.tick().await;
+            let mut sequence = 0u64;
+            loop {
+                interval.tick().await;
+                sequence += 1;
+                notifier.keepalive_ping(sequence);
+            }
+        }))
+    }
+
+    /// Handles one framed JSON-RPC line for the network transports, exactly
+    /// as stdio handles a line: client responses and blank lines are
+    /// discarded, malformed frames get a parse error, notifications produce
+    /// nothing, and requests produce their serialized response.
+    pub(crate) async fn respond_line(&self, line: &str) -> Option<String> {
+        if line.trim().is_empty() || is_client_response(line) {
+            return None;
+        }
+        let request: JsonRpcRequest = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => {
+                let response = JsonRpcResponse::error(Value::Null, PARSE_ERROR, err.to_string());
+                return serde_json::to_string(&response).ok();
+            }
+        };
+        let response = self.handle(request).await?;
+        serde_json::to_string(&response).ok()
+    }
+
+    /// Dispatches a single JSON-RPC message. Returns `None` for notifications.
+    pub async fn handle(&self, request: JsonRpcRequest) -> Option<JsonRpcResponse> {
+        let id = request.id.clone();
+        let is_notification = id.is_none();
+        let result = self.handle_method(&request).await;
+        if is_notification {
+            return None;
+        }
+        let id = id.unwrap_or(Value::Null);
+        Some(match result {
+            Ok(value) => JsonRpcResponse::success(id, value),
+            Err((code, message)) => JsonRpcResponse::error(id, code, message),
+        })
+    }
+
+    /// Capabilities advertised at initialization. Each sub-capability is
+    /// stated explicitly: the tool, prompt and resource lists are static for
+    /// the lifetime of a session, and resource subscriptions are not
+    /// supported.
+    fn capabilities() -> Value {
+        json!({
+            "tools": { "listChanged": false },
+            "prompts": { "listChanged": false },
+            "resources": { "subscribe": false, "listChanged": false },
+        })
+    }
+
+    async fn handle_method(
+        &self,
+        request: &JsonRpcRequest,
+    ) -> std::result::Result<Value, (i64, String)> {
+        match request.method.as_str() {
+            "initialize" => {
+                // Session metadata: remember the client identity so the
+                // impersonation header (when configured) can forward it.
+                if let Some(client_name) = request
+                    .params
+                    .get("clientInfo")
+                    .and_then(|info| info.get("name"))
+                    .and_then(Value::as_str)
+                {
+                    self.ctx
+                        .client
+                        .set_impersonated_user(Some(client_name.to_string()));
+                }
+                Ok(json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": Self::capabilities(),
+                    "serverInfo": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }))
+            }
+            "notifications/initialized" | "notifications/cancelled" => Ok(Value::Null),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools::definitions() })),
+            "resources/list" => {
+                let cursor = request.params.get("cursor").and_then(Value::as_str);
+                crate::resources::list(&self.ctx, cursor)
+                    .await
+                    .map_err(|err| (INTERNAL_ERROR, err.to_string()))
+            }
+            "resources/read" => {
+                let uri = request
+                    .params
+                    .get("uri")
+                    .and_then(Value::as_str)
+                    .ok_or((INVALID_PARAMS, "missing resource uri".to_string()))?;
+                crate::resources::read(&self.ctx, uri)
+                    .await
+                    .map_err(|err| (INTERNAL_ERROR, err.to_string()))
+            }
+            "prompts/list" => Ok(crate::prompts::list()),
+            "prompts/get" => {
+                let name = request
+                    .params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or((INVALID_PARAMS, "missing prompt name".to_string()))?;
+                let arguments = request
+                    .params
+                    .get("arguments")
+                    .map(|args| {
+                        serde_json::from_value(args.clone())
+                            .map_err(|err| (INVALID_PARAMS, err.to_string()))
+                    })
+                    .transpose()?
+                    .unwrap_or_default();
+                crate::prompts::get(name, &arguments)
+                    .map_err(|err| (INVALID_PARAMS, err.to_string()))
+            }
+            "tools/call" => {
+                let name = request
+                    .params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or((INVALID_PARAMS, "missing tool name".to_string()))?;
+                let arguments = request
+                    .params
+                    .get("arguments")
+                    .cloned()
+                    .unwrap_or_else(|| json!({}));
+                let progress_token = request
+                    .params
+                    .get("_meta")
+                    .and_then(|meta| meta.get("progressToken"))
+                    .cloned();
+                let result =
+                    match tools::dispatch(&self.ctx, name, arguments, progress_token).await {
+                        Ok(result) => result,
+                        Err(err @ crate::error::Error::UnknownTool(_)) => {
+                            return Err((METHOD_NOT_FOUND, err.to_string()));
+                        }
+                        Err(err) => crate::mcp::protocol::CallToolResult::error(err.to_string()),
+                    };
+                serde_json::to_value(result).map_err(|err| (INTERNAL_ERROR, err.to_string()))
+            }
+            other => Err((METHOD_NOT_FOUND, format!("unknown method: {other}"))),
+        }
+    }
+}
+
+/// Whether a line is a JSON-RPC response (result or error, no method) —
+/// i.e. the client answering a server-initiated request.
+pub(crate) fn is_client_response(line: &str) -> bool {
+    serde_json::from_str::<Value>(line)
+        .map(|value| {
+            value.get("method").is_none()
+                && (value.get("result").is_some() || value.get("error").is_some())
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    fn test_server() -> McpServer {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ]);
+        McpServer::new(Arc::new(ServerContext::new(config).expect("context")))
+    }
+
+    fn request(method: &str, params: Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(1)),
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    #[tokio::test]
+    async fn ping_answers_with_an_empty_object() {
+        let server = test_server();
+        let response = server
+            .handle(request("ping", json!({})))
+            .await
+            .expect("ping gets a response");
+        assert_eq!(response.result, Some(json!({})));
+    }
+
+    #[test]
+    fn client_responses_are_recognized_and_requests_are_not() {
+        assert!(is_client_response(r#"{"jsonrpc":"2.0","id":"keepalive-1","result":{}}"#));
+        assert!(is_client_response(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32600,"message":"x"}}"#
+        ));
+        assert!(!is_client_response(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#));
+        assert!(!is_client_response("not json"));
+    }
+
+    #[tokio::test]
+    async fn initialize_advertises_prompt_and_resource_capabilities() {
+        let server = test_server();
+        let response = server
+            .handle(request("initialize", json!({"clientInfo": {"name": "test"}})))
+            .await
+            .expect("initialize gets a response");
+        let result = response.result.expect("success");
+        let capabilities = &result["capabilities"];
+        assert_eq!(capabilities["tools"]["listChanged"], json!(false));
+        assert_eq!(capabilities["prompts"]["listChanged"], json!(false));
+        assert_eq!(capabilities["resources"]["subscribe"], json!(false));
+        assert_eq!(capabilities["resources"]["listChanged"], json!(false));
+    }
+
+    #[tokio::test]
+    async fn advertised_capabilities_are_backed_by_list_methods() {
+        let server = test_server();
+        let tools = server
+            .handle(request("tools/list", json!({})))
+            .await
+            .unwrap()
+            .result
+            .unwrap();
+        assert!(!tools["tools"].as_array().unwrap().is_empty());
+        let prompts = server
+            .handle(request("prompts/list", json!({})))
+            .await
+            .unwrap()
+            .result
+            .unwrap();
+        assert!(!prompts["prompts"].as_array().unwrap().is_empty());
+    }
+}
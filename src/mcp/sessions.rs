@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::mcp::protocol::{JsonRpcResponse, INVALID_PARAMS, SERVER_NOT_INITIALIZED};
+use crate::mcp::server::McpServer;
+use crate::server_context::{ServerContext, SessionCredentials};
+
+/// One MCP session over a network transport, with its own initialization
+/// state and request-id sequence. The stdio transport has exactly one
+/// implicit session and does not use this type.
+pub struct Session {
+    pub id: String,
+    /// The session's context; swapped when initialization presents its own
+    /// SonarQube credentials, so every later call uses the session client.
+    ctx: RwLock<Arc<ServerContext>>,
+    initialized: AtomicBool,
+    request_ids: AtomicU64,
+    last_seen: Mutex<Instant>,
+}
+
+impl Session {
+    /// Handles one framed JSON-RPC line within this session. Initialization
+    /// is tracked per session: until `initialize` has been seen, anything
+    /// but a ping or a notification is refused, so concurrent clients
+    /// cannot ride on each other's handshake.
+    pub async fn handle_line(&self, line: &str) -> Option<String> {
+        self.touch();
+        if let Ok(value) = serde_json::from_str::<Value>(line) {
+            match value.get("method").and_then(Value::as_str) {
+                Some("initialize") => {
+                    if let Err(message) = self.adopt_credentials(&value) {
+                        let id = value.get("id").cloned().unwrap_or(Value::Null);
+                        let response =
+                            JsonRpcResponse::error(id, INVALID_PARAMS, message);
+                        return serde_json::to_string(&response).ok();
+                    }
+                    self.initialized.store(true, Ordering::SeqCst);
+                }
+                Some(method) if !self.initialized.load(Ordering::SeqCst)
+                    && requires_initialization(method) =>
+                {
+                    let id = value.get("id").cloned()?;
+                    let response = JsonRpcResponse::error(
+                        id,
+                        SERVER_NOT_INITIALIZED,
+                        "session not initialized",
+                    );
+                    return serde_json::to_string(&response).ok();
+                }
+                _ => {}
+            }
+        }
+        let server = McpServer::new(self.context());
+        server.respond_line(line).await
+    }
+
+    /// Applies SonarQube credentials carried in the `sonarqube` member of
+    /// the initialize params, deriving a per-session client. Absent or
+    /// empty credentials keep the server-wide client.
+    fn adopt_credentials(&self, request: &Value) -> std::result::Result<(), String> {
+        let Some(raw) = request.get("params").and_then(|p| p.get("sonarqube")) else {
+            return Ok(());
+        };
+        let credentials: SessionCredentials = serde_json::from_value(raw.clone())
+            .map_err(|err| format!("invalid sonarqube credentials: {err}"))?;
+        self.adopt(&credentials)
+    }
+
+    /// Swaps in a context derived from `credentials` (no-op when empty);
+    /// transports that carry credentials in headers call this directly.
+    pub fn adopt(&self, credentials: &SessionCredentials) -> std::result::Result<(), String> {
+        if credentials.is_empty() {
+            return Ok(());
+        }
+        let derived = self
+            .context()
+            .with_session_credentials(credentials)
+            .map_err(|err| format!("invalid sonarqube credentials: {err}"))?;
+        *self.ctx.write().expect("session lock poisoned") = Arc::new(derived);
+        Ok(())
+    }
+
+    fn context(&self) -> Arc<ServerContext> {
+        Arc::clone(&self.ctx.read().expect("session lock poisoned"))
+    }
+
+    /// Next id for a server-initiated request on this session. Sessions
+    /// count independently, so ids never collide across clients.
+    pub fn next_request_id(&self) -> u64 {
+        self.request_ids.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn touch(&self) {
+        *self.last_seen.lock().expect("session lock poisoned") = Instant::now();
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_seen
+            .lock()
+            .expect("session lock poisoned")
+            .elapsed()
+    }
+}
+
+/// Whether a method is only meaningful on an initialized session.
+fn requires_initialization(method: &str) -> bool {
+    method != "ping" && !method.starts_with("notifications/")
+}
+
+/// Tracks the concurrent sessions of the network transports: creation with
+/// unguessable ids, lookup that refreshes the idle clock, and expiry of
+/// sessions whose client went away without saying goodbye.
+pub struct SessionManager {
+    ctx: Arc<ServerContext>,
+    sessions: Mutex<HashMap<String, Arc<Session>>>,
+    idle_timeout: Duration,
+}
+
+impl SessionManager {
+    /// A zero `idle_timeout` keeps sessions until explicitly removed.
+    pub fn new(ctx: Arc<ServerContext>, idle_timeout: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            ctx,
+            sessions: Mutex::new(HashMap::new()),
+            idle_timeout,
+        })
+    }
+
+    pub fn create(&self) -> Arc<Session> {
+        self.create_for(Arc::clone(&self.ctx))
+    }
+
+    /// Creates a session bound to a specific context. The network
+    /// transports use this to pin a session to the tenant selected by the
+    /// client's API key instead of the shared host context.
+    pub fn create_for(&self, ctx: Arc<ServerContext>) -> Arc<Session> {
+        let session = Arc::new(Session {
+            id: self.session_id(),
+            ctx: RwLock::new(ctx),
+            initialized: AtomicBool::new(false),
+            request_ids: AtomicU64::new(0),
+            last_seen: Mutex::new(Instant::now()),
+        });
+        self.sessions
+            .lock()
+            .expect("session lock poisoned")
+            .insert(session.id.clone(), Arc::clone(&session));
+        session
+    }
+
+    /// Looks a session up and refreshes its idle clock. Expired sessions
+    /// are indistinguishable from ones that never existed.
+    pub fn get(&self, id: &str) -> Option<Arc<Session>> {
+        let session = self
+            .sessions
+            .lock()
+            .expect("session lock poisoned")
+            .get(id)
+            .cloned()?;
+        if !self.idle_timeout.is_zero() && session.idle_for() > self.idle_timeout {
+            self.remove(id);
+            return None;
+        }
+        session.touch();
+        Some(session)
+    }
+
+    pub fn remove(&self, id: &str) -> bool {
+        self.sessions
+            .lock()
+            .expect("session lock poisoned")
+            .remove(id)
+            .is_some()
+    }
+
+    /// Drops every session idle longer than the timeout.
+    pub fn sweep(&self) {
+        if self.idle_timeout.is_zero() {
+            return;
+        }
+        self.sessions
+            .lock()
+            .expect("session lock poisoned")
+            .retain(|_, session| session.idle_for() <= self.idle_timeout);
+    }
+
+    /// Starts the periodic sweep. A no-op when expiry is disabled.
+    pub fn spawn_sweeper(self: &Arc<Self>) {
+        if self.idle_timeout.is_zero() {
+            return;
+        }
+        let manager = Arc::clone(self);
+        let period = (self.idle_timeout / 2).max(Duration::from_secs(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                manager.sweep();
+            }
+        });
+    }
+
+    pub fn context(&self) -> &Arc<ServerContext> {
+        &self.ctx
+    }
+
+    pub fn active_sessions(&self) -> usize {
+        self.sessions.lock().expect("session lock poisoned").len()
+    }
+
+    /// Ids must be unguessable (a session id is the only credential a
+    /// Streamable HTTP client presents after initialization), so they are
+    /// 128 bits straight from the OS random number generator. Hashing
+    /// guessable inputs like a counter or the clock would not do: anyone
+    /// who can bound them could enumerate candidate ids offline.
+    fn session_id(&self) -> String {
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes).expect("OS random number generator unavailable");
+        hex::encode(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    fn manager(idle: Duration) -> Arc<SessionManager> {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ]);
+        let ctx = Arc::new(ServerContext::new(config).expect("context"));
+        SessionManager::new(ctx, idle)
+    }
+
+    #[test]
+    fn sessions_get_distinct_ids_and_independent_request_ids() {
+        let manager = manager(Duration::from_secs(60));
+        let first = manager.create();
+        let second = manager.create();
+        assert_ne!(first.id, second.id);
+        assert_eq!(first.next_request_id(), 1);
+        assert_eq!(first.next_request_id(), 2);
+        assert_eq!(second.next_request_id(), 1);
+        assert_eq!(manager.active_sessions(), 2);
+        assert!(manager.remove(&first.id));
+        assert!(manager.get(&first.id).is_none());
+        assert!(manager.get(&second.id).is_some());
+    }
+
+    #[test]
+    fn idle_sessions_expire_and_active_ones_survive_a_sweep() {
+        let manager = manager(Duration::ZERO);
+        let session = manager.create();
+        // A zero timeout disables expiry entirely.
+        manager.sweep();
+        assert!(manager.get(&session.id).is_some());
+    }
+
+    #[tokio::test]
+    async fn initialize_may_carry_per_session_sonarqube_credentials() {
+        let manager = manager(Duration::from_secs(60));
+        let session = manager.create();
+        let line = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"sonarqube":{"url":"http://team.example.com","token":"squ_team"}}}"#;
+        let response = session.handle_line(line).await.expect("initialize responds");
+        let value: Value = serde_json::from_str(&response).unwrap();
+        assert!(value["error"].is_null());
+        assert_eq!(
+            session.context().config.sonarqube_url,
+            "http://team.example.com"
+        );
+
+        // Malformed credentials fail the handshake instead of silently
+        // falling back to the shared client.
+        let other = manager.create();
+        let bad = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"sonarqube":{"url":42}}}"#;
+        let response = other.handle_line(bad).await.expect("refusal responds");
+        let value: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(value["error"]["code"], serde_json::json!(INVALID_PARAMS));
+    }
+
+    #[tokio::test]
+    async fn requests_before_initialize_are_refused_per_session() {
+        let manager = manager(Duration::from_secs(60));
+        let session = manager.create();
+        let refused = session
+            .handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"tools/list","params":{}}"#)
+            .await
+            .expect("refusal carries a response");
+        let value: Value = serde_json::from_str(&refused).unwrap();
+        assert_eq!(value["error"]["code"], serde_json::json!(SERVER_NOT_INITIALIZED));
+
+        // Pings are fine before the handshake; initialize unlocks the rest.
+        assert!(session
+            .handle_line(r#"{"jsonrpc":"2.0","id":2,"method":"ping","params":{}}"#)
+            .await
+            .is_some());
+        session
+            .handle_line(r#"{"jsonrpc":"2.0","id":3,"method":"initialize","params":{}}"#)
+            .await
+            .expect("initialize gets a response");
+        let listed = session
+            .handle_line(r#"{"jsonrpc":"2.0","id":4,"method":"tools/list","params":{}}"#)
+            .await
+            .expect("initialized sessions may list tools");
+        let value: Value = serde_json::from_str(&listed).unwrap();
+        assert!(value["error"].is_null());
+    }
+}
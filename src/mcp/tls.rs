@@ -0,0 +1,82 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+
+use crate::error::{Error, Result};
+use crate::server_context::ServerContext;
+
+/// Serves `app` on `addr`, terminating TLS directly when --tls-cert and
+/// --tls-key are configured so no reverse proxy is needed in front of the
+/// network transports. SIGHUP reloads the certificate and key from the
+/// same paths without dropping connections, for short-lived certificates
+/// rotated by an agent.
+pub(crate) async fn serve_app(ctx: &ServerContext, addr: SocketAddr, app: Router) -> Result<()> {
+    match (&ctx.config.tls_cert, &ctx.config.tls_key) {
+        (Some(cert), Some(key)) => {
+            let rustls = RustlsConfig::from_pem_file(cert, key).await.map_err(|err| {
+                Error::Config(format!("cannot load TLS certificate or key: {err}"))
+            })?;
+            spawn_reload(rustls.clone(), cert.clone(), key.clone());
+            tracing::info!("serving TLS with certificate {}", cert.display());
+            axum_server::bind_rustls(addr, rustls)
+                .serve(app.into_make_service())
+                .await?;
+        }
+        (None, None) => {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            axum::serve(listener, app).await?;
+        }
+        _ => {
+            return Err(Error::Config(
+                "--tls-cert and --tls-key must be set together".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Re-reads the certificate and key on every SIGHUP. A failed reload keeps
+/// the previous certificate serving rather than tearing the listener down.
+fn spawn_reload(rustls: RustlsConfig, cert: PathBuf, key: PathBuf) {
+    tokio::spawn(async move {
+        let mut hangup =
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                Ok(signal) => signal,
+                Err(err) => {
+                    tracing::warn!("could not install SIGHUP handler: {err}");
+                    return;
+                }
+            };
+        while hangup.recv().await.is_some() {
+            match rustls.reload_from_pem_file(&cert, &key).await {
+                Ok(()) => tracing::info!("reloaded TLS certificate {}", cert.display()),
+                Err(err) => tracing::warn!("TLS certificate reload failed: {err}"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    #[tokio::test]
+    async fn a_lone_cert_or_key_is_a_configuration_error() {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+            "--tls-cert",
+            "/etc/ssl/server.pem",
+        ]);
+        let ctx = ServerContext::new(config).expect("context");
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let result = serve_app(&ctx, addr, Router::new()).await;
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+}
@@ -0,0 +1,126 @@
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+
+use crate::error::{Error, Result};
+use crate::mcp::server::McpServer;
+use crate::server_context::ServerContext;
+
+/// Runs the MCP server on a Unix domain socket, for local orchestrators
+/// that want neither TCP ports nor stdio process management. Framing is the
+/// same as stdio: one JSON-RPC message per line, in either direction.
+///
+/// A stale socket left by an unclean shutdown is removed before binding;
+/// any other file at the path is refused. The socket file is unlinked again
+/// on SIGINT/SIGTERM so restarts find a clean path.
+pub async fn serve(ctx: Arc<ServerContext>, path: PathBuf) -> Result<()> {
+    remove_stale_socket(&path)?;
+    let listener = UnixListener::bind(&path)?;
+    if let Some(mode) = &ctx.config.socket_mode {
+        let mode = u32::from_str_radix(mode, 8)
+            .map_err(|_| Error::Config(format!("invalid socket mode: {mode}")))?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode))?;
+    }
+    tracing::info!("mcp unix socket transport bound on {}", path.display());
+    spawn_cleanup(path);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let ctx = Arc::clone(&ctx);
+        tokio::spawn(async move {
+            connection(ctx, stream).await;
+        });
+    }
+}
+
+/// Unlinks a leftover socket file so rebinding succeeds; refuses to touch
+/// anything that is not a socket, which would point at a misconfiguration.
+fn remove_stale_socket(path: &Path) -> Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            std::fs::remove_file(path)?;
+            Ok(())
+        }
+        Ok(_) => Err(Error::Config(format!(
+            "refusing to replace non-socket file at {}",
+            path.display()
+        ))),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Removes the socket file when the process is asked to stop, then exits.
+/// Without this the next start would find a stale socket (harmless, but
+/// noisy) and connected clients would see an abrupt EOF with no cleanup.
+fn spawn_cleanup(path: PathBuf) {
+    tokio::spawn(async move {
+        let mut terminate =
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(signal) => signal,
+                Err(err) => {
+                    tracing::warn!("could not install SIGTERM handler: {err}");
+                    return;
+                }
+            };
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+        let _ = std::fs::remove_file(&path);
+        std::process::exit(0);
+    });
+}
+
+/// Serves one connection until the peer disconnects. Responses and
+/// notifications share the outbound channel so they cannot interleave;
+/// notifications go to the most recently connected client, as with the
+/// other network transports.
+async fn connection(ctx: Arc<ServerContext>, stream: UnixStream) {
+    let (reader, mut writer) = stream.into_split();
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    ctx.notifier.bind(tx.clone());
+    let server = McpServer::new(ctx);
+
+    let write_task = tokio::spawn(async move {
+        while let Some(line) = rx.recv().await {
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+            if writer.write_all(b"\n").await.is_err() {
+                break;
+            }
+            let _ = writer.flush().await;
+        }
+    });
+
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Some(response) = server.respond_line(&line).await {
+            let _ = tx.send(response);
+        }
+    }
+    drop(tx);
+    let _ = write_task.await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_paths_are_fine_and_regular_files_are_refused() {
+        let dir = std::env::temp_dir().join(format!("sonar-mcp-unix-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(remove_stale_socket(&dir.join("absent.sock")).is_ok());
+
+        let file = dir.join("not-a-socket");
+        std::fs::write(&file, b"data").unwrap();
+        assert!(matches!(remove_stale_socket(&file), Err(Error::Config(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use tokio::sync::mpsc;
+
+use crate::error::Result;
+use crate::mcp::sessions::SessionManager;
+use crate::server_context::ServerContext;
+
+/// Seconds between protocol-level pings when --keepalive-seconds is unset.
+/// WebSocket connections routinely cross proxies that drop idle streams, so
+/// this transport always pings.
+const DEFAULT_PING_SECONDS: u64 = 30;
+
+/// Runs the MCP WebSocket transport on `addr`, for clients that can use
+/// neither stdio nor SSE. Each text frame carries one JSON-RPC message in
+/// either direction, exactly as a stdio line would; server-initiated
+/// notifications go to the most recently connected client. Every connection
+/// is its own session with independent initialization state; the idle
+/// sweeper reaps sessions whose connection died without a close frame.
+pub async fn serve(ctx: Arc<ServerContext>, addr: SocketAddr) -> Result<()> {
+    let idle = Duration::from_secs(ctx.config.session_idle_seconds);
+    let sessions = SessionManager::new(ctx, idle);
+    sessions.spawn_sweeper();
+    let ctx = Arc::clone(sessions.context());
+    let app = Router::new().route("/mcp", get(upgrade)).with_state(sessions);
+    tracing::info!("mcp websocket transport binding on {addr}");
+    crate::mcp::tls::serve_app(&ctx, addr, app).await
+}
+
+async fn upgrade(
+    State(sessions): State<Arc<SessionManager>>,
+    headers: axum::http::HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let Some(ctx) = crate::mcp::access::client_context(sessions.context(), &headers) else {
+        return axum::http::StatusCode::UNAUTHORIZED.into_response();
+    };
+    ws.on_upgrade(move |socket| connection(sessions, ctx, socket))
+}
+
+/// Serves one connection until the client closes it or the stream errors.
+/// Responses and notifications share the outbound channel so they cannot
+/// interleave mid-frame, and a ping keeps the connection warm through
+/// proxies and NAT.
+async fn connection(sessions: Arc<SessionManager>, ctx: Arc<ServerContext>, mut socket: WebSocket) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    ctx.notifier.bind(tx.clone());
+    let session = sessions.create_for(Arc::clone(&ctx));
+    let seconds = match ctx.config.keepalive_seconds {
+        0 => DEFAULT_PING_SECONDS,
+        seconds => seconds.max(5),
+    };
+    let mut ping = tokio::time::interval(Duration::from_secs(seconds));
+    // The first tick fires immediately; skip it so a fresh connection is
+    // not greeted with a ping.
+    ping.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = ping.tick() => {
+                if socket.send(Message::Ping(Vec::new().into())).await.is_err() {
+                    break;
+                }
+            }
+            outgoing = rx.recv() => {
+                let Some(line) = outgoing else { break };
+                if socket.send(Message::Text(line.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Some(response) = session.handle_line(&text).await {
+                            let _ = tx.send(response);
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    // Pings are answered at the protocol layer; pongs and
+                    // binary frames carry nothing for us.
+                    Some(Ok(_)) => {}
+                }
+            }
+        }
+    }
+    // Graceful close: a close frame (or an echo of the client's) before
+    // dropping the socket, best-effort, and the session goes with it.
+    let _ = socket.send(Message::Close(None)).await;
+    sessions.remove(&session.id);
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::mcp::protocol::PARSE_ERROR;
+    use crate::mcp::server::McpServer;
+
+    fn test_server() -> McpServer {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ]);
+        McpServer::new(Arc::new(ServerContext::new(config).expect("context")))
+    }
+
+    #[tokio::test]
+    async fn requests_produce_a_response_frame() {
+        let server = test_server();
+        let response = server
+            .respond_line(r#"{"jsonrpc":"2.0","id":1,"method":"ping","params":{}}"#)
+            .await
+            .expect("requests always get a response");
+        let value: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(value["result"], serde_json::json!({}));
+    }
+
+    #[tokio::test]
+    async fn notifications_and_client_responses_produce_nothing() {
+        let server = test_server();
+        let notification = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        assert!(server.respond_line(notification).await.is_none());
+        let pong = r#"{"jsonrpc":"2.0","id":"keepalive-1","result":{}}"#;
+        assert!(server.respond_line(pong).await.is_none());
+        assert!(server.respond_line("  ").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn malformed_frames_get_a_parse_error() {
+        let server = test_server();
+        let response = server.respond_line("not json").await.unwrap();
+        let value: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(value["error"]["code"], serde_json::json!(PARSE_ERROR));
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+
+/// Prompt templates wrapping common multi-step workflows. Each prompt spells
+/// out the exact sequence of tool calls so that less capable clients can
+/// drive the server correctly.
+struct PromptTemplate {
+    name: &'static str,
+    description: &'static str,
+    arguments: &'static [PromptArgument],
+    render: fn(&HashMap<String, String>) -> String,
+}
+
+struct PromptArgument {
+    name: &'static str,
+    description: &'static str,
+    required: bool,
+}
+
+fn templates() -> Vec<PromptTemplate> {
+    vec![
+        PromptTemplate {
+            name: "triage_new_criticals",
+            description: "Triage today's new CRITICAL and BLOCKER issues in a project",
+            arguments: &[PromptArgument {
+                name: "project_key",
+                description: "Project key to triage",
+                required: true,
+            }],
+            render: render_triage_new_criticals,
+        },
+        PromptTemplate {
+            name: "prepare_pr_quality_comment",
+            description: "Prepare a pull-request comment summarising project quality",
+            arguments: &[PromptArgument {
+                name: "project_key",
+                description: "Project key the pull request belongs to",
+                required: true,
+            }],
+            render: render_pr_quality_comment,
+        },
+        PromptTemplate {
+            name: "await_ci_analysis",
+            description: "Wait for a CI-triggered analysis and report the quality gate outcome",
+            arguments: &[PromptArgument {
+                name: "project_key",
+                description: "Project key being analysed",
+                required: true,
+            }],
+            render: render_await_ci_analysis,
+        },
+    ]
+}
+
+/// Handles `prompts/list`.
+pub fn list() -> Value {
+    let prompts: Vec<Value> = templates()
+        .iter()
+        .map(|template| {
+            json!({
+                "name": template.name,
+                "description": template.description,
+                "arguments": template
+                    .arguments
+                    .iter()
+                    .map(|arg| json!({
+                        "name": arg.name,
+                        "description": arg.description,
+                        "required": arg.required,
+                    }))
+                    .collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    json!({ "prompts": prompts })
+}
+
+/// Handles `prompts/get`.
+pub fn get(name: &str, arguments: &HashMap<String, String>) -> Result<Value> {
+    let template = templates()
+        .into_iter()
+        .find(|template| template.name == name)
+        .ok_or_else(|| Error::InvalidArguments(format!("unknown prompt: {name}")))?;
+    for arg in template.arguments {
+        if arg.required && !arguments.contains_key(arg.name) {
+            return Err(Error::InvalidArguments(format!(
+                "prompt {name} requires argument {}",
+                arg.name
+            )));
+        }
+    }
+    let text = (template.render)(arguments);
+    Ok(json!({
+        "description": template.description,
+        "messages": [{
+            "role": "user",
+            "content": {"type": "text", "text": text},
+        }],
+    }))
+}
+
+fn arg<'a>(arguments: &'a HashMap<String, String>, name: &str) -> &'a str {
+    arguments.get(name).map(String::as_str).unwrap_or("<unset>")
+}
+
+fn render_triage_new_criticals(arguments: &HashMap<String, String>) -> String {
+    let project_key = arg(arguments, "project_key");
+    format!(
+        "Triage today's new critical issues in SonarQube project `{project_key}`.\n\
+         \n\
+         Follow this tool sequence:\n\
+         1. Call `sonarqube_get_issues` with {{\"project_key\": \"{project_key}\", \
+         \"severities\": [\"CRITICAL\", \"BLOCKER\"], \"statuses\": [\"OPEN\", \"REOPENED\"]}}.\n\
+         2. For context on overall health, call `sonarqube_get_quality_gate_status` with \
+         {{\"project_key\": \"{project_key}\"}}.\n\
+         3. Group the issues by rule and component, and propose for each group: fix now, \
+         schedule, or challenge the finding.\n\
+         \n\
+         Present the result as a short triage board with one line per issue group."
+    )
+}
+
+fn render_pr_quality_comment(arguments: &HashMap<String, String>) -> String {
+    let project_key = arg(arguments, "project_key");
+    format!(
+        "Prepare a pull-request quality comment for SonarQube project `{project_key}`.\n\
+         \n\
+         Follow this tool sequence:\n\
+         1. Call `sonarqube_get_quality_gate_status` with {{\"project_key\": \"{project_key}\"}}.\n\
+         2. Call `sonarqube_get_metrics` with {{\"project_key\": \"{project_key}\"}} for the \
+         default metric set.\n\
+         3. Call `sonarqube_get_issues` with {{\"project_key\": \"{project_key}\", \
+         \"statuses\": [\"OPEN\"]}} and keep only the most severe findings.\n\
+         \n\
+         Write a concise markdown comment: gate status first, then a metrics table, then at \
+         most five noteworthy issues with file and line."
+    )
+}
+
+fn render_await_ci_analysis(arguments: &HashMap<String, String>) -> String {
+    let project_key = arg(arguments, "project_key");
+    format!(
+        "A CI pipeline has just submitted an analysis for SonarQube project `{project_key}`.\n\
+         \n\
+         Follow this tool sequence:\n\
+         1. Call `sonarqube_wait_for_analysis` with {{\"project_key\": \"{project_key}\"}} and \
+         wait for it to return.\n\
+         2. If the quality gate failed, call `sonarqube_get_issues` with \
+         {{\"project_key\": \"{project_key}\", \"statuses\": [\"OPEN\"]}} to find what to fix.\n\
+         \n\
+         Report the gate outcome and, on failure, the conditions that failed with their \
+         actual values."
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_all_prompts() {
+        let listed = list();
+        let prompts = listed["prompts"].as_array().unwrap();
+        assert_eq!(prompts.len(), 3);
+        assert!(prompts.iter().any(|p| p["name"] == "triage_new_criticals"));
+    }
+
+    #[test]
+    fn renders_prompt_with_arguments() {
+        let mut arguments = HashMap::new();
+        arguments.insert("project_key".to_string(), "demo".to_string());
+        let prompt = get("triage_new_criticals", &arguments).unwrap();
+        let text = prompt["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("\"project_key\": \"demo\""));
+    }
+
+    #[test]
+    fn rejects_missing_required_argument() {
+        let err = get("triage_new_criticals", &HashMap::new()).unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments(_)));
+    }
+}
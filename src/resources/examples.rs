@@ -0,0 +1,197 @@
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+
+pub const URI_PREFIX: &str = "sonarqube://examples/";
+
+/// One canned invocation of a tool: the request a client would send and an
+/// illustrative (abbreviated) response. Authoritative examples for client
+/// developers and models; tests keep them aligned with the registered tools.
+pub struct ToolExample {
+    pub tool: &'static str,
+    pub title: &'static str,
+    pub request: Value,
+    pub response: Value,
+}
+
+pub fn examples() -> Vec<ToolExample> {
+    vec![
+        ToolExample {
+            tool: "sonarqube_get_issues",
+            title: "Open blockers in one project",
+            request: json!({
+                "project_key": "my-app",
+                "severities": ["BLOCKER"],
+                "statuses": ["OPEN"],
+            }),
+            response: json!({
+                "paging": {"pageIndex": 1, "pageSize": 100, "total": 2},
+                "issues": [{
+                    "key": "AYhq1",
+                    "rule": "java:S2095",
+                    "severity": "BLOCKER",
+                    "component": "my-app:src/Main.java",
+                    "project": "my-app",
+                    "line": 42,
+                    "message": "Close this resource.",
+                    "type": "BUG",
+                    "status": "OPEN",
+                }],
+            }),
+        },
+        ToolExample {
+            tool: "sonarqube_get_metrics",
+            title: "Default metric set with gate thresholds",
+            request: json!({"project_key": "my-app"}),
+            response: json!({
+                "component": "my-app",
+                "measures": [{
+                    "metric": "coverage",
+                    "value": "73.5",
+                    "quality_gate": {
+                        "metric": "new_coverage",
+                        "comparator": "LT",
+                        "threshold": "80",
+                        "status": "ERROR",
+                    },
+                }],
+            }),
+        },
+        ToolExample {
+            tool: "sonarqube_wait_for_analysis",
+            title: "Await a scanner-reported task",
+            request: json!({"task_id": "AYhq-task", "timeout_seconds": 600}),
+            response: json!({
+                "task": {"id": "AYhq-task", "status": "SUCCESS"},
+                "quality_gate": {"projectStatus": {"status": "OK", "conditions": []}},
+            }),
+        },
+        ToolExample {
+            tool: "sonarqube_get_issue_facets",
+            title: "Counts by severity and rule",
+            request: json!({"project_key": "my-app", "facets": ["severities", "rules"]}),
+            response: json!({
+                "total": 1342,
+                "facets": [{
+                    "property": "severities",
+                    "values": [{"val": "MAJOR", "count": 801}, {"val": "MINOR", "count": 541}],
+                }],
+            }),
+        },
+        ToolExample {
+            tool: "search_issues_by_text",
+            title: "Regex search across two projects",
+            request: json!({
+                "query": "complexity .* over \\d+",
+                "regex": true,
+                "projects": ["my-app", "my-lib"],
+            }),
+            response: json!({
+                "matched": 3,
+                "scanned": 950,
+                "total_candidates": 950,
+                "truncated": false,
+                "issues": [],
+            }),
+        },
+        ToolExample {
+            tool: "sonarqube_set_setting",
+            title: "Project-scoped exclusion patterns",
+            request: json!({
+                "key": "sonar.exclusions",
+                "values": ["**/generated/**", "**/vendor/**"],
+                "project_key": "my-app",
+            }),
+            response: json!({"updated": true, "key": "sonar.exclusions", "project": "my-app"}),
+        },
+    ]
+}
+
+/// Example payloads for one tool, or empty when none are registered.
+pub fn for_tool(tool: &str) -> Vec<Value> {
+    examples()
+        .into_iter()
+        .filter(|example| example.tool == tool)
+        .map(|example| {
+            json!({
+                "title": example.title,
+                "request": example.request,
+                "response": example.response,
+            })
+        })
+        .collect()
+}
+
+/// Adds one resource entry per tool that has examples.
+pub fn list_all(resources: &mut Vec<Value>) {
+    let mut tools: Vec<&str> = examples().iter().map(|example| example.tool).collect();
+    tools.dedup();
+    for tool in tools {
+        resources.push(json!({
+            "uri": format!("{URI_PREFIX}{tool}.json"),
+            "name": format!("Examples: {tool}"),
+            "description": "Canned request/response pairs for this tool",
+            "mimeType": "application/json",
+        }));
+    }
+}
+
+pub fn read(uri: &str) -> Result<Value> {
+    let tool = uri
+        .strip_prefix(URI_PREFIX)
+        .and_then(|rest| rest.strip_suffix(".json"))
+        .ok_or_else(|| Error::InvalidArguments(format!("invalid examples URI: {uri}")))?;
+    let payloads = for_tool(tool);
+    if payloads.is_empty() {
+        return Err(Error::InvalidArguments(format!(
+            "no examples registered for tool: {tool}"
+        )));
+    }
+    Ok(json!({
+        "uri": uri,
+        "mimeType": "application/json",
+        "text": serde_json::to_string_pretty(&json!({"tool": tool, "examples": payloads}))?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_reference_registered_tools_and_satisfy_required_fields() {
+        let definitions = crate::tools::definitions();
+        for example in examples() {
+            let definition = definitions
+                .iter()
+                .find(|tool| tool.name == example.tool)
+                .unwrap_or_else(|| panic!("example for unregistered tool {}", example.tool));
+            let required = definition.input_schema["required"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            for field in required {
+                let field = field.as_str().unwrap();
+                assert!(
+                    example.request.get(field).is_some(),
+                    "example '{}' for {} misses required field {field}",
+                    example.title,
+                    example.tool
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn example_resources_round_trip_through_read() {
+        let mut resources = Vec::new();
+        list_all(&mut resources);
+        assert!(!resources.is_empty());
+        for resource in resources {
+            let uri = resource["uri"].as_str().unwrap();
+            let contents = read(uri).unwrap();
+            assert_eq!(contents["mimeType"], "application/json");
+        }
+        assert!(read("sonarqube://examples/unknown.json").is_err());
+    }
+}
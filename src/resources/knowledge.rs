@@ -0,0 +1,82 @@
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+
+pub const URI_PREFIX: &str = "sonarqube://kb/";
+
+/// Curated articles embedded at compile time from `docs/knowledge/`.
+const ARTICLES: &[(&str, &str, &str)] = &[
+    (
+        "metrics-and-ratings",
+        "SonarQube metrics and ratings",
+        include_str!("../../docs/knowledge/metrics-and-ratings.md"),
+    ),
+    (
+        "new-code-period",
+        "The new code period",
+        include_str!("../../docs/knowledge/new-code-period.md"),
+    ),
+    (
+        "quality-gates",
+        "Quality gate semantics",
+        include_str!("../../docs/knowledge/quality-gates.md"),
+    ),
+];
+
+fn article_uri(slug: &str) -> String {
+    format!("{URI_PREFIX}{slug}.md")
+}
+
+/// Appends all knowledge base resources (the set is small and unpaginated).
+pub fn list_all(resources: &mut Vec<Value>) {
+    for (slug, title, _) in ARTICLES {
+        resources.push(json!({
+            "uri": article_uri(slug),
+            "name": title,
+            "description": format!("Built-in reference article: {title}"),
+            "mimeType": "text/markdown",
+        }));
+    }
+}
+
+pub fn read(uri: &str) -> Result<Value> {
+    let slug = uri
+        .strip_prefix(URI_PREFIX)
+        .and_then(|rest| rest.strip_suffix(".md"))
+        .ok_or_else(|| Error::InvalidArguments(format!("invalid knowledge base URI: {uri}")))?;
+    let (_, _, text) = ARTICLES
+        .iter()
+        .find(|(candidate, _, _)| *candidate == slug)
+        .ok_or_else(|| Error::InvalidArguments(format!("unknown knowledge base article: {slug}")))?;
+    Ok(json!({
+        "uri": uri,
+        "mimeType": "text/markdown",
+        "text": text,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_article() {
+        let mut resources = Vec::new();
+        list_all(&mut resources);
+        assert_eq!(resources.len(), ARTICLES.len());
+        assert!(resources
+            .iter()
+            .all(|r| r["uri"].as_str().unwrap().starts_with(URI_PREFIX)));
+    }
+
+    #[test]
+    fn reads_article_by_uri() {
+        let contents = read("sonarqube://kb/quality-gates.md").unwrap();
+        assert!(contents["text"].as_str().unwrap().contains("quality gate"));
+    }
+
+    #[test]
+    fn rejects_unknown_article() {
+        assert!(read("sonarqube://kb/missing.md").is_err());
+    }
+}
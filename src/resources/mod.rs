@@ -0,0 +1,66 @@
+pub mod examples;
+pub mod knowledge;
+pub mod project_overview;
+pub mod report;
+pub mod rules;
+
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::server_context::ServerContext;
+
+/// Handles `resources/list`. The cursor is opaque to clients; internally it
+/// is the page number of the underlying rules search.
+pub async fn list(ctx: &ServerContext, cursor: Option<&str>) -> Result<Value> {
+    let page: u32 = match cursor {
+        Some(cursor) => cursor
+            .parse()
+            .map_err(|_| Error::InvalidArguments(format!("invalid cursor: {cursor}")))?,
+        None => 1,
+    };
+    let mut resources = Vec::new();
+    if page == 1 {
+        knowledge::list_all(&mut resources);
+        examples::list_all(&mut resources);
+        resources.push(serde_json::json!({
+            "uri": crate::tools::support_bundle::BUNDLE_URI,
+            "name": "Support bundle",
+            "description": "Redacted config, server stats and recent failing request traces",
+            "mimeType": "application/json",
+        }));
+        project_overview::list_all(ctx, &mut resources).await?;
+        report::list_all(ctx, &mut resources).await?;
+    }
+    let next_cursor = rules::list_page(ctx, page, &mut resources).await?;
+    let mut result = json!({ "resources": resources });
+    if let Some(next) = next_cursor {
+        result["nextCursor"] = Value::String(next);
+    }
+    Ok(result)
+}
+
+/// Handles `resources/read`, dispatching on the URI prefix.
+pub async fn read(ctx: &ServerContext, uri: &str) -> Result<Value> {
+    let contents = if uri.starts_with(rules::URI_PREFIX) {
+        rules::read(ctx, uri).await?
+    } else if uri.starts_with(knowledge::URI_PREFIX) {
+        knowledge::read(uri)?
+    } else if uri.starts_with(examples::URI_PREFIX) {
+        examples::read(uri)?
+    } else if uri.starts_with(project_overview::URI_PREFIX) {
+        project_overview::read(ctx, uri).await?
+    } else if uri.starts_with(report::URI_PREFIX) {
+        report::read(ctx, uri).await?
+    } else if uri == crate::tools::support_bundle::BUNDLE_URI {
+        json!({
+            "uri": uri,
+            "mimeType": "application/json",
+            "text": serde_json::to_string_pretty(&crate::tools::support_bundle::bundle(ctx))?,
+        })
+    } else {
+        return Err(Error::InvalidArguments(format!(
+            "unknown resource URI: {uri}"
+        )));
+    };
+    Ok(json!({ "contents": [contents] }))
+}
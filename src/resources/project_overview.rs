@@ -0,0 +1,200 @@
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::{MeasuresResponse, ProjectStatus};
+
+pub const URI_PREFIX: &str = "sonarqube://project/";
+
+const OVERVIEW_SUFFIX: &str = "/overview.md";
+
+const OVERVIEW_METRICS: &[&str] = &[
+    "ncloc",
+    "bugs",
+    "vulnerabilities",
+    "code_smells",
+    "coverage",
+    "duplicated_lines_density",
+    "sqale_index",
+];
+
+fn overview_uri(project_key: &str) -> String {
+    format!("{URI_PREFIX}{project_key}{OVERVIEW_SUFFIX}")
+}
+
+/// Extracts the project key from `sonarqube://project/{key}/overview.md`.
+fn parse_project_key(uri: &str) -> Option<&str> {
+    let key = uri
+        .strip_prefix(URI_PREFIX)?
+        .strip_suffix(OVERVIEW_SUFFIX)?;
+    if key.is_empty() || key.contains('/') {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+/// Appends an overview resource for each project on the first projects page.
+pub async fn list_all(ctx: &ServerContext, resources: &mut Vec<Value>) -> Result<()> {
+    let projects = ctx.client.list_projects(None, None, Some(100)).await?;
+    for project in &projects.components {
+        resources.push(json!({
+            "uri": overview_uri(&project.key),
+            "name": format!("{} — project overview", project.name),
+            "description": "Generated briefing: metrics, gate status and top violated rules",
+            "mimeType": "text/markdown",
+        }));
+    }
+    Ok(())
+}
+
+/// Builds the overview on demand from the component description, current
+/// measures, quality gate status and the most violated rules.
+pub async fn read(ctx: &ServerContext, uri: &str) -> Result<Value> {
+    let project_key = parse_project_key(uri)
+        .ok_or_else(|| Error::InvalidArguments(format!("invalid project overview URI: {uri}")))?;
+
+    let component: Value = ctx
+        .client
+        .get(
+            "/api/components/show",
+            &[("component", project_key.to_string())],
+        )
+        .await?;
+    let measures = ctx
+        .client
+        .get_measures(
+            project_key,
+            &OVERVIEW_METRICS
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+    let gate = ctx.client.quality_gate_status(project_key).await?;
+    let facets: Value = ctx
+        .client
+        .get(
+            "/api/issues/search",
+            &[
+                ("componentKeys", project_key.to_string()),
+                ("facets", "rules".to_string()),
+                ("ps", "1".to_string()),
+            ],
+        )
+        .await?;
+    let top_rules = extract_rule_facet(&facets);
+
+    Ok(json!({
+        "uri": uri,
+        "mimeType": "text/markdown",
+        "text": render(
+            ctx,
+            project_key,
+            &component,
+            &measures,
+            &gate.project_status,
+            &top_rules,
+        ),
+    }))
+}
+
+/// Pulls `(rule key, count)` pairs out of the `rules` facet of an issues
+/// search response, most violated first.
+fn extract_rule_facet(response: &Value) -> Vec<(String, u64)> {
+    response["facets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|facet| facet["property"] == "rules")
+        .flat_map(|facet| facet["values"].as_array().into_iter().flatten())
+        .filter_map(|value| {
+            Some((
+                value["val"].as_str()?.to_string(),
+                value["count"].as_u64()?,
+            ))
+        })
+        .take(5)
+        .collect()
+}
+
+fn render(
+    ctx: &ServerContext,
+    project_key: &str,
+    component: &Value,
+    measures: &MeasuresResponse,
+    gate: &ProjectStatus,
+    top_rules: &[(String, u64)],
+) -> String {
+    let name = component["component"]["name"]
+        .as_str()
+        .unwrap_or(project_key);
+    let mut out = format!("# {name}\n\n");
+    if let Some(description) = component["component"]["description"].as_str() {
+        out.push_str(description);
+        out.push_str("\n\n");
+    }
+    out.push_str(&format!("**Quality gate**: {}\n\n", gate.status));
+    for condition in &gate.conditions {
+        if condition.status == "ERROR" {
+            out.push_str(&format!(
+                "- failing: {} {} {} (actual {})\n",
+                condition.metric_key,
+                condition.comparator,
+                condition.error_threshold.as_deref().unwrap_or("?"),
+                condition.actual_value.as_deref().unwrap_or("?"),
+            ));
+        }
+    }
+    out.push_str("\n## Key metrics\n\n");
+    for measure in &measures.component.measures {
+        out.push_str(&format!(
+            "- {}: {}\n",
+            measure.metric,
+            measure.value.as_deref().unwrap_or("-"),
+        ));
+    }
+    if !top_rules.is_empty() {
+        out.push_str("\n## Most violated rules\n\n");
+        for (rule, count) in top_rules {
+            out.push_str(&format!("- {rule}: {count} open issues\n"));
+        }
+    }
+    out.push_str(&format!(
+        "\n[Open in SonarQube]({}/dashboard?id={project_key})\n",
+        ctx.config.sonarqube_url.trim_end_matches('/'),
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uri_round_trips_through_parser() {
+        assert_eq!(parse_project_key(&overview_uri("demo")), Some("demo"));
+        assert_eq!(parse_project_key("sonarqube://project//overview.md"), None);
+        assert_eq!(
+            parse_project_key("sonarqube://project/a/b/overview.md"),
+            None
+        );
+    }
+
+    #[test]
+    fn extracts_top_rules_from_facets() {
+        let response = serde_json::json!({
+            "facets": [{
+                "property": "rules",
+                "values": [
+                    {"val": "rust:S1135", "count": 12},
+                    {"val": "rust:S2589", "count": 3},
+                ],
+            }],
+        });
+        assert_eq!(
+            extract_rule_facet(&response),
+            vec![("rust:S1135".to_string(), 12), ("rust:S2589".to_string(), 3)]
+        );
+    }
+}
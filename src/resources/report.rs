@@ -0,0 +1,351 @@
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::SonarQubeIssuesRequest;
+
+pub const URI_PREFIX: &str = "sonarqube://report/";
+
+const REPORT_SUFFIX: &str = ".md";
+
+/// Overall metrics shown in the report.
+const REPORT_METRICS: &[&str] = &[
+    "ncloc",
+    "bugs",
+    "vulnerabilities",
+    "code_smells",
+    "coverage",
+    "duplicated_lines_density",
+];
+
+/// New-code metrics shown in the report; their values live in the measure's
+/// period rather than its plain value.
+const NEW_CODE_METRICS: &[&str] = &[
+    "new_violations",
+    "new_bugs",
+    "new_vulnerabilities",
+    "new_coverage",
+    "new_duplicated_lines_density",
+];
+
+/// Issues listed at the bottom of the report.
+const TOP_ISSUES: u32 = 10;
+
+/// Everything a report renders, gathered once so the Markdown and HTML
+/// renderers cannot drift apart.
+pub struct Report {
+    pub project_key: String,
+    pub name: String,
+    pub gate_status: String,
+    /// Failing conditions, pre-formatted one per line.
+    pub failing_conditions: Vec<String>,
+    pub metrics: Vec<(String, String)>,
+    pub new_code: Vec<(String, String)>,
+    /// (key, severity, message, component) of the most severe open issues.
+    pub top_issues: Vec<(String, String, String, String)>,
+    pub dashboard_url: String,
+}
+
+fn report_uri(project_key: &str) -> String {
+    format!("{URI_PREFIX}{project_key}{REPORT_SUFFIX}")
+}
+
+/// Extracts the project key from `sonarqube://report/{key}.md`.
+fn parse_project_key(uri: &str) -> Option<&str> {
+    let key = uri.strip_prefix(URI_PREFIX)?.strip_suffix(REPORT_SUFFIX)?;
+    if key.is_empty() || key.contains('/') {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+/// Appends a report resource for each project on the first projects page.
+pub async fn list_all(ctx: &ServerContext, resources: &mut Vec<Value>) -> Result<()> {
+    let projects = ctx.client.list_projects(None, None, Some(100)).await?;
+    for project in &projects.components {
+        resources.push(serde_json::json!({
+            "uri": report_uri(&project.key),
+            "name": format!("{} — quality report", project.name),
+            "description": "Generated report: gate status, overall and new-code measures, top issues",
+            "mimeType": "text/markdown",
+        }));
+    }
+    Ok(())
+}
+
+/// Serves the report resource, always as Markdown.
+pub async fn read(ctx: &ServerContext, uri: &str) -> Result<Value> {
+    let project_key = parse_project_key(uri)
+        .ok_or_else(|| Error::InvalidArguments(format!("invalid report URI: {uri}")))?;
+    let report = gather(ctx, project_key).await?;
+    Ok(serde_json::json!({
+        "uri": uri,
+        "mimeType": "text/markdown",
+        "text": render_markdown(&report),
+    }))
+}
+
+/// Collects the report inputs: component name, quality gate, overall and
+/// new-code measures, and the most severe open issues.
+pub async fn gather(ctx: &ServerContext, project_key: &str) -> Result<Report> {
+    let component: Value = ctx
+        .client
+        .get(
+            "/api/components/show",
+            &[("component", project_key.to_string())],
+        )
+        .await?;
+    let gate = ctx.client.quality_gate_status(project_key).await?;
+    let measures = ctx
+        .client
+        .get_measures(
+            project_key,
+            &REPORT_METRICS
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+    let new_code: Value = ctx
+        .client
+        .get(
+            "/api/measures/component",
+            &[
+                ("component", project_key.to_string()),
+                ("metricKeys", NEW_CODE_METRICS.join(",")),
+            ],
+        )
+        .await?;
+    let issues = ctx
+        .client
+        .search_issues(
+            &SonarQubeIssuesRequest::builder(project_key.to_string())
+                .page_size(Some(TOP_ISSUES))
+                .build(),
+        )
+        .await?;
+
+    let failing_conditions = gate
+        .project_status
+        .conditions
+        .iter()
+        .filter(|condition| condition.status == "ERROR")
+        .map(|condition| {
+            format!(
+                "{} {} {} (actual {})",
+                condition.metric_key,
+                condition.comparator,
+                condition.error_threshold.as_deref().unwrap_or("?"),
+                condition.actual_value.as_deref().unwrap_or("?"),
+            )
+        })
+        .collect();
+    let metrics = measures
+        .component
+        .measures
+        .iter()
+        .map(|measure| {
+            (
+                measure.metric.clone(),
+                measure.value.clone().unwrap_or_else(|| "-".to_string()),
+            )
+        })
+        .collect();
+    let new_code = new_code["component"]["measures"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|measure| {
+            Some((
+                measure["metric"].as_str()?.to_string(),
+                period_value(measure)?.to_string(),
+            ))
+        })
+        .collect();
+    let top_issues = issues
+        .issues
+        .iter()
+        .map(|issue| {
+            (
+                issue.key.clone(),
+                issue.severity.as_str().to_string(),
+                issue.message.clone(),
+                issue.component.clone(),
+            )
+        })
+        .collect();
+
+    Ok(Report {
+        project_key: project_key.to_string(),
+        name: component["component"]["name"]
+            .as_str()
+            .unwrap_or(project_key)
+            .to_string(),
+        gate_status: gate.project_status.status,
+        failing_conditions,
+        metrics,
+        new_code,
+        top_issues,
+        dashboard_url: format!(
+            "{}/dashboard?id={project_key}",
+            ctx.config.sonarqube_url.trim_end_matches('/'),
+        ),
+    })
+}
+
+/// New-code measures carry their value in the period; older servers put it
+/// in `value` directly.
+fn period_value(measure: &Value) -> Option<&str> {
+    measure["period"]["value"]
+        .as_str()
+        .or_else(|| measure["value"].as_str())
+}
+
+pub fn render_markdown(report: &Report) -> String {
+    let mut out = format!("# Quality report: {}\n\n", report.name);
+    out.push_str(&format!("**Quality gate**: {}\n\n", report.gate_status));
+    for condition in &report.failing_conditions {
+        out.push_str(&format!("- failing: {condition}\n"));
+    }
+    out.push_str("\n## Overall\n\n");
+    for (metric, value) in &report.metrics {
+        out.push_str(&format!("- {metric}: {value}\n"));
+    }
+    if !report.new_code.is_empty() {
+        out.push_str("\n## New code\n\n");
+        for (metric, value) in &report.new_code {
+            out.push_str(&format!("- {metric}: {value}\n"));
+        }
+    }
+    if !report.top_issues.is_empty() {
+        out.push_str("\n## Top issues\n\n");
+        for (key, severity, message, component) in &report.top_issues {
+            out.push_str(&format!("- [{severity}] {message} ({component}, {key})\n"));
+        }
+    }
+    out.push_str(&format!("\n[Open in SonarQube]({})\n", report.dashboard_url));
+    out
+}
+
+pub fn render_html(report: &Report) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head><title>Quality report: {}</title></head>\n<body>\n",
+        html_escape(&report.name)
+    );
+    out.push_str(&format!("<h1>Quality report: {}</h1>\n", html_escape(&report.name)));
+    out.push_str(&format!(
+        "<p><strong>Quality gate</strong>: {}</p>\n",
+        html_escape(&report.gate_status)
+    ));
+    if !report.failing_conditions.is_empty() {
+        out.push_str("<ul>\n");
+        for condition in &report.failing_conditions {
+            out.push_str(&format!("<li>failing: {}</li>\n", html_escape(condition)));
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("<h2>Overall</h2>\n<ul>\n");
+    for (metric, value) in &report.metrics {
+        out.push_str(&format!(
+            "<li>{}: {}</li>\n",
+            html_escape(metric),
+            html_escape(value)
+        ));
+    }
+    out.push_str("</ul>\n");
+    if !report.new_code.is_empty() {
+        out.push_str("<h2>New code</h2>\n<ul>\n");
+        for (metric, value) in &report.new_code {
+            out.push_str(&format!(
+                "<li>{}: {}</li>\n",
+                html_escape(metric),
+                html_escape(value)
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+    if !report.top_issues.is_empty() {
+        out.push_str("<h2>Top issues</h2>\n<ul>\n");
+        for (key, severity, message, component) in &report.top_issues {
+            out.push_str(&format!(
+                "<li>[{}] {} ({}, {})</li>\n",
+                html_escape(severity),
+                html_escape(message),
+                html_escape(component),
+                html_escape(key),
+            ));
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str(&format!(
+        "<p><a href=\"{}\">Open in SonarQube</a></p>\n</body>\n</html>\n",
+        html_escape(&report.dashboard_url)
+    ));
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo_report() -> Report {
+        Report {
+            project_key: "demo".to_string(),
+            name: "Demo <Project>".to_string(),
+            gate_status: "ERROR".to_string(),
+            failing_conditions: vec!["new_coverage LT 80 (actual 61.0)".to_string()],
+            metrics: vec![("coverage".to_string(), "73.5".to_string())],
+            new_code: vec![("new_bugs".to_string(), "2".to_string())],
+            top_issues: vec![(
+                "AX1".to_string(),
+                "BLOCKER".to_string(),
+                "Fix this".to_string(),
+                "demo:src/Main.java".to_string(),
+            )],
+            dashboard_url: "https://sonar.example.com/dashboard?id=demo".to_string(),
+        }
+    }
+
+    #[test]
+    fn uri_round_trips_through_parser() {
+        assert_eq!(parse_project_key(&report_uri("demo")), Some("demo"));
+        assert_eq!(parse_project_key("sonarqube://report/.md"), None);
+        assert_eq!(parse_project_key("sonarqube://report/a/b.md"), None);
+    }
+
+    #[test]
+    fn markdown_report_covers_every_section() {
+        let markdown = render_markdown(&demo_report());
+        assert!(markdown.contains("# Quality report: Demo <Project>"));
+        assert!(markdown.contains("**Quality gate**: ERROR"));
+        assert!(markdown.contains("- failing: new_coverage LT 80 (actual 61.0)"));
+        assert!(markdown.contains("## New code"));
+        assert!(markdown.contains("- [BLOCKER] Fix this (demo:src/Main.java, AX1)"));
+    }
+
+    #[test]
+    fn html_report_escapes_markup() {
+        let html = render_html(&demo_report());
+        assert!(html.contains("<h1>Quality report: Demo &lt;Project&gt;</h1>"));
+        assert!(!html.contains("<Project>"));
+        assert!(html.contains("<li>new_bugs: 2</li>"));
+    }
+
+    #[test]
+    fn period_values_fall_back_to_plain_values() {
+        let with_period = serde_json::json!({"metric": "new_bugs", "period": {"value": "2"}});
+        assert_eq!(period_value(&with_period), Some("2"));
+        let plain = serde_json::json!({"metric": "new_bugs", "value": "3"});
+        assert_eq!(period_value(&plain), Some("3"));
+        assert_eq!(period_value(&serde_json::json!({})), None);
+    }
+}
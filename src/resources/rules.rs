@@ -0,0 +1,133 @@
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::Rule;
+
+pub const URI_PREFIX: &str = "sonarqube://rules/";
+
+const PAGE_SIZE: u32 = 100;
+
+/// URI for one rule, e.g. `sonarqube://rules/rust/rust:S1135.md`.
+fn rule_uri(rule: &Rule) -> String {
+    let lang = rule.lang.as_deref().unwrap_or("unknown");
+    format!("{URI_PREFIX}{lang}/{}.md", rule.key)
+}
+
+/// Appends one page of rule resources; returns the cursor for the next page.
+pub async fn list_page(
+    ctx: &ServerContext,
+    page: u32,
+    resources: &mut Vec<Value>,
+) -> Result<Option<String>> {
+    let response = ctx.client.search_rules(page, PAGE_SIZE).await?;
+    for rule in &response.rules {
+        resources.push(json!({
+            "uri": rule_uri(rule),
+            "name": rule.name,
+            "description": format!(
+                "{} rule {} ({} {})",
+                rule.lang_name.as_deref().or(rule.lang.as_deref()).unwrap_or("unknown"),
+                rule.key,
+                rule.severity,
+                rule.rule_type,
+            ),
+            "mimeType": "text/markdown",
+        }));
+    }
+    let seen = response.p * response.ps;
+    if seen < response.total {
+        Ok(Some((page + 1).to_string()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Reads one rule resource, rendering rule metadata as markdown.
+pub async fn read(ctx: &ServerContext, uri: &str) -> Result<Value> {
+    let rule_key = parse_rule_key(uri)
+        .ok_or_else(|| Error::InvalidArguments(format!("invalid rule resource URI: {uri}")))?;
+    let rule = ctx.client.show_rule(rule_key).await?.rule;
+    Ok(json!({
+        "uri": uri,
+        "mimeType": "text/markdown",
+        "text": render_markdown(&rule),
+    }))
+}
+
+/// Extracts the rule key from `sonarqube://rules/{lang}/{key}.md`.
+fn parse_rule_key(uri: &str) -> Option<&str> {
+    let rest = uri.strip_prefix(URI_PREFIX)?;
+    let (_lang, key) = rest.split_once('/')?;
+    let key = key.strip_suffix(".md")?;
+    if key.is_empty() {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+fn render_markdown(rule: &Rule) -> String {
+    let mut out = format!("# {}\n\n", rule.name);
+    out.push_str(&format!(
+        "- **Key**: {}\n- **Language**: {}\n- **Severity**: {}\n- **Type**: {}\n\n",
+        rule.key,
+        rule.lang_name
+            .as_deref()
+            .or(rule.lang.as_deref())
+            .unwrap_or("unknown"),
+        rule.severity,
+        rule.rule_type,
+    ));
+    if let Some(md) = &rule.md_desc {
+        out.push_str(md);
+        out.push('\n');
+    } else if let Some(html) = &rule.html_desc {
+        out.push_str(html);
+        out.push('\n');
+    } else {
+        out.push_str("_No description available._\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_rule() -> Rule {
+        Rule {
+            key: "rust:S1135".to_string(),
+            name: "Track uses of TODO tags".to_string(),
+            lang: Some("rust".to_string()),
+            lang_name: Some("Rust".to_string()),
+            severity: crate::sonarqube::types::Severity::Info,
+            rule_type: crate::sonarqube::types::IssueType::CodeSmell,
+            md_desc: Some("TODO tags should be tracked.".to_string()),
+            html_desc: None,
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn uri_round_trips_through_parser() {
+        let uri = rule_uri(&sample_rule());
+        assert_eq!(uri, "sonarqube://rules/rust/rust:S1135.md");
+        assert_eq!(parse_rule_key(&uri), Some("rust:S1135"));
+    }
+
+    #[test]
+    fn rejects_malformed_uris() {
+        assert_eq!(parse_rule_key("sonarqube://rules/rust/.md"), None);
+        assert_eq!(parse_rule_key("sonarqube://rules/no-slash.md"), None);
+        assert_eq!(parse_rule_key("sonarqube://other/x/y.md"), None);
+    }
+
+    #[test]
+    fn renders_rule_markdown() {
+        let markdown = render_markdown(&sample_rule());
+        assert!(markdown.starts_with("# Track uses of TODO tags"));
+        assert!(markdown.contains("**Severity**: INFO"));
+        assert!(markdown.contains("TODO tags should be tracked."));
+    }
+}
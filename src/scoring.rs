@@ -0,0 +1,173 @@
+//! Prioritization weights, tunable without recompiling. A TOML file with a
+//! `[scoring]` section (pointed at by `--scoring-config`) overrides the
+//! built-in defaults; subsystems that rank or schedule issues read their
+//! weights from here. `show_effective_scoring` reports the merged result.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::sonarqube::types::{IssueType, Severity};
+
+/// The `[scoring]` section of the config file. All keys are optional;
+/// anything not set keeps its default weight.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScoringSection {
+    #[serde(default)]
+    severity_weights: BTreeMap<String, f64>,
+    #[serde(default)]
+    type_multipliers: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ScoringFile {
+    #[serde(default)]
+    scoring: ScoringSection,
+}
+
+/// Effective scoring weights after merging any configured overrides over
+/// the defaults.
+#[derive(Debug, Clone, Serialize)]
+pub struct Scoring {
+    /// Relative weight per issue severity; higher means more urgent.
+    pub severity_weights: BTreeMap<String, f64>,
+    /// Multiplier per issue type, applied on top of the severity weight.
+    pub type_multipliers: BTreeMap<String, f64>,
+    /// Where the weights came from: "defaults" or the config file path.
+    pub source: String,
+}
+
+impl Scoring {
+    pub fn defaults() -> Self {
+        let severity_weights = [
+            ("INFO", 1.0),
+            ("MINOR", 2.0),
+            ("MAJOR", 4.0),
+            ("CRITICAL", 8.0),
+            ("BLOCKER", 16.0),
+            ("LOW", 2.0),
+            ("MEDIUM", 4.0),
+            ("HIGH", 8.0),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        let type_multipliers = [
+            ("CODE_SMELL", 1.0),
+            ("BUG", 1.5),
+            ("VULNERABILITY", 2.0),
+            ("SECURITY_HOTSPOT", 1.5),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        Self {
+            severity_weights,
+            type_multipliers,
+            source: "defaults".to_string(),
+        }
+    }
+
+    /// Loads and validates overrides from a TOML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path).map_err(|err| {
+            Error::Config(format!("cannot read scoring config {}: {err}", path.display()))
+        })?;
+        let mut scoring = Self::from_toml_str(&raw)
+            .map_err(|err| Error::Config(format!("in {}: {err}", path.display())))?;
+        scoring.source = path.display().to_string();
+        Ok(scoring)
+    }
+
+    /// Parses a `[scoring]` section and merges it over the defaults,
+    /// rejecting unknown keys and non-finite or negative weights.
+    fn from_toml_str(raw: &str) -> std::result::Result<Self, String> {
+        let file: ScoringFile = toml::from_str(raw).map_err(|err| err.to_string())?;
+        let mut scoring = Self::defaults();
+        for (key, value) in &file.scoring.severity_weights {
+            if !Severity::VALUES.contains(&key.as_str()) {
+                return Err(format!(
+                    "unknown severity {key:?} in [scoring.severity_weights]; expected one of {}",
+                    Severity::VALUES.join(", ")
+                ));
+            }
+            validate_weight(key, *value)?;
+            scoring.severity_weights.insert(key.clone(), *value);
+        }
+        for (key, value) in &file.scoring.type_multipliers {
+            if !IssueType::VALUES.contains(&key.as_str()) {
+                return Err(format!(
+                    "unknown issue type {key:?} in [scoring.type_multipliers]; expected one of {}",
+                    IssueType::VALUES.join(", ")
+                ));
+            }
+            validate_weight(key, *value)?;
+            scoring.type_multipliers.insert(key.clone(), *value);
+        }
+        Ok(scoring)
+    }
+
+    /// Weight for a severity; unknown (future) severities score as MAJOR so
+    /// they are neither buried nor inflated.
+    pub fn weight(&self, severity: Severity) -> f64 {
+        self.severity_weights
+            .get(severity.as_str())
+            .copied()
+            .unwrap_or(4.0)
+    }
+
+    /// Multiplier for an issue type; unknown types are neutral.
+    pub fn multiplier(&self, issue_type: IssueType) -> f64 {
+        self.type_multipliers
+            .get(issue_type.as_str())
+            .copied()
+            .unwrap_or(1.0)
+    }
+}
+
+fn validate_weight(key: &str, value: f64) -> std::result::Result<(), String> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(format!("weight for {key} must be a non-negative number, got {value}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_merge_over_the_defaults() {
+        let scoring = Scoring::from_toml_str(
+            "[scoring.severity_weights]\nBLOCKER = 100.0\n\n[scoring.type_multipliers]\nBUG = 3.0\n",
+        )
+        .unwrap();
+        assert_eq!(scoring.weight(Severity::Blocker), 100.0);
+        assert_eq!(scoring.weight(Severity::Minor), 2.0);
+        assert_eq!(scoring.multiplier(IssueType::Bug), 3.0);
+        assert_eq!(scoring.multiplier(IssueType::CodeSmell), 1.0);
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_negative_weights() {
+        let unknown = Scoring::from_toml_str("[scoring.severity_weights]\nURGENT = 1.0\n");
+        assert!(unknown.unwrap_err().contains("unknown severity"));
+
+        let negative = Scoring::from_toml_str("[scoring.type_multipliers]\nBUG = -1.0\n");
+        assert!(negative.unwrap_err().contains("non-negative"));
+
+        let typo = Scoring::from_toml_str("[scoring]\nseverty_weights = {}\n");
+        assert!(typo.is_err());
+    }
+
+    #[test]
+    fn empty_file_yields_the_defaults() {
+        let scoring = Scoring::from_toml_str("").unwrap();
+        assert_eq!(scoring.weight(Severity::Blocker), 16.0);
+        assert_eq!(scoring.source, "defaults");
+    }
+}
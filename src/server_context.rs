@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::diagnostics::Diagnostics;
+use crate::error::{Error, Result};
+use crate::mcp::notifier::Notifier;
+use crate::sonarqube::client::SonarQubeClient;
+use crate::watch::Watchlist;
+
+/// Shared state handed to every tool invocation.
+pub struct ServerContext {
+    pub config: Config,
+    pub client: SonarQubeClient,
+    pub notifier: Arc<Notifier>,
+    pub diagnostics: Arc<Diagnostics>,
+    pub watchlist: Watchlist,
+    /// Parsed --severity-map entries, applied in the tool output layer.
+    pub severity_map: std::collections::HashMap<String, String>,
+    /// Effective prioritization weights, defaults merged with
+    /// --scoring-config overrides.
+    pub scoring: crate::scoring::Scoring,
+    /// Tenant registry for hosted deployments; None means single-tenant.
+    pub tenants: Option<crate::tenants::TenantRegistry>,
+    /// The tenant this context is scoped to; None for the host context and
+    /// for single-tenant deployments. Set by `for_tenant`, consulted by the
+    /// tool dispatcher to enforce the tenant's project allowlist.
+    pub tenant: Option<crate::tenants::Tenant>,
+    /// Named SonarQube instances; None means the single primary instance.
+    pub instances: Option<crate::instances::InstanceRegistry>,
+    /// TTL cache for GET responses, shared with the client.
+    pub cache: Arc<crate::cache::ResponseCache>,
+    /// Deduplicates identical in-flight requests, shared with the client.
+    pub coalescer: Arc<crate::coalesce::RequestCoalescer>,
+    /// Audit trail of tool invocations; None when --audit-log is unset.
+    pub audit: Option<crate::audit::AuditLog>,
+}
+
+/// SonarQube credentials a network session may present at initialization,
+/// either as init params or transport headers. Unset fields keep the
+/// server-wide configuration.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct SessionCredentials {
+    pub url: Option<String>,
+    pub token: Option<String>,
+    pub organization: Option<String>,
+}
+
+impl SessionCredentials {
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none() && self.token.is_none() && self.organization.is_none()
+    }
+}
+
+impl ServerContext {
+    pub fn new(config: Config) -> Result<Self> {
+        Self::build(
+            config,
+            Arc::new(Notifier::default()),
+            Arc::new(Diagnostics::default()),
+        )
+    }
+
+    /// Derives a context whose client speaks to the session's own SonarQube
+    /// URL/token/organization, so a shared gateway can serve several teams.
+    /// The cache and coalescer are fresh — sessions must not observe each
+    /// other's cached responses — while the notifier and diagnostics stay
+    /// shared with the host context.
+    pub fn with_session_credentials(&self, credentials: &SessionCredentials) -> Result<Self> {
+        if credentials.url.is_some() && credentials.token.is_none() {
+            // A URL-only override would point the server-wide token at a
+            // host of the session's choosing (the client auto-allowlists
+            // its own SonarQube host) -- exactly the exfiltration the
+            // outbound allowlist exists to prevent.
+            return Err(Error::InvalidArguments(
+                "overriding the SonarQube URL requires presenting a token for it; \
+                 the server-wide credential is never sent to a session-supplied host"
+                    .to_string(),
+            ));
+        }
+        let mut config = self.config.clone();
+        if let Some(url) = &credentials.url {
+            config.sonarqube_url = url.clone();
+        }
+        if let Some(token) = &credentials.token {
+            // A directly supplied token bypasses whatever provider the
+            // server-wide credential uses.
+            config.sonarqube_token = token.clone();
+            config.auth_provider = crate::auth::AuthProviderKind::Static;
+        }
+        if let Some(organization) = &credentials.organization {
+            config.organization = Some(organization.clone());
+        }
+        Self::build(
+            config,
+            Arc::clone(&self.notifier),
+            Arc::clone(&self.diagnostics),
+        )
+    }
+
+    /// Derives the context a tenant's sessions operate in: the tenant's
+    /// own upstream token, isolated cache and coalescer, and the admin
+    /// flag narrowed to what both the server and the tenant allow.
+    pub fn for_tenant(&self, tenant: &crate::tenants::Tenant) -> Result<Self> {
+        let mut derived = self.with_session_credentials(&SessionCredentials {
+            token: Some(tenant.sonarqube_token.clone()),
+            ..Default::default()
+        })?;
+        derived.config.allow_admin_operations &= tenant.allow_admin_operations;
+        derived.tenant = Some(tenant.clone());
+        Ok(derived)
+    }
+
+    fn build(
+        config: Config,
+        notifier: Arc<Notifier>,
+        diagnostics: Arc<Diagnostics>,
+    ) -> Result<Self> {
+        let auth = crate::auth::from_config(&config)?;
+        let cache = Arc::new(crate::cache::ResponseCache::from_config(&config));
+        let coalescer = Arc::new(crate::coalesce::RequestCoalescer::default());
+        let client = SonarQubeClient::new(
+            &config,
+            auth,
+            Arc::clone(&diagnostics),
+            Arc::clone(&cache),
+            Arc::clone(&coalescer),
+            Arc::clone(&notifier),
+        )?;
+        let watchlist = Watchlist::from_config(&config);
+        let severity_map = crate::severity_map::parse_mapping(&config.severity_map);
+        let scoring = match &config.scoring_config {
+            Some(path) => crate::scoring::Scoring::load(path)?,
+            None => crate::scoring::Scoring::defaults(),
+        };
+        let tenants = match &config.tenants_config {
+            Some(path) => {
+                let registry = crate::tenants::TenantRegistry::load(path)?;
+                tracing::info!(tenants = ?registry.names(), "loaded tenant registry");
+                Some(registry)
+            }
+            None => None,
+        };
+        let audit = match &config.audit_log {
+            Some(path) => Some(crate::audit::AuditLog::open(path)?),
+            None => None,
+        };
+        let instances = match &config.instances_config {
+            Some(path) => {
+                let registry = crate::instances::InstanceRegistry::load(path)?;
+                tracing::info!(instances = ?registry.names(), "loaded instance registry");
+                Some(registry)
+            }
+            None => None,
+        };
+        Ok(Self {
+            config,
+            client,
+            notifier,
+            diagnostics,
+            watchlist,
+            severity_map,
+            scoring,
+            tenants,
+            tenant: None,
+            instances,
+            cache,
+            coalescer,
+            audit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    fn context() -> ServerContext {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+            "--sonarqube-token",
+            "squ_shared",
+        ]);
+        ServerContext::new(config).expect("context")
+    }
+
+    #[test]
+    fn session_credentials_override_url_token_and_organization() {
+        let host = context();
+        let derived = host
+            .with_session_credentials(&SessionCredentials {
+                url: Some("http://team.example.com".to_string()),
+                token: Some("squ_team".to_string()),
+                organization: Some("team-org".to_string()),
+            })
+            .expect("derived context");
+        assert_eq!(derived.config.sonarqube_url, "http://team.example.com");
+        assert_eq!(derived.config.sonarqube_token, "squ_team");
+        assert_eq!(derived.config.organization.as_deref(), Some("team-org"));
+        assert_eq!(
+            derived.config.auth_provider,
+            crate::auth::AuthProviderKind::Static
+        );
+        // The host keeps its own configuration untouched.
+        assert_eq!(host.config.sonarqube_token, "squ_shared");
+    }
+
+    #[test]
+    fn a_url_override_without_its_own_token_is_refused() {
+        let host = context();
+        let refused = host.with_session_credentials(&SessionCredentials {
+            url: Some("https://attacker.example".to_string()),
+            ..Default::default()
+        });
+        let err = match refused {
+            Err(err) => err,
+            Ok(_) => panic!("url-only credentials must be refused"),
+        };
+        assert!(err.to_string().contains("requires presenting a token"));
+    }
+
+    #[test]
+    fn unset_credentials_keep_the_server_wide_configuration() {
+        let host = context();
+        let derived = host
+            .with_session_credentials(&SessionCredentials {
+                organization: Some("team-org".to_string()),
+                ..Default::default()
+            })
+            .expect("derived context");
+        assert_eq!(derived.config.sonarqube_url, "http://localhost:9000");
+        assert_eq!(derived.config.sonarqube_token, "squ_shared");
+    }
+}
@@ -0,0 +1,87 @@
+//! Output-layer severity mapping. Organizations rarely speak SonarQube's
+//! severity scale; a configured mapping (e.g. `BLOCKER=P0,CRITICAL=P1`)
+//! annotates every object carrying a `severity` field with the equivalent
+//! `priority`. The raw severity is always retained.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// Parses `SEVERITY=LABEL` pairs, ignoring malformed entries with a warning.
+pub fn parse_mapping(entries: &[String]) -> HashMap<String, String> {
+    let mut mapping = HashMap::new();
+    for entry in entries {
+        match entry.split_once('=') {
+            Some((severity, label)) if !severity.trim().is_empty() && !label.trim().is_empty() => {
+                mapping.insert(
+                    severity.trim().to_ascii_uppercase(),
+                    label.trim().to_string(),
+                );
+            }
+            _ => tracing::warn!("ignoring malformed severity mapping entry: {entry:?}"),
+        }
+    }
+    mapping
+}
+
+/// Recursively annotates objects that have a mapped `severity` with the
+/// organization's `priority` label.
+pub fn annotate(value: &mut Value, mapping: &HashMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            let priority = map
+                .get("severity")
+                .and_then(Value::as_str)
+                .and_then(|severity| mapping.get(&severity.to_ascii_uppercase()))
+                .cloned();
+            if let Some(priority) = priority {
+                map.insert("priority".to_string(), Value::String(priority));
+            }
+            for nested in map.values_mut() {
+                annotate(nested, mapping);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                annotate(item, mapping);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_pairs_and_skips_malformed_entries() {
+        let mapping = parse_mapping(&[
+            "BLOCKER=P0".to_string(),
+            "critical=P1".to_string(),
+            "nonsense".to_string(),
+            "=P2".to_string(),
+        ]);
+        assert_eq!(mapping.len(), 2);
+        assert_eq!(mapping["BLOCKER"], "P0");
+        assert_eq!(mapping["CRITICAL"], "P1");
+    }
+
+    #[test]
+    fn annotates_nested_severities_and_keeps_the_raw_value() {
+        let mapping = parse_mapping(&["BLOCKER=P0".to_string(), "MAJOR=P2".to_string()]);
+        let mut value = json!({
+            "issues": [
+                {"key": "a", "severity": "BLOCKER"},
+                {"key": "b", "severity": "MINOR"},
+                {"key": "c", "impacts": [{"softwareQuality": "SECURITY", "severity": "MAJOR"}]},
+            ],
+        });
+        annotate(&mut value, &mapping);
+        assert_eq!(value["issues"][0]["priority"], "P0");
+        assert_eq!(value["issues"][0]["severity"], "BLOCKER");
+        assert!(value["issues"][1].get("priority").is_none());
+        assert_eq!(value["issues"][2]["impacts"][0]["priority"], "P2");
+    }
+}
@@ -0,0 +1,1141 @@
+use std::sync::{Arc, RwLock};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
+
+use crate::auth::AuthProvider;
+use crate::coalesce::RequestCoalescer;
+use crate::config::Config;
+use crate::cache::ResponseCache;
+use crate::diagnostics::Diagnostics;
+use crate::error::{Error, Result};
+use crate::sonarqube::types::{
+    BranchesResponse, CeComponentResponse, CeTaskResponse, IssuesResponse, MeasuresResponse,
+    Paging, ProjectsResponse, QualityGateStatusResponse, RuleShowResponse, RulesResponse,
+    Severity, SonarQubeIssuesRequest,
+};
+use crate::sonarqube::version::ServerVersion;
+
+/// Header carrying the configured deployment tag, for traffic attribution
+/// in SonarQube access logs.
+const TAG_HEADER: &str = "x-sonar-mcp-tag";
+
+/// Longest we delay a request to satisfy the server's rate limit before
+/// letting it proceed anyway.
+const MAX_RATE_LIMIT_WAIT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Tracks the server's `X-RateLimit-Remaining`/`X-RateLimit-Reset` response
+/// headers so the client delays requests once the quota is exhausted and
+/// retries after a 429, instead of surfacing raw rate-limit errors to the
+/// MCP client.
+#[derive(Default)]
+struct RateLimiter {
+    state: std::sync::Mutex<RateLimitState>,
+}
+
+#[derive(Default)]
+struct RateLimitState {
+    remaining: Option<u64>,
+    reset_unix: Option<u64>,
+}
+
+impl RateLimiter {
+    /// How long to wait before the next request; None while the quota has
+    /// room or after the advertised reset has passed.
+    fn delay(&self, now_unix: u64) -> Option<std::time::Duration> {
+        let state = self.state.lock().expect("rate limiter poisoned");
+        if state.remaining != Some(0) {
+            return None;
+        }
+        let reset = state.reset_unix?;
+        if reset <= now_unix {
+            return None;
+        }
+        Some(std::time::Duration::from_secs(reset - now_unix).min(MAX_RATE_LIMIT_WAIT))
+    }
+
+    fn observe(&self, remaining: Option<u64>, reset_unix: Option<u64>) {
+        let mut state = self.state.lock().expect("rate limiter poisoned");
+        if remaining.is_some() {
+            state.remaining = remaining;
+        }
+        if reset_unix.is_some() {
+            state.reset_unix = reset_unix;
+        }
+    }
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// Page size used when fetching all pages; the Web API maximum.
+const ALL_PAGES_PAGE_SIZE: u32 = 500;
+
+/// SonarQube refuses to serve results past this offset (`p * ps` must stay
+/// within it), so all-pages fetches stop at the window even when the total
+/// is larger.
+const PAGINATION_WINDOW: u32 = 10_000;
+
+/// Page numbers an all-pages fetch still needs after the first page, given
+/// that page's paging block, the configured hard cap, and the server's
+/// pagination window.
+fn remaining_pages(first: &Paging, cap: u32) -> std::ops::RangeInclusive<u32> {
+    let page_size = first.page_size.max(1);
+    let last_by_total = first.total.min(cap).div_ceil(page_size);
+    let last_by_window = PAGINATION_WINDOW / page_size;
+    2..=last_by_total.min(last_by_window)
+}
+
+/// Consecutive failures that open the circuit.
+const CIRCUIT_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long requests are short-circuited once the circuit opens.
+const CIRCUIT_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Opens after [`CIRCUIT_FAILURE_THRESHOLD`] consecutive transport or
+/// server errors, failing tool calls fast for a cooldown period instead of
+/// letting every call run into a long timeout while SonarQube is down.
+#[derive(Default)]
+struct CircuitBreaker {
+    state: std::sync::Mutex<CircuitState>,
+}
+
+#[derive(Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    open_until_unix: u64,
+}
+
+impl CircuitBreaker {
+    /// Seconds of cooldown remaining, or None when requests may proceed.
+    fn open_for(&self, now_unix: u64) -> Option<u64> {
+        let state = self.state.lock().expect("circuit breaker poisoned");
+        (state.open_until_unix > now_unix).then(|| state.open_until_unix - now_unix)
+    }
+
+    /// Records a failure; true when this one opened (or re-opened) the
+    /// circuit.
+    fn on_failure(&self, now_unix: u64) -> bool {
+        let mut state = self.state.lock().expect("circuit breaker poisoned");
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= CIRCUIT_FAILURE_THRESHOLD
+            && state.open_until_unix <= now_unix
+        {
+            state.open_until_unix = now_unix + CIRCUIT_COOLDOWN.as_secs();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn on_success(&self) {
+        let mut state = self.state.lock().expect("circuit breaker poisoned");
+        state.consecutive_failures = 0;
+        state.open_until_unix = 0;
+    }
+}
+
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Thin wrapper over the SonarQube Web API.
+pub struct SonarQubeClient {
+    http: reqwest::Client,
+    base_url: String,
+    auth: Box<dyn AuthProvider>,
+    organization: Option<String>,
+    /// Hosts outbound requests may reach; always contains the SonarQube host.
+    allowed_hosts: Vec<String>,
+    /// Static headers from configuration, sent on every request.
+    extra_headers: HeaderMap,
+    /// Header used to forward the end-user identity of the current session.
+    impersonation_header: Option<HeaderName>,
+    impersonated_user: RwLock<Option<String>>,
+    /// Send the token as the Basic username instead of a Bearer header.
+    basic_auth_token: bool,
+    /// Base User-Agent; the MCP client name is appended once known.
+    user_agent: String,
+    /// Detected server version, fetched lazily on first use.
+    version: tokio::sync::OnceCell<ServerVersion>,
+    diagnostics: Arc<Diagnostics>,
+    cache: Arc<ResponseCache>,
+    coalescer: Arc<RequestCoalescer>,
+    /// Bounds how many pages an all-pages fetch has in flight at once.
+    page_semaphore: tokio::sync::Semaphore,
+    rate_limiter: RateLimiter,
+    circuit: CircuitBreaker,
+    notifier: Arc<crate::mcp::notifier::Notifier>,
+}
+
+impl SonarQubeClient {
+    pub fn new(
+        config: &Config,
+        auth: Box<dyn AuthProvider>,
+        diagnostics: Arc<Diagnostics>,
+        cache: Arc<ResponseCache>,
+        coalescer: Arc<RequestCoalescer>,
+        notifier: Arc<crate::mcp::notifier::Notifier>,
+    ) -> Result<Self> {
+        let base_url = config.sonarqube_url.trim_end_matches('/').to_string();
+        let mut allowed_hosts: Vec<String> = config
+            .outbound_allowlist
+            .iter()
+            .map(|host| host.trim().to_ascii_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect();
+        if let Ok(url) = reqwest::Url::parse(&base_url) {
+            if let Some(host) = url.host_str() {
+                allowed_hosts.push(host.to_ascii_lowercase());
+            }
+        }
+        // Redirects could otherwise smuggle a request off the allowlist.
+        let redirect_hosts = allowed_hosts.clone();
+        let mut builder = reqwest::Client::builder().redirect(reqwest::redirect::Policy::custom(
+            move |attempt| {
+                if host_allowed(attempt.url(), &redirect_hosts) {
+                    attempt.follow()
+                } else {
+                    attempt.error("redirect target not in outbound allowlist")
+                }
+            },
+        ));
+        if let Some(path) = &config.ca_cert {
+            let pem = std::fs::read(path).map_err(|err| {
+                Error::Config(format!("cannot read CA bundle {}: {err}", path.display()))
+            })?;
+            let certs = reqwest::Certificate::from_pem_bundle(&pem).map_err(|err| {
+                Error::Config(format!("invalid CA bundle {}: {err}", path.display()))
+            })?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        if config.danger_accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        if config.connect_timeout_seconds > 0 {
+            builder = builder
+                .connect_timeout(std::time::Duration::from_secs(config.connect_timeout_seconds));
+        }
+        if config.request_timeout_seconds > 0 {
+            builder =
+                builder.timeout(std::time::Duration::from_secs(config.request_timeout_seconds));
+        }
+        if config.pool_max_idle_per_host > 0 {
+            builder = builder.pool_max_idle_per_host(config.pool_max_idle_per_host);
+        }
+        if config.http1_only {
+            builder = builder.http1_only();
+        }
+        if let Some(url) = &config.proxy_url {
+            // HTTPS_PROXY/NO_PROXY already apply via reqwest's defaults;
+            // an explicit proxy overrides them but keeps NO_PROXY
+            // exemptions.
+            let proxy = reqwest::Proxy::all(url)
+                .map_err(|err| Error::Config(format!("invalid proxy URL {url}: {err}")))?
+                .no_proxy(reqwest::NoProxy::from_env());
+            builder = builder.proxy(proxy);
+        }
+        match (&config.client_cert, &config.client_key) {
+            (Some(cert_path), Some(key_path)) => {
+                // rustls wants certificate and key in one PEM buffer.
+                let mut pem = std::fs::read(cert_path).map_err(|err| {
+                    Error::Config(format!("cannot read client cert {}: {err}", cert_path.display()))
+                })?;
+                pem.extend(std::fs::read(key_path).map_err(|err| {
+                    Error::Config(format!("cannot read client key {}: {err}", key_path.display()))
+                })?);
+                let identity = reqwest::Identity::from_pem(&pem).map_err(|err| {
+                    Error::Config(format!("invalid client certificate or key: {err}"))
+                })?;
+                builder = builder.identity(identity);
+            }
+            (None, None) => {}
+            _ => {
+                return Err(Error::Config(
+                    "--client-cert and --client-key must be set together".to_string(),
+                ))
+            }
+        }
+        let http = builder
+            .build()
+            .map_err(|err| Error::Config(format!("cannot construct HTTP client: {err}")))?;
+        let mut extra_headers = parse_extra_headers(&config.extra_headers);
+        if let Some(tag) = &config.request_tag {
+            if let Ok(value) = HeaderValue::try_from(tag.as_str()) {
+                extra_headers.insert(HeaderName::from_static(TAG_HEADER), value);
+            }
+        }
+        Ok(Self {
+            http,
+            base_url,
+            auth,
+            organization: config.organization.clone(),
+            allowed_hosts,
+            extra_headers,
+            impersonation_header: config
+                .impersonation_header
+                .as_deref()
+                .and_then(|name| HeaderName::try_from(name).ok()),
+            impersonated_user: RwLock::new(None),
+            basic_auth_token: config.basic_auth_token,
+            user_agent: config.user_agent.clone().unwrap_or_else(|| {
+                format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+            }),
+            version: tokio::sync::OnceCell::new(),
+            diagnostics,
+            cache,
+            coalescer,
+            page_semaphore: tokio::sync::Semaphore::new(config.all_pages_concurrency.max(1)),
+            rate_limiter: RateLimiter::default(),
+            circuit: CircuitBreaker::default(),
+            notifier,
+        })
+    }
+
+    /// Attaches the credential the way the server expects: a Bearer header
+    /// by default, or the token as the Basic username with an empty
+    /// password for SonarQube versions before 10.
+    async fn authorize(&self, builder: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder> {
+        let token = self.auth.token().await?;
+        Ok(if self.basic_auth_token {
+            builder.basic_auth(token, None::<&str>)
+        } else {
+            builder.bearer_auth(token)
+        })
+    }
+
+    /// Fails fast while the circuit breaker is open.
+    fn check_circuit(&self) -> Result<()> {
+        match self.circuit.open_for(now_unix()) {
+            Some(remaining) => Err(Error::CircuitOpen(remaining)),
+            None => Ok(()),
+        }
+    }
+
+    /// Feeds the circuit breaker: transport errors and 5xx responses count
+    /// as outage signals, any other response proves the server reachable.
+    fn record_circuit_outcome(&self, outage: bool) {
+        if !outage {
+            self.circuit.on_success();
+            return;
+        }
+        if self.circuit.on_failure(now_unix()) {
+            let message = format!(
+                "SonarQube looks down ({CIRCUIT_FAILURE_THRESHOLD} consecutive failures); \
+                 failing requests fast for the next {}s",
+                CIRCUIT_COOLDOWN.as_secs()
+            );
+            tracing::error!("{message}");
+            self.notifier.notify(
+                "notifications/message",
+                serde_json::json!({
+                    "level": "error",
+                    "logger": "sonarqube.client",
+                    "data": {"message": message},
+                }),
+            );
+        }
+    }
+
+    /// Sends a prepared request, delaying when the advertised quota is
+    /// exhausted and retrying once after a 429.
+    async fn send_rate_limited(
+        &self,
+        request: reqwest::RequestBuilder,
+        path: &str,
+    ) -> Result<reqwest::Response> {
+        self.check_circuit()?;
+        let mut request = Some(request);
+        let mut retried = false;
+        loop {
+            if let Some(delay) = self.rate_limiter.delay(now_unix()) {
+                tracing::debug!("rate limit exhausted; delaying {path} by {delay:?}");
+                tokio::time::sleep(delay).await;
+            }
+            let builder = match request.as_ref().and_then(|r| r.try_clone()) {
+                Some(clone) => clone,
+                // Unclonable bodies cannot be retried; send the original.
+                None => request.take().expect("request already consumed"),
+            };
+            let response = builder
+                .send()
+                .await
+                .inspect_err(|err| {
+                    self.diagnostics.record_failure(path, &err.to_string());
+                    self.record_circuit_outcome(true);
+                })?;
+            self.record_circuit_outcome(response.status().is_server_error());
+            self.rate_limiter.observe(
+                header_u64(response.headers(), "x-ratelimit-remaining"),
+                header_u64(response.headers(), "x-ratelimit-reset"),
+            );
+            if response.status().as_u16() == 429 && !retried && request.is_some() {
+                retried = true;
+                let wait = header_u64(response.headers(), "retry-after")
+                    .map(std::time::Duration::from_secs)
+                    .or_else(|| self.rate_limiter.delay(now_unix()))
+                    .unwrap_or(std::time::Duration::from_secs(5))
+                    .min(MAX_RATE_LIMIT_WAIT);
+                tracing::warn!("SonarQube rate limited {path} (HTTP 429); retrying in {wait:?}");
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+            return Ok(response);
+        }
+    }
+
+    /// Records the end-user identity of the current MCP session, forwarded
+    /// on subsequent requests via the configured impersonation header.
+    /// Client name reported at initialization, recorded in audit records.
+    pub fn impersonated_user(&self) -> Option<String> {
+        self.impersonated_user.read().expect("lock poisoned").clone()
+    }
+
+    pub fn set_impersonated_user(&self, user: Option<String>) {
+        *self.impersonated_user.write().expect("lock poisoned") = user;
+    }
+
+    /// Headers applied to every outgoing request: the configured static set,
+    /// a descriptive User-Agent, plus the impersonation header when a
+    /// session user is known.
+    fn request_headers(&self) -> HeaderMap {
+        let mut headers = self.extra_headers.clone();
+        let user_agent = match self.impersonated_user.read().expect("lock poisoned").as_deref() {
+            Some(client_name) => format!("{} ({client_name})", self.user_agent),
+            None => self.user_agent.clone(),
+        };
+        if let Ok(value) = HeaderValue::try_from(user_agent) {
+            headers.insert(reqwest::header::USER_AGENT, value);
+        }
+        if let (Some(name), Some(user)) = (
+            &self.impersonation_header,
+            self.impersonated_user
+                .read()
+                .expect("lock poisoned")
+                .as_deref(),
+        ) {
+            if let Ok(value) = HeaderValue::try_from(user) {
+                headers.insert(name.clone(), value);
+            }
+        }
+        headers
+    }
+
+    /// Refuses any URL whose host is not allowlisted. The client only builds
+    /// URLs from the configured base today; this guards future extensions.
+    pub(crate) fn check_outbound(&self, url: &str) -> Result<()> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|_| Error::OutboundBlocked(format!("unparseable URL: {url}")))?;
+        if host_allowed(&parsed, &self.allowed_hosts) {
+            Ok(())
+        } else {
+            Err(Error::OutboundBlocked(
+                parsed.host_str().unwrap_or(url).to_string(),
+            ))
+        }
+    }
+
+    /// Performs a GET against a Web API path (e.g. `/api/issues/search`) and
+    /// deserializes the JSON response.
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T> {
+        self.get_with_cache(path, query, true).await
+    }
+
+    /// Same as [`Self::get`], honouring a per-request cache opt-out. Only
+    /// endpoints the [`ResponseCache`] declares a TTL for are cached.
+    pub async fn get_with_cache<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        use_cache: bool,
+    ) -> Result<T> {
+        let ttl = if use_cache { self.cache.ttl_for(path) } else { None };
+        let key = ResponseCache::key(path, query);
+        if let Some(ttl) = ttl {
+            if let Some(hit) = self.cache.get(&key, ttl) {
+                return Ok(serde_json::from_value(hit)?);
+            }
+        }
+        // Identical concurrent calls share one upstream request; the flight
+        // is keyed like the cache, so coalescing mirrors cacheability of the
+        // parameters rather than inventing a second identity.
+        let flight = self.coalescer.join(&key);
+        let value = flight
+            .get_or_try_init(|| self.fetch_json(path, query))
+            .await
+            .cloned();
+        self.coalescer.complete(&key, &flight);
+        let value = value?;
+        if ttl.is_some() {
+            self.cache.put(key, value.clone());
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Performs the actual GET for [`Self::get_with_cache`], bypassing cache
+    /// and coalescing.
+    async fn fetch_json(&self, path: &str, query: &[(&str, String)]) -> Result<serde_json::Value> {
+        let url = format!("{}{}", self.base_url, path);
+        self.check_outbound(&url)?;
+        let builder = self
+            .authorize(self.http.get(&url))
+            .await?
+            .headers(self.request_headers())
+            .query(query);
+        let response = self.send_rate_limited(builder, path).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = parse_error_message(&body);
+            self.diagnostics
+                .record_failure(path, &format!("HTTP {status}: {message}"));
+            return Err(Error::Api {
+                status: status.as_u16(),
+                message,
+            });
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        if let Some(message) = non_json_response_message(status.as_u16(), &content_type) {
+            self.diagnostics.record_failure(path, &message);
+            return Err(Error::Api {
+                status: status.as_u16(),
+                message,
+            });
+        }
+        self.diagnostics.record_success();
+        self.note_deprecations(path, response.headers());
+        Ok(response.json().await?)
+    }
+
+    /// Warns (once per notice) when SonarQube flags the endpoint or a sent
+    /// parameter as deprecated via response headers. Notices accumulate in
+    /// diagnostics so `sonarqube_get_server_stats` can report them.
+    fn note_deprecations(&self, path: &str, headers: &reqwest::header::HeaderMap) {
+        for name in ["sonar-deprecation", "deprecation", "sunset"] {
+            for value in headers.get_all(name) {
+                let Ok(value) = value.to_str() else { continue };
+                let notice = format!("{path}: {name}: {value}");
+                if self.diagnostics.record_deprecation(&notice) {
+                    tracing::warn!(
+                        "SonarQube deprecation notice on {path}: {name}: {value} \
+                         (reported once; see sonarqube_get_server_stats)"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Performs a GET returning the raw body and its content type, for
+    /// endpoints serving non-JSON payloads such as badges.
+    pub async fn get_bytes(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<(Vec<u8>, String)> {
+        let url = format!("{}{}", self.base_url, path);
+        self.check_outbound(&url)?;
+        let builder = self
+            .authorize(self.http.get(&url))
+            .await?
+            .headers(self.request_headers())
+            .query(query);
+        let response = self.send_rate_limited(builder, path).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = parse_error_message(&body);
+            self.diagnostics
+                .record_failure(path, &format!("HTTP {status}: {message}"));
+            return Err(Error::Api {
+                status: status.as_u16(),
+                message,
+            });
+        }
+        self.diagnostics.record_success();
+        self.note_deprecations(path, response.headers());
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        Ok((response.bytes().await?.to_vec(), content_type))
+    }
+
+    /// Performs a form-encoded POST, used by Web API write endpoints.
+    pub async fn post(&self, path: &str, form: &[(&str, String)]) -> Result<()> {
+        let url = format!("{}{}", self.base_url, path);
+        self.check_outbound(&url)?;
+        let builder = self
+            .authorize(self.http.post(&url))
+            .await?
+            .headers(self.request_headers())
+            .form(form);
+        let response = self.send_rate_limited(builder, path).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = parse_error_message(&body);
+            self.diagnostics
+                .record_failure(path, &format!("HTTP {status}: {message}"));
+            return Err(Error::Api {
+                status: status.as_u16(),
+                message,
+            });
+        }
+        self.diagnostics.record_success();
+        self.note_deprecations(path, response.headers());
+        Ok(())
+    }
+
+    /// Detects the server version from the plain-text `/api/server/version`
+    /// endpoint, fetching it once per process and caching the result.
+    pub async fn server_version(&self) -> Result<ServerVersion> {
+        self.version
+            .get_or_try_init(|| async {
+                let (body, _) = self.get_bytes("/api/server/version", &[]).await?;
+                ServerVersion::parse(&String::from_utf8_lossy(&body))
+            })
+            .await
+            .copied()
+    }
+
+    /// Fails with a configuration error when the detected server is older
+    /// than the given version, naming the feature that needs it.
+    pub async fn require_version(&self, major: u32, minor: u32, what: &str) -> Result<()> {
+        let version = self.server_version().await?;
+        if version.at_least(major, minor) {
+            Ok(())
+        } else {
+            Err(Error::Config(format!(
+                "{what} requires SonarQube {major}.{minor} or later, but the server reports {version}"
+            )))
+        }
+    }
+
+    pub async fn search_issues(&self, request: &SonarQubeIssuesRequest) -> Result<IssuesResponse> {
+        let mut query: Vec<(&str, String)> =
+            vec![("componentKeys", request.project_key.clone())];
+        let mut mqr_severities = request.impact_severities.clone().unwrap_or_default();
+        if let Some(severities) = &request.severities {
+            // MQR severities go to a different parameter and only exist on
+            // 10.4+; classic severities keep working everywhere.
+            let (mqr, classic): (Vec<&Severity>, Vec<&Severity>) = severities
+                .iter()
+                .partition(|severity| severity.is_mqr_only());
+            mqr_severities.extend(mqr.iter().map(|severity| severity.as_str().to_string()));
+            if !classic.is_empty() {
+                let classic: Vec<_> = classic.iter().map(|severity| severity.as_str()).collect();
+                query.push(("severities", classic.join(",")));
+            }
+        }
+        if !mqr_severities.is_empty() {
+            self.require_version(
+                10,
+                4,
+                format!("impact severity filter {}", mqr_severities.join(",")).as_str(),
+            )
+            .await?;
+            query.push(("impactSeverities", mqr_severities.join(",")));
+        }
+        if let Some(categories) = &request.clean_code_attribute_categories {
+            self.require_version(10, 2, "Clean Code attribute category filters")
+                .await?;
+            query.push(("cleanCodeAttributeCategories", categories.join(",")));
+        }
+        if let Some(types) = &request.types {
+            let types: Vec<_> = types.iter().map(|t| t.as_str()).collect();
+            query.push(("types", types.join(",")));
+        }
+        if let Some(statuses) = &request.statuses {
+            let statuses: Vec<_> = statuses.iter().map(|s| s.as_str()).collect();
+            query.push(("statuses", statuses.join(",")));
+        }
+        if let Some(facets) = &request.facets {
+            query.push(("facets", facets.join(",")));
+        }
+        if let Some(page) = request.page {
+            query.push(("p", page.to_string()));
+        }
+        if let Some(page_size) = request.page_size {
+            query.push(("ps", page_size.to_string()));
+        }
+        if let Some(organization) = &self.organization {
+            query.push(("organization", organization.clone()));
+        }
+        self.get("/api/issues/search", &query).await
+    }
+
+    /// Fetches every page of an issue search and merges the results, up to
+    /// `cap` issues or the server's pagination window, whichever is hit
+    /// first. Pages after the first are fetched concurrently, bounded by
+    /// `--all-pages-concurrency`. The returned paging block describes the
+    /// merge: one page holding everything fetched, with the server's total.
+    pub async fn search_issues_all_pages(
+        &self,
+        request: &SonarQubeIssuesRequest,
+        cap: u32,
+    ) -> Result<IssuesResponse> {
+        let mut request = request.clone();
+        request.page = Some(1);
+        request.page_size = Some(ALL_PAGES_PAGE_SIZE.min(cap.max(1)));
+        let mut merged = self.search_issues(&request).await?;
+        let fetches = remaining_pages(&merged.paging, cap).map(|page| {
+            let mut request = request.clone();
+            async move {
+                let _permit = self.page_semaphore.acquire().await.expect("semaphore closed");
+                request.page = Some(page);
+                self.search_issues(&request).await
+            }
+        });
+        // try_join_all preserves page order, so the merge stays sorted the
+        // way the server sorted it.
+        for next in futures::future::try_join_all(fetches).await? {
+            merged.issues.extend(next.issues);
+        }
+        merged.issues.truncate(cap as usize);
+        merged.paging = Paging {
+            page_index: 1,
+            page_size: merged.issues.len() as u32,
+            total: merged.paging.total,
+            extra: Default::default(),
+        };
+        Ok(merged)
+    }
+
+    /// All-pages counterpart of [`Self::list_projects_filtered`]; see
+    /// [`Self::search_issues_all_pages`] for the merge and concurrency
+    /// semantics.
+    pub async fn list_projects_all_pages(
+        &self,
+        name_query: Option<&str>,
+        tags: Option<&str>,
+        use_cache: bool,
+        cap: u32,
+    ) -> Result<ProjectsResponse> {
+        let page_size = Some(ALL_PAGES_PAGE_SIZE.min(cap.max(1)));
+        let mut merged = self
+            .list_projects_filtered(name_query, tags, Some(1), page_size, use_cache)
+            .await?;
+        let fetches = remaining_pages(&merged.paging, cap).map(|page| async move {
+            let _permit = self.page_semaphore.acquire().await.expect("semaphore closed");
+            self.list_projects_filtered(name_query, tags, Some(page), page_size, use_cache)
+                .await
+        });
+        for next in futures::future::try_join_all(fetches).await? {
+            merged.components.extend(next.components);
+        }
+        merged.components.truncate(cap as usize);
+        merged.paging = Paging {
+            page_index: 1,
+            page_size: merged.components.len() as u32,
+            total: merged.paging.total,
+            extra: Default::default(),
+        };
+        Ok(merged)
+    }
+
+    pub async fn list_projects(
+        &self,
+        name_query: Option<&str>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+    ) -> Result<ProjectsResponse> {
+        self.list_projects_filtered(name_query, None, page, page_size, true)
+            .await
+    }
+
+    pub async fn list_projects_filtered(
+        &self,
+        name_query: Option<&str>,
+        tags: Option<&str>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        use_cache: bool,
+    ) -> Result<ProjectsResponse> {
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(name_query) = name_query {
+            query.push(("q", name_query.to_string()));
+        }
+        if let Some(tags) = tags {
+            query.push(("tags", tags.to_string()));
+        }
+        if let Some(page) = page {
+            query.push(("p", page.to_string()));
+        }
+        if let Some(page_size) = page_size {
+            query.push(("ps", page_size.to_string()));
+        }
+        if let Some(organization) = &self.organization {
+            query.push(("organization", organization.clone()));
+        }
+        self.get_with_cache("/api/projects/search", &query, use_cache)
+            .await
+    }
+
+    pub async fn get_measures(
+        &self,
+        project_key: &str,
+        metric_keys: &[String],
+    ) -> Result<MeasuresResponse> {
+        self.get_measures_with_cache(project_key, metric_keys, true)
+            .await
+    }
+
+    pub async fn get_measures_with_cache(
+        &self,
+        project_key: &str,
+        metric_keys: &[String],
+        use_cache: bool,
+    ) -> Result<MeasuresResponse> {
+        let query = vec![
+            ("component", project_key.to_string()),
+            ("metricKeys", metric_keys.join(",")),
+        ];
+        self.get_with_cache("/api/measures/component", &query, use_cache)
+            .await
+    }
+
+    pub async fn quality_gate_status(&self, project_key: &str) -> Result<QualityGateStatusResponse> {
+        let query = vec![("projectKey", project_key.to_string())];
+        self.get("/api/qualitygates/project_status", &query).await
+    }
+
+    pub async fn quality_gate_status_for_analysis(
+        &self,
+        analysis_id: &str,
+    ) -> Result<QualityGateStatusResponse> {
+        let query = vec![("analysisId", analysis_id.to_string())];
+        self.get("/api/qualitygates/project_status", &query).await
+    }
+
+    pub async fn ce_task(&self, task_id: &str) -> Result<CeTaskResponse> {
+        let query = vec![("id", task_id.to_string())];
+        self.get("/api/ce/task", &query).await
+    }
+
+    pub async fn ce_component(&self, project_key: &str) -> Result<CeComponentResponse> {
+        let query = vec![("component", project_key.to_string())];
+        self.get("/api/ce/component", &query).await
+    }
+
+    pub async fn list_branches(&self, project_key: &str) -> Result<BranchesResponse> {
+        let query = vec![("project", project_key.to_string())];
+        self.get("/api/project_branches/list", &query).await
+    }
+
+    pub async fn search_rules(&self, page: u32, page_size: u32) -> Result<RulesResponse> {
+        let query = vec![
+            ("p", page.to_string()),
+            ("ps", page_size.to_string()),
+            ("f", "name,lang,langName,severity".to_string()),
+        ];
+        self.get("/api/rules/search", &query).await
+    }
+
+    pub async fn show_rule(&self, rule_key: &str) -> Result<RuleShowResponse> {
+        let query = vec![("key", rule_key.to_string())];
+        self.get("/api/rules/show", &query).await
+    }
+}
+
+/// Parses "Name: value" pairs from configuration into a header map,
+/// skipping entries that are not valid HTTP headers.
+fn parse_extra_headers(entries: &[String]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for entry in entries {
+        let Some((name, value)) = entry.split_once(':') else {
+            tracing::warn!("ignoring malformed extra header (expected \"Name: value\"): {entry}");
+            continue;
+        };
+        match (
+            HeaderName::try_from(name.trim()),
+            HeaderValue::try_from(value.trim()),
+        ) {
+            (Ok(name), Ok(value)) => {
+                headers.insert(name, value);
+            }
+            _ => tracing::warn!("ignoring invalid extra header: {entry}"),
+        }
+    }
+    headers
+}
+
+/// Case-insensitive host match against the allowlist.
+fn host_allowed(url: &reqwest::Url, allowed_hosts: &[String]) -> bool {
+    url.host_str()
+        .map(|host| {
+            let host = host.to_ascii_lowercase();
+            allowed_hosts.contains(&host)
+        })
+        .unwrap_or(false)
+}
+
+/// Extracts human-readable messages from a SonarQube error body, which looks
+/// like `{"errors":[{"msg":"..."}]}`. Falls back to the raw body.
+fn parse_error_message(body: &str) -> String {
+    #[derive(serde::Deserialize)]
+    struct ErrorBody {
+        errors: Vec<ErrorItem>,
+    }
+    #[derive(serde::Deserialize)]
+    struct ErrorItem {
+        msg: String,
+    }
+    if let Ok(parsed) = serde_json::from_str::<ErrorBody>(body) {
+        let messages: Vec<String> = parsed.errors.into_iter().map(|e| e.msg).collect();
+        if !messages.is_empty() {
+            return messages.join("; ");
+        }
+    }
+    let trimmed = body.trim();
+    if trimmed.is_empty() {
+        "no error details provided".to_string()
+    } else {
+        crate::text::truncate(trimmed, 300)
+    }
+}
+
+/// Builds a targeted error when a successful response is not JSON — the
+/// classic symptom of a corporate SSO proxy answering with an HTML login
+/// page in place of the SonarQube API.
+fn non_json_response_message(status: u16, content_type: &str) -> Option<String> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if essence == "application/json" || essence.ends_with("+json") {
+        return None;
+    }
+    let hint = if essence == "text/html" {
+        " — received HTML; are you behind an SSO proxy or captive login page?"
+    } else {
+        ""
+    };
+    Some(format!(
+        "expected JSON but got content type {} (HTTP {status}){hint}",
+        if essence.is_empty() { "<none>" } else { &essence }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_client(extra_args: &[&str]) -> SonarQubeClient {
+        let mut args = vec![
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "https://sonar.example.com",
+        ];
+        args.extend_from_slice(extra_args);
+        let config = <Config as clap::Parser>::parse_from(args);
+        SonarQubeClient::new(
+            &config,
+            Box::new(crate::auth::StaticTokenProvider {
+                token: "token".to_string(),
+            }),
+            Arc::new(Diagnostics::default()),
+            Arc::new(ResponseCache::from_config(&config)),
+            Arc::new(RequestCoalescer::default()),
+            Arc::new(crate::mcp::notifier::Notifier::default()),
+        )
+        .expect("client")
+    }
+
+    #[test]
+    fn construction_surfaces_config_errors_instead_of_panicking() {
+        let config = <Config as clap::Parser>::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "https://sonar.example.com",
+            "--proxy-url",
+            "::not a proxy::",
+        ]);
+        let result = SonarQubeClient::new(
+            &config,
+            Box::new(crate::auth::StaticTokenProvider {
+                token: "token".to_string(),
+            }),
+            Arc::new(Diagnostics::default()),
+            Arc::new(ResponseCache::from_config(&config)),
+            Arc::new(RequestCoalescer::default()),
+            Arc::new(crate::mcp::notifier::Notifier::default()),
+        );
+        let err = match result {
+            Err(err) => err,
+            Ok(_) => panic!("a bad proxy URL must not build a client"),
+        };
+        assert!(err.to_string().contains("invalid proxy URL"));
+    }
+
+    #[test]
+    fn allowlist_matches_hosts_case_insensitively() {
+        let allowed = vec!["sonar.example.com".to_string()];
+        let ok = reqwest::Url::parse("https://SONAR.example.com/api/issues/search").unwrap();
+        let blocked = reqwest::Url::parse("https://attacker.example.net/exfil").unwrap();
+        assert!(host_allowed(&ok, &allowed));
+        assert!(!host_allowed(&blocked, &allowed));
+    }
+
+    #[test]
+    fn client_refuses_off_allowlist_urls() {
+        let client = test_client(&["--outbound-allowlist", "mirror.example.com"]);
+        assert!(client.check_outbound("https://sonar.example.com/api/x").is_ok());
+        assert!(client.check_outbound("https://mirror.example.com/api/x").is_ok());
+        assert!(matches!(
+            client.check_outbound("https://other.example.com/api/x"),
+            Err(Error::OutboundBlocked(_))
+        ));
+    }
+
+    #[test]
+    fn parses_extra_headers_and_skips_malformed() {
+        let headers = parse_extra_headers(&[
+            "X-Gateway-Route: sonar".to_string(),
+            "not-a-header".to_string(),
+        ]);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers.get("x-gateway-route").unwrap(), "sonar");
+    }
+
+    #[test]
+    fn impersonation_header_follows_session_user() {
+        let client = test_client(&["--impersonation-header", "X-Forwarded-User"]);
+        assert!(client.request_headers().get("x-forwarded-user").is_none());
+        client.set_impersonated_user(Some("alice".to_string()));
+        assert_eq!(
+            client.request_headers().get("x-forwarded-user").unwrap(),
+            "alice"
+        );
+    }
+
+    #[test]
+    fn user_agent_names_the_crate_and_session_client() {
+        let client = test_client(&["--request-tag", "team-a"]);
+        let headers = client.request_headers();
+        let user_agent = headers.get("user-agent").unwrap().to_str().unwrap();
+        assert!(user_agent.starts_with("sonarqube-mcp-server/"));
+        assert_eq!(headers.get(TAG_HEADER).unwrap(), "team-a");
+
+        client.set_impersonated_user(Some("vscode".to_string()));
+        let headers = client.request_headers();
+        let user_agent = headers.get("user-agent").unwrap().to_str().unwrap();
+        assert!(user_agent.ends_with("(vscode)"));
+
+        let overridden = test_client(&["--user-agent", "custom-agent/1.0"]);
+        let headers = overridden.request_headers();
+        assert_eq!(headers.get("user-agent").unwrap(), "custom-agent/1.0");
+    }
+
+    #[test]
+    fn parses_structured_error_body() {
+        let body = r#"{"errors":[{"msg":"Insufficient privileges"},{"msg":"Try again"}]}"#;
+        assert_eq!(
+            parse_error_message(body),
+            "Insufficient privileges; Try again"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_raw_body() {
+        assert_eq!(parse_error_message("Bad Gateway"), "Bad Gateway");
+        assert_eq!(parse_error_message("  "), "no error details provided");
+    }
+
+    #[test]
+    fn all_pages_stops_at_the_total_the_cap_and_the_window() {
+        let first = |page_size, total| Paging {
+            page_index: 1,
+            page_size,
+            total,
+            extra: Default::default(),
+        };
+        // 1 200 results in pages of 500: pages 2 and 3 remain.
+        assert_eq!(remaining_pages(&first(500, 1_200), 10_000), 2..=3);
+        // Everything fit on the first page.
+        assert!(remaining_pages(&first(500, 300), 10_000).is_empty());
+        // The cap trims what the server would offer.
+        assert_eq!(remaining_pages(&first(500, 5_000), 1_000), 2..=2);
+        // The 10k window bounds the fetch even for huge totals.
+        assert_eq!(remaining_pages(&first(500, 50_000), 50_000), 2..=20);
+    }
+
+    #[test]
+    fn circuit_opens_after_consecutive_failures_and_recovers() {
+        let circuit = CircuitBreaker::default();
+        for _ in 0..CIRCUIT_FAILURE_THRESHOLD - 1 {
+            assert!(!circuit.on_failure(100));
+        }
+        assert_eq!(circuit.open_for(100), None);
+        assert!(circuit.on_failure(100));
+        assert_eq!(circuit.open_for(100), Some(CIRCUIT_COOLDOWN.as_secs()));
+
+        // After the cooldown the circuit half-opens: one more failure
+        // re-opens it immediately, a success resets everything.
+        let later = 100 + CIRCUIT_COOLDOWN.as_secs();
+        assert_eq!(circuit.open_for(later), None);
+        assert!(circuit.on_failure(later));
+        circuit.on_success();
+        assert_eq!(circuit.open_for(later), None);
+        assert!(!circuit.on_failure(later));
+    }
+
+    #[test]
+    fn rate_limiter_delays_only_when_the_quota_is_exhausted() {
+        let limiter = RateLimiter::default();
+        assert_eq!(limiter.delay(100), None);
+
+        limiter.observe(Some(5), Some(130));
+        assert_eq!(limiter.delay(100), None);
+
+        limiter.observe(Some(0), Some(130));
+        assert_eq!(limiter.delay(100), Some(std::time::Duration::from_secs(30)));
+        // A reset in the past means the quota has been replenished.
+        assert_eq!(limiter.delay(200), None);
+
+        // Waits are capped so a bogus far-future reset cannot stall tools.
+        limiter.observe(Some(0), Some(100_000));
+        assert_eq!(limiter.delay(100), Some(MAX_RATE_LIMIT_WAIT));
+    }
+
+    #[test]
+    fn parses_rate_limit_headers_leniently() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static(" 12 "));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("oops"));
+        assert_eq!(header_u64(&headers, "x-ratelimit-remaining"), Some(12));
+        assert_eq!(header_u64(&headers, "x-ratelimit-reset"), None);
+        assert_eq!(header_u64(&headers, "retry-after"), None);
+    }
+
+    #[test]
+    fn flags_non_json_responses_with_a_proxy_hint() {
+        assert!(non_json_response_message(200, "application/json").is_none());
+        assert!(non_json_response_message(200, "application/json; charset=utf-8").is_none());
+        assert!(non_json_response_message(200, "application/hal+json").is_none());
+        let html = non_json_response_message(200, "text/html; charset=utf-8").unwrap();
+        assert!(html.contains("text/html"));
+        assert!(html.contains("SSO proxy"));
+        let plain = non_json_response_message(502, "text/plain").unwrap();
+        assert!(plain.contains("HTTP 502"));
+        assert!(!plain.contains("SSO proxy"));
+    }
+}
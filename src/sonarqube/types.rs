@@ -0,0 +1,609 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Paging {
+    pub page_index: u32,
+    pub page_size: u32,
+    pub total: u32,
+    /// Fields this build does not model, passed through to the client
+    /// untouched instead of being stripped. Every response struct in this
+    /// module carries the same passthrough, so newer SonarQube versions can
+    /// add fields without losing data behind this server.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Issue severity, covering both the classic scale (INFO through BLOCKER)
+/// and the MQR impact scale (LOW/MEDIUM/HIGH). Values this build does not
+/// know about deserialize as `Unknown` instead of failing the whole
+/// response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Severity {
+    Info,
+    Minor,
+    Major,
+    Critical,
+    Blocker,
+    Low,
+    Medium,
+    High,
+    #[serde(other)]
+    Unknown,
+}
+
+impl Severity {
+    /// Values advertised in tool input schemas; `Unknown` is deliberately
+    /// absent because it is a parsing fallback, not a filter.
+    pub const VALUES: &'static [&'static str] = &[
+        "INFO", "MINOR", "MAJOR", "CRITICAL", "BLOCKER", "LOW", "MEDIUM", "HIGH",
+    ];
+
+    /// The classic scale only, for call sites that pass severities straight
+    /// to the `severities` query parameter without MQR translation.
+    pub const CLASSIC_VALUES: &'static [&'static str] =
+        &["INFO", "MINOR", "MAJOR", "CRITICAL", "BLOCKER"];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Info => "INFO",
+            Self::Minor => "MINOR",
+            Self::Major => "MAJOR",
+            Self::Critical => "CRITICAL",
+            Self::Blocker => "BLOCKER",
+            Self::Low => "LOW",
+            Self::Medium => "MEDIUM",
+            Self::High => "HIGH",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// True for severities that only exist on the MQR impact scale and must
+    /// be sent as `impactSeverities` (SonarQube 10.4+).
+    pub fn is_mqr_only(self) -> bool {
+        matches!(self, Self::Low | Self::Medium | Self::High)
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IssueType {
+    CodeSmell,
+    Bug,
+    Vulnerability,
+    SecurityHotspot,
+    #[serde(other)]
+    Unknown,
+}
+
+impl IssueType {
+    pub const VALUES: &'static [&'static str] =
+        &["CODE_SMELL", "BUG", "VULNERABILITY", "SECURITY_HOTSPOT"];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CodeSmell => "CODE_SMELL",
+            Self::Bug => "BUG",
+            Self::Vulnerability => "VULNERABILITY",
+            Self::SecurityHotspot => "SECURITY_HOTSPOT",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl std::fmt::Display for IssueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IssueStatus {
+    Open,
+    Confirmed,
+    Reopened,
+    Resolved,
+    Closed,
+    #[serde(other)]
+    Unknown,
+}
+
+impl IssueStatus {
+    pub const VALUES: &'static [&'static str] =
+        &["OPEN", "CONFIRMED", "REOPENED", "RESOLVED", "CLOSED"];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "OPEN",
+            Self::Confirmed => "CONFIRMED",
+            Self::Reopened => "REOPENED",
+            Self::Resolved => "RESOLVED",
+            Self::Closed => "CLOSED",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl std::fmt::Display for IssueStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issue {
+    pub key: String,
+    pub rule: String,
+    pub severity: Severity,
+    pub component: String,
+    pub project: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    pub message: String,
+    #[serde(rename = "type")]
+    pub issue_type: IssueType,
+    pub status: IssueStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// SCM author of the offending line, when available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creation_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_date: Option<String>,
+    /// Estimated remediation effort, e.g. "5min".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<String>,
+    /// Legacy alias for `effort` kept by older servers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debt: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Clean Code attribute (10.x), e.g. CONVENTIONAL or TRUSTWORTHY.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clean_code_attribute: Option<String>,
+    /// Clean Code attribute category: ADAPTABLE, CONSISTENT, INTENTIONAL
+    /// or RESPONSIBLE.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clean_code_attribute_category: Option<String>,
+    /// Software-quality impacts (10.x MQR mode). Empty on older servers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub impacts: Vec<Impact>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// One software-quality impact of an issue, e.g. MAINTAINABILITY/HIGH.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Impact {
+    pub software_quality: String,
+    pub severity: Severity,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuesResponse {
+    pub paging: Paging,
+    pub issues: Vec<Issue>,
+    /// Facet buckets, present when the request asked for facets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facets: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Query parameters for `/api/issues/search`.
+#[derive(Debug, Clone, Default)]
+pub struct SonarQubeIssuesRequest {
+    pub project_key: String,
+    pub severities: Option<Vec<Severity>>,
+    pub types: Option<Vec<IssueType>>,
+    pub statuses: Option<Vec<IssueStatus>>,
+    /// Clean Code attribute categories (10.x), e.g. INTENTIONAL.
+    pub clean_code_attribute_categories: Option<Vec<String>>,
+    /// MQR impact severities (10.4+), e.g. HIGH. Classic severities belong
+    /// in `severities`.
+    pub impact_severities: Option<Vec<String>>,
+    /// Facets to aggregate counts by, e.g. severities.
+    pub facets: Option<Vec<String>>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl SonarQubeIssuesRequest {
+    /// Starts a fluent builder, so call sites set only the filters they
+    /// care about instead of spelling out every `None`.
+    pub fn builder(project_key: impl Into<String>) -> SonarQubeIssuesRequestBuilder {
+        SonarQubeIssuesRequestBuilder {
+            request: Self {
+                project_key: project_key.into(),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+/// Fluent builder for [`SonarQubeIssuesRequest`].
+#[derive(Debug, Clone)]
+pub struct SonarQubeIssuesRequestBuilder {
+    request: SonarQubeIssuesRequest,
+}
+
+impl SonarQubeIssuesRequestBuilder {
+    pub fn severities(mut self, severities: impl Into<Option<Vec<Severity>>>) -> Self {
+        self.request.severities = severities.into();
+        self
+    }
+
+    pub fn types(mut self, types: impl Into<Option<Vec<IssueType>>>) -> Self {
+        self.request.types = types.into();
+        self
+    }
+
+    pub fn statuses(mut self, statuses: impl Into<Option<Vec<IssueStatus>>>) -> Self {
+        self.request.statuses = statuses.into();
+        self
+    }
+
+    pub fn clean_code_attribute_categories(
+        mut self,
+        categories: impl Into<Option<Vec<String>>>,
+    ) -> Self {
+        self.request.clean_code_attribute_categories = categories.into();
+        self
+    }
+
+    pub fn impact_severities(mut self, severities: impl Into<Option<Vec<String>>>) -> Self {
+        self.request.impact_severities = severities.into();
+        self
+    }
+
+    pub fn facets(mut self, facets: impl Into<Option<Vec<String>>>) -> Self {
+        self.request.facets = facets.into();
+        self
+    }
+
+    pub fn page(mut self, page: impl Into<Option<u32>>) -> Self {
+        self.request.page = page.into();
+        self
+    }
+
+    pub fn page_size(mut self, page_size: impl Into<Option<u32>>) -> Self {
+        self.request.page_size = page_size.into();
+        self
+    }
+
+    pub fn build(self) -> SonarQubeIssuesRequest {
+        self.request
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Component {
+    pub key: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_analysis_date: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectsResponse {
+    pub paging: Paging,
+    pub components: Vec<Component>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Measure {
+    pub metric: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(rename = "bestValue", skip_serializing_if = "Option::is_none")]
+    pub best_value: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentMeasures {
+    pub key: String,
+    pub name: String,
+    pub measures: Vec<Measure>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeasuresResponse {
+    pub component: ComponentMeasures,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityGateCondition {
+    pub status: String,
+    pub metric_key: String,
+    pub comparator: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_threshold: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_value: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatus {
+    pub status: String,
+    #[serde(default)]
+    pub conditions: Vec<QualityGateCondition>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityGateStatusResponse {
+    pub project_status: ProjectStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CeTask {
+    pub id: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analysis_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submitted_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executed_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CeTaskResponse {
+    pub task: CeTask,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response of `/api/ce/component`: queued tasks plus the most recent one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CeComponentResponse {
+    #[serde(default)]
+    pub queue: Vec<CeTask>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<CeTask>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_gate_status: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Branch {
+    pub name: String,
+    #[serde(rename = "isMain")]
+    pub is_main: bool,
+    #[serde(rename = "type")]
+    pub branch_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<BranchStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analysis_date: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchesResponse {
+    pub branches: Vec<Branch>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub key: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang_name: Option<String>,
+    pub severity: Severity,
+    #[serde(rename = "type")]
+    pub rule_type: IssueType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md_desc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_desc: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response of `/api/rules/search`, which uses flat `total`/`p`/`ps` fields
+/// instead of the usual `paging` object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulesResponse {
+    pub total: u32,
+    pub p: u32,
+    pub ps: u32,
+    pub rules: Vec<Rule>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleShowResponse {
+    pub rule: Rule,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_issue_from_api_payload() {
+        let raw = serde_json::json!({
+            "key": "AYx1",
+            "rule": "rust:S1135",
+            "severity": "INFO",
+            "component": "demo:src/main.rs",
+            "project": "demo",
+            "line": 4,
+            "message": "Complete the task associated to this TODO comment.",
+            "type": "CODE_SMELL",
+            "status": "OPEN",
+            "flows": [],
+            "textRange": {"startLine": 4, "endLine": 4},
+            "cleanCodeAttribute": "COMPLETE",
+            "cleanCodeAttributeCategory": "INTENTIONAL",
+            "impacts": [{"softwareQuality": "MAINTAINABILITY", "severity": "INFO"}]
+        });
+        let issue: Issue = serde_json::from_value(raw).expect("issue should deserialize");
+        assert_eq!(issue.key, "AYx1");
+        assert_eq!(issue.issue_type, IssueType::CodeSmell);
+        assert_eq!(issue.severity, Severity::Info);
+        assert_eq!(issue.line, Some(4));
+        assert!(issue.assignee.is_none());
+        assert_eq!(issue.clean_code_attribute_category.as_deref(), Some("INTENTIONAL"));
+        assert_eq!(issue.impacts[0].software_quality, "MAINTAINABILITY");
+        assert_eq!(issue.impacts[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn issue_tolerates_pre_10x_payloads_without_clean_code_fields() {
+        let raw = serde_json::json!({
+            "key": "AYx2",
+            "rule": "java:S100",
+            "severity": "MAJOR",
+            "component": "demo:src/A.java",
+            "project": "demo",
+            "message": "Rename this method.",
+            "type": "CODE_SMELL",
+            "status": "OPEN"
+        });
+        let issue: Issue = serde_json::from_value(raw).unwrap();
+        assert!(issue.clean_code_attribute.is_none());
+        assert!(issue.impacts.is_empty());
+    }
+
+    #[test]
+    fn unrecognized_enum_values_fall_back_to_unknown() {
+        let raw = serde_json::json!({
+            "key": "AYx3",
+            "rule": "java:S100",
+            "severity": "SOMETHING_NEW",
+            "component": "demo:src/A.java",
+            "project": "demo",
+            "message": "Future server speaking a newer dialect.",
+            "type": "QUANTUM_SMELL",
+            "status": "ACCEPTED"
+        });
+        let issue: Issue = serde_json::from_value(raw).unwrap();
+        assert_eq!(issue.severity, Severity::Unknown);
+        assert_eq!(issue.issue_type, IssueType::Unknown);
+        assert_eq!(issue.status, IssueStatus::Unknown);
+    }
+
+    #[test]
+    fn schema_values_round_trip_through_the_enums() {
+        for value in Severity::VALUES {
+            let parsed: Severity = serde_json::from_value(serde_json::json!(value)).unwrap();
+            assert_eq!(parsed.as_str(), *value);
+        }
+        for value in IssueType::VALUES {
+            let parsed: IssueType = serde_json::from_value(serde_json::json!(value)).unwrap();
+            assert_eq!(parsed.as_str(), *value);
+        }
+        for value in IssueStatus::VALUES {
+            let parsed: IssueStatus = serde_json::from_value(serde_json::json!(value)).unwrap();
+            assert_eq!(parsed.as_str(), *value);
+        }
+    }
+
+    #[test]
+    fn unknown_response_fields_survive_a_round_trip() {
+        let raw = serde_json::json!({
+            "key": "AYx4",
+            "rule": "java:S100",
+            "severity": "MAJOR",
+            "component": "demo:src/A.java",
+            "project": "demo",
+            "message": "Rename this method.",
+            "type": "CODE_SMELL",
+            "status": "OPEN",
+            "quickFixAvailable": true,
+            "scope": "MAIN"
+        });
+        let issue: Issue = serde_json::from_value(raw).unwrap();
+        assert_eq!(issue.extra["quickFixAvailable"], serde_json::json!(true));
+        let reserialized = serde_json::to_value(&issue).unwrap();
+        assert_eq!(reserialized["quickFixAvailable"], serde_json::json!(true));
+        assert_eq!(reserialized["scope"], "MAIN");
+    }
+
+    #[test]
+    fn issues_request_builder_defaults_unset_filters_to_none() {
+        let request = SonarQubeIssuesRequest::builder("demo")
+            .severities(vec![Severity::Blocker])
+            .page_size(50)
+            .build();
+        assert_eq!(request.project_key, "demo");
+        assert_eq!(request.severities, Some(vec![Severity::Blocker]));
+        assert_eq!(request.page_size, Some(50));
+        assert!(request.types.is_none());
+        assert!(request.statuses.is_none());
+        assert!(request.page.is_none());
+    }
+
+    #[test]
+    fn ce_component_response_tolerates_empty_queue() {
+        let raw = serde_json::json!({"queue": [], "current": {"id": "t1", "status": "SUCCESS"}});
+        let response: CeComponentResponse = serde_json::from_value(raw).unwrap();
+        assert!(response.queue.is_empty());
+        assert_eq!(response.current.unwrap().status, "SUCCESS");
+    }
+}
@@ -0,0 +1,64 @@
+use crate::error::{Error, Result};
+
+/// Parsed SonarQube server version, from the plain-text
+/// `/api/server/version` endpoint (e.g. `10.4.1.88267`). Only major and
+/// minor matter for compatibility decisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ServerVersion {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parts = text.trim().split('.');
+        let major = parts
+            .next()
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(|| Error::Config(format!("unparseable server version: {text:?}")))?;
+        let minor = parts.next().and_then(|part| part.parse().ok()).unwrap_or(0);
+        Ok(Self { major, minor })
+    }
+
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+
+    /// MQR-mode impact severities (HIGH/MEDIUM/LOW and friends) exist since
+    /// SonarQube 10.4; older servers only understand the classic scale.
+    pub fn supports_mqr_severities(&self) -> bool {
+        self.at_least(10, 4)
+    }
+}
+
+impl std::fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_full_and_short_versions() {
+        let version = ServerVersion::parse("10.4.1.88267\n").unwrap();
+        assert_eq!(version, ServerVersion { major: 10, minor: 4 });
+        assert_eq!(
+            ServerVersion::parse("9.9").unwrap(),
+            ServerVersion { major: 9, minor: 9 }
+        );
+        assert!(ServerVersion::parse("next").is_err());
+    }
+
+    #[test]
+    fn compares_versions() {
+        let version = ServerVersion::parse("10.4").unwrap();
+        assert!(version.at_least(9, 9));
+        assert!(version.at_least(10, 4));
+        assert!(!version.at_least(10, 5));
+        assert!(version.supports_mqr_severities());
+        assert!(!ServerVersion::parse("10.3").unwrap().supports_mqr_severities());
+    }
+}
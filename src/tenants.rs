@@ -0,0 +1,300 @@
+//! Tenant definitions for centrally hosted deployments. A TOML file
+//! (`--tenants-config`) declares one `[[tenant]]` per team with its own
+//! credential, project allowlist, and tool policy. Network transports
+//! select the tenant from the client's auth identity (API key); the stdio
+//! transport stays single-tenant. Per-tenant state (caches, local stores)
+//! must be keyed by tenant name so teams cannot observe each other's data.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::server_context::ServerContext;
+
+/// One team's slice of the deployment.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Tenant {
+    /// Stable identifier, also the isolation key for caches and stores.
+    pub name: String,
+    /// API key presented by the tenant's clients on network transports.
+    pub api_key: String,
+    /// SonarQube token used for this tenant's upstream requests.
+    pub sonarqube_token: String,
+    /// Project keys this tenant may query; empty means all visible
+    /// projects.
+    #[serde(default)]
+    pub allowed_projects: Vec<String>,
+    /// Whether this tenant may use administrative write tools.
+    #[serde(default)]
+    pub allow_admin_operations: bool,
+}
+
+impl Tenant {
+    /// True when the tenant may query the given project.
+    pub fn is_project_allowed(&self, project_key: &str) -> bool {
+        self.allowed_projects.is_empty()
+            || self.allowed_projects.iter().any(|p| p == project_key)
+    }
+
+    /// Refuses tool arguments naming a project outside the tenant's
+    /// allowlist. Arguments are inspected by key: anything that names a
+    /// project or component is checked, including arrays, comma-separated
+    /// lists, and `project:path` component keys.
+    pub fn check_project_args(&self, args: &Value) -> Result<()> {
+        if self.allowed_projects.is_empty() {
+            return Ok(());
+        }
+        let Some(map) = args.as_object() else {
+            return Ok(());
+        };
+        for (key, value) in map {
+            let lowered = key.to_ascii_lowercase();
+            if !(lowered.contains("project") || lowered.starts_with("component")) {
+                continue;
+            }
+            let mut names: Vec<&str> = Vec::new();
+            match value {
+                Value::String(list) => names.extend(list.split(',')),
+                Value::Array(items) => names.extend(items.iter().filter_map(Value::as_str)),
+                _ => {}
+            }
+            for name in names {
+                let project = name.trim().split(':').next().unwrap_or("");
+                if !project.is_empty() && !self.is_project_allowed(project) {
+                    return Err(Error::InvalidArguments(format!(
+                        "project {project:?} is not available to tenant {:?}",
+                        self.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TenantsFile {
+    #[serde(default, rename = "tenant")]
+    tenants: Vec<Tenant>,
+}
+
+/// All configured tenants, indexed by API key for selection at the
+/// transport boundary. Each tenant's context is derived lazily and cached
+/// by tenant name, so teams share neither credentials nor caches.
+pub struct TenantRegistry {
+    by_api_key: BTreeMap<String, Tenant>,
+    contexts: Mutex<BTreeMap<String, Arc<ServerContext>>>,
+}
+
+/// Hand-written so a debug dump shows tenant names but never the keyed
+/// map of API keys and tokens.
+impl std::fmt::Debug for TenantRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TenantRegistry")
+            .field("tenants", &self.names())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TenantRegistry {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path).map_err(|err| {
+            Error::Config(format!("cannot read tenants config {}: {err}", path.display()))
+        })?;
+        Self::from_toml_str(&raw)
+            .map_err(|err| Error::Config(format!("in {}: {err}", path.display())))
+    }
+
+    fn from_toml_str(raw: &str) -> std::result::Result<Self, String> {
+        let file: TenantsFile = toml::from_str(raw).map_err(|err| err.to_string())?;
+        if file.tenants.is_empty() {
+            return Err("tenants config declares no [[tenant]] entries".to_string());
+        }
+        let mut by_api_key = BTreeMap::new();
+        let mut names = std::collections::BTreeSet::new();
+        for tenant in file.tenants {
+            if tenant.name.trim().is_empty() {
+                return Err("tenant name must not be empty".to_string());
+            }
+            if tenant.api_key.trim().is_empty() || tenant.sonarqube_token.trim().is_empty() {
+                return Err(format!(
+                    "tenant {:?} must set both api_key and sonarqube_token",
+                    tenant.name
+                ));
+            }
+            if !names.insert(tenant.name.clone()) {
+                return Err(format!("duplicate tenant name {:?}", tenant.name));
+            }
+            if by_api_key.insert(tenant.api_key.clone(), tenant).is_some() {
+                return Err("two tenants share the same api_key".to_string());
+            }
+        }
+        Ok(Self {
+            by_api_key,
+            contexts: Mutex::new(BTreeMap::new()),
+        })
+    }
+
+    /// Resolves the tenant presenting the given API key.
+    pub fn by_api_key(&self, api_key: &str) -> Option<&Tenant> {
+        self.by_api_key.get(api_key)
+    }
+
+    /// All configured tenants. Callers comparing presented keys should use
+    /// a constant-time comparison rather than `by_api_key`.
+    pub fn tenants(&self) -> impl Iterator<Item = &Tenant> {
+        self.by_api_key.values()
+    }
+
+    /// The tenant's isolated context, derived from the host context on
+    /// first use and cached by tenant name. Mirrors
+    /// `InstanceRegistry::resolve`: every tenant gets its own client,
+    /// cache, and coalescer, with the server's admin flag narrowed by the
+    /// tenant's own policy.
+    pub fn context_for(&self, host: &ServerContext, tenant: &Tenant) -> Result<Arc<ServerContext>> {
+        let mut contexts = self.contexts.lock().expect("tenant context lock poisoned");
+        if let Some(ctx) = contexts.get(&tenant.name) {
+            return Ok(Arc::clone(ctx));
+        }
+        let ctx = Arc::new(host.for_tenant(tenant)?);
+        contexts.insert(tenant.name.clone(), Arc::clone(&ctx));
+        Ok(ctx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_api_key.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_api_key.is_empty()
+    }
+
+    /// Tenant names, for diagnostics. API keys and tokens stay private.
+    pub fn names(&self) -> Vec<&str> {
+        self.by_api_key.values().map(|t| t.name.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    const SAMPLE: &str = r#"
+[[tenant]]
+name = "payments"
+api_key = "key-payments"
+sonarqube_token = "squ_aaa"
+allowed_projects = ["payments-api", "payments-web"]
+
+[[tenant]]
+name = "platform"
+api_key = "key-platform"
+sonarqube_token = "squ_bbb"
+allow_admin_operations = true
+"#;
+
+    #[test]
+    fn resolves_tenants_by_api_key_with_project_isolation() {
+        let registry = TenantRegistry::from_toml_str(SAMPLE).unwrap();
+        assert_eq!(registry.len(), 2);
+
+        let payments = registry.by_api_key("key-payments").unwrap();
+        assert!(payments.is_project_allowed("payments-api"));
+        assert!(!payments.is_project_allowed("platform-core"));
+        assert!(!payments.allow_admin_operations);
+
+        let platform = registry.by_api_key("key-platform").unwrap();
+        assert!(platform.is_project_allowed("anything"));
+        assert!(platform.allow_admin_operations);
+
+        assert!(registry.by_api_key("key-unknown").is_none());
+    }
+
+    #[test]
+    fn tenant_contexts_are_cached_isolated_and_policy_narrowed() {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+            "--sonarqube-token",
+            "squ_shared",
+            "--allow-admin-operations",
+        ]);
+        let host = ServerContext::new(config).expect("context");
+        let registry = TenantRegistry::from_toml_str(SAMPLE).unwrap();
+
+        let payments = registry.by_api_key("key-payments").unwrap();
+        let ctx = registry.context_for(&host, payments).unwrap();
+        assert_eq!(ctx.config.sonarqube_token, "squ_aaa");
+        assert_eq!(ctx.tenant.as_ref().unwrap().name, "payments");
+        // The server allows admin writes, but this tenant does not.
+        assert!(!ctx.config.allow_admin_operations);
+        let platform = registry.by_api_key("key-platform").unwrap();
+        assert!(registry
+            .context_for(&host, platform)
+            .unwrap()
+            .config
+            .allow_admin_operations);
+
+        // The second resolution reuses the cached context.
+        let again = registry.context_for(&host, payments).unwrap();
+        assert!(Arc::ptr_eq(&ctx, &again));
+    }
+
+    #[test]
+    fn project_arguments_outside_the_allowlist_are_refused() {
+        use serde_json::json;
+
+        let registry = TenantRegistry::from_toml_str(SAMPLE).unwrap();
+        let payments = registry.by_api_key("key-payments").unwrap();
+        assert!(payments
+            .check_project_args(&json!({"project": "payments-api"}))
+            .is_ok());
+        assert!(payments
+            .check_project_args(&json!({"component": "payments-web:src/cart.ts"}))
+            .is_ok());
+        assert!(payments
+            .check_project_args(&json!({"project": "platform-core"}))
+            .is_err());
+        assert!(payments
+            .check_project_args(&json!({"projectKeys": "payments-api,platform-core"}))
+            .is_err());
+        assert!(payments
+            .check_project_args(&json!({"projects": ["payments-api", "platform-core"]}))
+            .is_err());
+
+        // An empty allowlist means every project.
+        let platform = registry.by_api_key("key-platform").unwrap();
+        assert!(platform
+            .check_project_args(&json!({"project": "anything"}))
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_duplicate_names_shared_keys_and_missing_credentials() {
+        let duplicate = format!("{SAMPLE}\n[[tenant]]\nname = \"payments\"\napi_key = \"k\"\nsonarqube_token = \"t\"\n");
+        assert!(TenantRegistry::from_toml_str(&duplicate)
+            .unwrap_err()
+            .contains("duplicate tenant name"));
+
+        let shared_key = format!("{SAMPLE}\n[[tenant]]\nname = \"other\"\napi_key = \"key-payments\"\nsonarqube_token = \"t\"\n");
+        assert!(TenantRegistry::from_toml_str(&shared_key)
+            .unwrap_err()
+            .contains("same api_key"));
+
+        let missing = "[[tenant]]\nname = \"x\"\napi_key = \"\"\nsonarqube_token = \"t\"\n";
+        assert!(TenantRegistry::from_toml_str(missing)
+            .unwrap_err()
+            .contains("must set both"));
+
+        assert!(TenantRegistry::from_toml_str("").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+//! Text truncation helpers.
+//!
+//! Byte-indexed slicing (`&body[..200]`) panics when the cut lands inside a
+//! multi-byte UTF-8 sequence, and even `char`-counted truncation can split a
+//! user-perceived character — an emoji with a skin-tone modifier, a flag, a
+//! combining accent. Everything that shortens text for previews, errors or
+//! logs goes through here instead, cutting on grapheme cluster boundaries.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Appended when text was cut short, so readers know a preview is partial.
+const ELLIPSIS: char = '…';
+
+/// Returns at most `max` grapheme clusters of `text`, with an ellipsis when
+/// anything was cut. Never splits inside a multi-byte sequence or a
+/// combined character.
+pub fn truncate(text: &str, max: usize) -> String {
+    match text.grapheme_indices(true).nth(max) {
+        Some((cut, _)) => {
+            let mut truncated = text[..cut].to_string();
+            truncated.push(ELLIPSIS);
+            truncated
+        }
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_returned_unchanged() {
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn cuts_on_grapheme_boundaries_in_cjk_text() {
+        // Each of these is one grapheme but three bytes; a byte-indexed
+        // slice at any count not divisible by three would panic.
+        assert_eq!(truncate("解析に失敗しました", 4), "解析に失…");
+    }
+
+    #[test]
+    fn never_splits_combined_emoji() {
+        // Family emoji: four code points joined with ZWJ, one grapheme.
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F466}";
+        let text = format!("{family}{family}");
+        assert_eq!(truncate(&text, 1), format!("{family}…"));
+        assert_eq!(truncate(&text, 2), text);
+    }
+}
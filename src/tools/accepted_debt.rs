@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// How many accepted issues get a changelog lookup to attribute who accepted
+/// them; the changelog endpoint is one call per issue.
+const DEFAULT_CHANGELOG_LOOKUPS: u32 = 20;
+
+const RESOLUTIONS: &str = "WONTFIX,FALSE-POSITIVE";
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Only count issues resolved after this date (YYYY-MM-DD).
+    #[serde(alias = "createdAfter")]
+    created_after: Option<String>,
+    #[serde(alias = "maxChangelogLookups")]
+    max_changelog_lookups: Option<u32>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_accepted_issues_report".to_string(),
+        description: "Audit accepted debt: issues resolved as won't fix or false positive, \
+                      broken down by resolution, rule and month, with the accepting user \
+                      attributed from a sample of issue changelogs."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "created_after": {
+                    "type": "string",
+                    "description": "Only issues created after this date (YYYY-MM-DD)",
+                },
+                "max_changelog_lookups": {
+                    "type": "integer",
+                    "description": "Changelog calls used to attribute acceptors (default 20)",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let mut query = vec![
+        ("componentKeys", params.project_key.clone()),
+        ("resolutions", RESOLUTIONS.to_string()),
+        ("facets", "resolutions,rules".to_string()),
+        ("s", "UPDATE_DATE".to_string()),
+        ("asc", "false".to_string()),
+        ("ps", "500".to_string()),
+    ];
+    if let Some(created_after) = &params.created_after {
+        query.push(("createdAfter", created_after.clone()));
+    }
+    let response: Value = super::map_project_not_found(
+        ctx.client.get("/api/issues/search", &query).await,
+        &params.project_key,
+    )?;
+
+    let issues: &[Value] = response["issues"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    let by_resolution = facet_counts(&response, "resolutions");
+    let by_rule = facet_counts(&response, "rules");
+    let by_month = monthly_counts(issues);
+
+    let lookups = params
+        .max_changelog_lookups
+        .unwrap_or(DEFAULT_CHANGELOG_LOOKUPS)
+        .min(100) as usize;
+    let mut accepted_by: BTreeMap<String, u64> = BTreeMap::new();
+    let mut sampled = 0usize;
+    for issue in issues.iter().take(lookups) {
+        let Some(key) = issue["key"].as_str() else {
+            continue;
+        };
+        let changelog: Value = ctx
+            .client
+            .get("/api/issues/changelog", &[("issue", key.to_string())])
+            .await?;
+        sampled += 1;
+        if let Some(user) = find_accepting_user(&changelog) {
+            *accepted_by.entry(user).or_default() += 1;
+        }
+    }
+
+    super::json_result(ctx, &json!({
+        "project": params.project_key,
+        "total_accepted": response["paging"]["total"],
+        "by_resolution": by_resolution,
+        "by_rule": by_rule,
+        "by_month": by_month,
+        "accepted_by": {
+            "sampled_issues": sampled,
+            "users": accepted_by,
+        },
+    }))
+}
+
+/// Collects `val -> count` pairs from the named facet.
+fn facet_counts(response: &Value, property: &str) -> BTreeMap<String, u64> {
+    response["facets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|facet| facet["property"] == property)
+        .flat_map(|facet| facet["values"].as_array().into_iter().flatten())
+        .filter_map(|value| {
+            Some((value["val"].as_str()?.to_string(), value["count"].as_u64()?))
+        })
+        .collect()
+}
+
+/// Buckets issues by the `YYYY-MM` of their last update, which for resolved
+/// issues approximates when they were accepted.
+fn monthly_counts(issues: &[Value]) -> BTreeMap<String, u64> {
+    let mut counts = BTreeMap::new();
+    for issue in issues {
+        if let Some(date) = issue["updateDate"].as_str() {
+            if date.len() >= 7 {
+                *counts.entry(date[..7].to_string()).or_insert(0u64) += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Finds the user who set a WONTFIX or FALSE-POSITIVE resolution in an issue
+/// changelog, preferring the most recent such change.
+fn find_accepting_user(changelog: &Value) -> Option<String> {
+    changelog["changelog"]
+        .as_array()?
+        .iter()
+        .rev()
+        .find(|entry| {
+            entry["diffs"].as_array().is_some_and(|diffs| {
+                diffs.iter().any(|diff| {
+                    diff["key"] == "resolution"
+                        && matches!(
+                            diff["newValue"].as_str(),
+                            Some("WONTFIX") | Some("FALSE-POSITIVE")
+                        )
+                })
+            })
+        })
+        .and_then(|entry| entry["user"].as_str())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_issues_by_month() {
+        let issues = vec![
+            json!({"updateDate": "2026-07-01T10:00:00+0000"}),
+            json!({"updateDate": "2026-07-15T10:00:00+0000"}),
+            json!({"updateDate": "2026-08-02T10:00:00+0000"}),
+        ];
+        let counts = monthly_counts(&issues);
+        assert_eq!(counts.get("2026-07"), Some(&2));
+        assert_eq!(counts.get("2026-08"), Some(&1));
+    }
+
+    #[test]
+    fn finds_user_who_set_resolution() {
+        let changelog = json!({
+            "changelog": [
+                {"user": "alice", "diffs": [{"key": "assignee", "newValue": "bob"}]},
+                {"user": "carol", "diffs": [{"key": "resolution", "newValue": "WONTFIX"}]},
+            ],
+        });
+        assert_eq!(find_accepting_user(&changelog), Some("carol".to_string()));
+        assert_eq!(find_accepting_user(&json!({"changelog": []})), None);
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::CeTask;
+
+const DEFAULT_TIMEOUT_SECONDS: u64 = 300;
+const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 5;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    /// Project whose most recent analysis should be awaited.
+    #[serde(alias = "projectKey")]
+    project_key: Option<String>,
+    /// Compute Engine task id (from the scanner's report-task.txt), takes
+    /// precedence over `project_key`.
+    #[serde(alias = "taskId")]
+    task_id: Option<String>,
+    #[serde(alias = "timeoutSeconds")]
+    timeout_seconds: Option<u64>,
+    #[serde(alias = "pollIntervalSeconds")]
+    poll_interval_seconds: Option<u64>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_wait_for_analysis".to_string(),
+        description: "Wait until a background analysis task completes, then report its outcome \
+                      together with the resulting quality gate status. Polls the Compute Engine \
+                      and emits progress notifications while waiting."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {
+                    "type": "string",
+                    "description": "Project key; the most recent analysis task is awaited",
+                },
+                "task_id": {
+                    "type": "string",
+                    "description": "Compute Engine task id, e.g. from report-task.txt",
+                },
+                "timeout_seconds": {
+                    "type": "integer",
+                    "description": "Give up after this many seconds (default 300)",
+                },
+                "poll_interval_seconds": {
+                    "type": "integer",
+                    "description": "Seconds between polls (default 5)",
+                },
+            },
+        }),
+    }
+}
+
+pub async fn run(
+    ctx: &ServerContext,
+    args: Value,
+    progress_token: Option<Value>,
+) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    if params.project_key.is_none() && params.task_id.is_none() {
+        return Err(Error::InvalidArguments(
+            "either project_key or task_id is required".to_string(),
+        ));
+    }
+    let timeout = Duration::from_secs(params.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT_SECONDS));
+    let interval = Duration::from_secs(
+        params
+            .poll_interval_seconds
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECONDS)
+            .max(1),
+    );
+    let started = Instant::now();
+
+    loop {
+        let task = poll_task(ctx, &params).await?;
+        match task {
+            Some(task) if is_terminal(&task.status) => {
+                return finish(ctx, task).await;
+            }
+            other => {
+                let status = other
+                    .map(|t| t.status)
+                    .unwrap_or_else(|| "QUEUED".to_string());
+                if let Some(token) = &progress_token {
+                    ctx.notifier.progress(
+                        token,
+                        started.elapsed().as_secs(),
+                        Some(timeout.as_secs()),
+                        &format!("analysis task is {status}"),
+                    );
+                }
+            }
+        }
+        if started.elapsed() + interval > timeout {
+            return Ok(CallToolResult::error(format!(
+                "timed out after {}s waiting for analysis to complete",
+                timeout.as_secs()
+            )));
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+fn is_terminal(status: &str) -> bool {
+    matches!(status, "SUCCESS" | "FAILED" | "CANCELED")
+}
+
+/// Fetches the task being awaited: either the explicit task id, or the
+/// in-progress/most-recent task of the project. Returns `None` while the
+/// project still has queued work and no terminal task.
+async fn poll_task(ctx: &ServerContext, params: &Params) -> Result<Option<CeTask>> {
+    if let Some(task_id) = &params.task_id {
+        return Ok(Some(ctx.client.ce_task(task_id).await?.task));
+    }
+    let project_key = params.project_key.as_deref().expect("validated above");
+    let activity = ctx.client.ce_component(project_key).await?;
+    if !activity.queue.is_empty() {
+        return Ok(activity.queue.into_iter().next());
+    }
+    match activity.current {
+        Some(task) => Ok(Some(task)),
+        None => Err(Error::InvalidArguments(format!(
+            "no analysis task found for project {project_key}"
+        ))),
+    }
+}
+
+async fn finish(ctx: &ServerContext, task: CeTask) -> Result<CallToolResult> {
+    let quality_gate = if task.status == "SUCCESS" {
+        match &task.analysis_id {
+            Some(analysis_id) => Some(
+                ctx.client
+                    .quality_gate_status_for_analysis(analysis_id)
+                    .await?
+                    .project_status,
+            ),
+            None => match &task.component_key {
+                Some(key) => Some(ctx.client.quality_gate_status(key).await?.project_status),
+                None => None,
+            },
+        }
+    } else {
+        None
+    };
+    let succeeded = task.status == "SUCCESS";
+    let result = json!({
+        "task": task,
+        "quality_gate": quality_gate,
+    });
+    let text = serde_json::to_string_pretty(&result)?;
+    Ok(if succeeded {
+        CallToolResult::text(text)
+    } else {
+        CallToolResult::error(text)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_camel_case_aliases() {
+        let params: Params = super::super::parse_args(json!({
+            "taskId": "AYhqq",
+            "timeoutSeconds": 60,
+            "pollIntervalSeconds": 2,
+        }))
+        .unwrap();
+        assert_eq!(params.task_id.as_deref(), Some("AYhqq"));
+        assert_eq!(params.timeout_seconds, Some(60));
+        assert_eq!(params.poll_interval_seconds, Some(2));
+    }
+}
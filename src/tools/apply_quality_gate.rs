@@ -0,0 +1,154 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    /// Name of the quality gate to assign, as shown in the UI.
+    #[serde(alias = "gateName")]
+    gate_name: String,
+    #[serde(alias = "projectKeys")]
+    project_keys: Vec<String>,
+    /// Report what would change without assigning anything.
+    #[serde(default)]
+    dry_run: bool,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_apply_quality_gate".to_string(),
+        description: "Assign a named quality gate to a list of projects, with per-project \
+                      results and a dry-run mode — for platform-wide gate rollouts. Requires \
+                      the server to run with --allow-admin-operations."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "gate_name": {"type": "string", "description": "Quality gate name, e.g. Sonar way"},
+                "project_keys": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Projects to assign the gate to",
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Report what would change without assigning anything",
+                },
+            },
+            "required": ["gate_name", "project_keys"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    super::ensure_admin_allowed(ctx)?;
+    let params: Params = super::parse_args(args)?;
+    if params.project_keys.is_empty() {
+        return Err(Error::InvalidArguments(
+            "project_keys must name at least one project".to_string(),
+        ));
+    }
+    // Resolve the gate up front so a typo fails once instead of per project.
+    let gates: Value = ctx.client.get("/api/qualitygates/list", &[]).await?;
+    let gate = gates["qualitygates"]
+        .as_array()
+        .and_then(|gates| gates.iter().find(|gate| gate["name"] == *params.gate_name))
+        .ok_or_else(|| {
+            Error::InvalidArguments(format!(
+                "no quality gate named {:?}; available: {}",
+                params.gate_name,
+                gate_names(&gates).join(", ")
+            ))
+        })?;
+    let gate_name = gate["name"].as_str().unwrap_or(&params.gate_name).to_string();
+
+    let mut results = Vec::new();
+    for project in &params.project_keys {
+        if params.dry_run {
+            results.push(json!({"project": project, "status": "would assign"}));
+            continue;
+        }
+        let form = vec![
+            ("gateName", gate_name.clone()),
+            ("projectKey", project.clone()),
+        ];
+        match ctx.client.post("/api/qualitygates/select", &form).await {
+            Ok(()) => results.push(json!({"project": project, "status": "assigned"})),
+            // Keep going: one missing project should not abort a rollout.
+            Err(err) => results.push(json!({
+                "project": project,
+                "status": "failed",
+                "error": err.to_string(),
+            })),
+        }
+    }
+
+    super::json_result(
+        ctx,
+        &json!({
+            "gate": gate_name,
+            "dry_run": params.dry_run,
+            "results": results,
+        }),
+    )
+}
+
+fn gate_names(gates: &Value) -> Vec<String> {
+    gates["qualitygates"]
+        .as_array()
+        .map(|gates| {
+            gates
+                .iter()
+                .filter_map(|gate| gate["name"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    fn context(allow_admin: bool) -> ServerContext {
+        let mut args = vec![
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ];
+        if allow_admin {
+            args.push("--allow-admin-operations");
+        }
+        ServerContext::new(Config::parse_from(args)).expect("context")
+    }
+
+    #[tokio::test]
+    async fn refuses_writes_unless_admin_operations_are_enabled() {
+        let ctx = context(false);
+        let err = run(&ctx, json!({"gate_name": "Sonar way", "project_keys": ["demo"]}))
+            .await
+            .expect_err("gate should refuse");
+        assert!(matches!(err, Error::AdminOperationsDisabled));
+    }
+
+    #[tokio::test]
+    async fn requires_at_least_one_project() {
+        let ctx = context(true);
+        let err = run(&ctx, json!({"gate_name": "Sonar way", "project_keys": []}))
+            .await
+            .expect_err("empty rollout should be refused");
+        assert!(matches!(err, Error::InvalidArguments(_)));
+    }
+
+    #[test]
+    fn lists_gate_names_from_the_response() {
+        let gates = json!({"qualitygates": [{"name": "Sonar way"}, {"name": "Strict"}]});
+        assert_eq!(gate_names(&gates), vec!["Sonar way", "Strict"]);
+        assert!(gate_names(&json!({})).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Assignees broken down individually; the facet covers the rest.
+const ASSIGNEES_SHOWN: usize = 10;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_assignee_report".to_string(),
+        description: "Break open issues down per assignee (and unassigned): counts per \
+                      severity and the oldest open issue per person, for balancing triage \
+                      work."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let overview: Value = super::map_project_not_found(
+        ctx.client
+            .get(
+                "/api/issues/search",
+                &[
+                    ("componentKeys", params.project_key.clone()),
+                    ("resolved", "false".to_string()),
+                    ("facets", "assignees".to_string()),
+                    ("ps", "1".to_string()),
+                ],
+            )
+            .await,
+        &params.project_key,
+    )?;
+
+    let logins: Vec<String> = facet_values(&overview, "assignees")
+        .into_iter()
+        .filter(|(login, _)| !login.is_empty())
+        .take(ASSIGNEES_SHOWN)
+        .map(|(login, _)| login)
+        .collect();
+    let project = &params.project_key;
+    let per_assignee = logins.iter().map(|login| async move {
+        let detail = breakdown(ctx, project, &[("assignees", login.clone())]).await?;
+        Ok::<_, Error>(json!({
+            "assignee": login,
+            "open_issues": detail.total,
+            "by_severity": detail.by_severity,
+            "oldest": detail.oldest,
+        }))
+    });
+    let assignees = futures::future::try_join_all(per_assignee).await?;
+    let unassigned = breakdown(ctx, project, &[("assigned", "false".to_string())]).await?;
+
+    super::json_result(
+        ctx,
+        &json!({
+            "project": params.project_key,
+            "total_open": overview["paging"]["total"],
+            "assignees": assignees,
+            "unassigned": {
+                "open_issues": unassigned.total,
+                "by_severity": unassigned.by_severity,
+                "oldest": unassigned.oldest,
+            },
+        }),
+    )
+}
+
+/// Severity counts and the oldest open issue for one slice of the project's
+/// issues.
+struct Breakdown {
+    total: u64,
+    by_severity: BTreeMap<String, u64>,
+    oldest: Value,
+}
+
+/// One search answers everything: the severities facet carries the counts,
+/// and sorting by creation date makes the single returned issue the oldest.
+async fn breakdown(
+    ctx: &ServerContext,
+    project: &str,
+    filter: &[(&str, String)],
+) -> Result<Breakdown> {
+    let mut query = vec![
+        ("componentKeys", project.to_string()),
+        ("resolved", "false".to_string()),
+        ("facets", "severities".to_string()),
+        ("s", "CREATION_DATE".to_string()),
+        ("asc", "true".to_string()),
+        ("ps", "1".to_string()),
+    ];
+    query.extend(filter.iter().cloned());
+    let response: Value = ctx.client.get("/api/issues/search", &query).await?;
+    Ok(Breakdown {
+        total: response["paging"]["total"].as_u64().unwrap_or(0),
+        by_severity: facet_values(&response, "severities").into_iter().collect(),
+        oldest: oldest_issue(&response),
+    })
+}
+
+/// Pulls `(value, count)` pairs out of a facet of an issues search response.
+fn facet_values(response: &Value, property: &str) -> Vec<(String, u64)> {
+    response["facets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|facet| facet["property"] == property)
+        .flat_map(|facet| facet["values"].as_array().into_iter().flatten())
+        .filter_map(|value| Some((value["val"].as_str()?.to_string(), value["count"].as_u64()?)))
+        .collect()
+}
+
+/// Summarizes the first (oldest, given the sort) issue of a search, with
+/// its age in days.
+fn oldest_issue(response: &Value) -> Value {
+    let Some(issue) = response["issues"].as_array().and_then(|issues| issues.first()) else {
+        return Value::Null;
+    };
+    let created = issue["creationDate"].as_str().unwrap_or_default();
+    let mut oldest = json!({
+        "key": issue["key"],
+        "severity": issue["severity"],
+        "created": created,
+    });
+    if let Some(age) = super::age_in_days(created) {
+        oldest["age_days"] = json!(age);
+    }
+    oldest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_the_oldest_issue_from_a_sorted_search() {
+        let response = json!({"issues": [
+            {"key": "AX1", "severity": "MAJOR", "creationDate": "2024-05-01T10:00:00+0000"},
+        ]});
+        let oldest = oldest_issue(&response);
+        assert_eq!(oldest["key"], "AX1");
+        assert!(oldest["age_days"].as_i64().unwrap() > 0);
+        assert_eq!(oldest_issue(&json!({"issues": []})), Value::Null);
+    }
+
+    #[test]
+    fn extracts_facet_values() {
+        let response = json!({"facets": [
+            {"property": "assignees", "values": [
+                {"val": "alice", "count": 7},
+                {"val": "", "count": 3},
+            ]},
+        ]});
+        assert_eq!(
+            facet_values(&response, "assignees"),
+            vec![("alice".to_string(), 7), (String::new(), 3)]
+        );
+    }
+}
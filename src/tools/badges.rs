@@ -0,0 +1,61 @@
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// When set, return the badge for this metric instead of the quality
+    /// gate badge.
+    metric: Option<String>,
+    branch: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_project_badge".to_string(),
+        description: "Fetch a project badge as an SVG image: the quality gate badge by default, \
+                      or a metric badge when a metric key is given."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "metric": {
+                    "type": "string",
+                    "description": "Metric key, e.g. coverage or bugs; omit for the gate badge",
+                },
+                "branch": {"type": "string", "description": "Branch name"},
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let mut query = vec![("project", params.project_key.clone())];
+    if let Some(branch) = &params.branch {
+        query.push(("branch", branch.clone()));
+    }
+    let path = match &params.metric {
+        Some(metric) => {
+            query.push(("metric", metric.clone()));
+            "/api/project_badges/measure"
+        }
+        None => "/api/project_badges/quality_gate",
+    };
+    let (bytes, content_type) = ctx.client.get_bytes(path, &query).await?;
+    let mime_type = if content_type.starts_with("image/") {
+        content_type
+    } else {
+        "image/svg+xml".to_string()
+    };
+    let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Ok(CallToolResult::image(data, mime_type))
+}
@@ -0,0 +1,37 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_list_branches".to_string(),
+        description: "List a project's branches with their type, quality gate status and last \
+                      analysis date."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let response = super::map_project_not_found(
+        ctx.client.list_branches(&params.project_key).await,
+        &params.project_key,
+    )?;
+    super::json_result(ctx, &response)
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Metrics compared when the caller does not ask for specific ones.
+const DEFAULT_METRICS: &[&str] = &[
+    "ncloc",
+    "bugs",
+    "vulnerabilities",
+    "code_smells",
+    "coverage",
+    "duplicated_lines_density",
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKeys")]
+    project_keys: Vec<String>,
+    #[serde(alias = "metricKeys")]
+    metric_keys: Option<Vec<String>>,
+    /// Metric to sort the matrix by; the first metric when omitted.
+    #[serde(alias = "sortBy")]
+    sort_by: Option<String>,
+    /// Sort ascending instead of the default descending.
+    ascending: Option<bool>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_compare_projects".to_string(),
+        description: "Fetch the same measures for several projects concurrently and return a \
+                      comparison matrix sorted by a chosen metric."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_keys": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Projects to compare",
+                },
+                "metric_keys": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Metric keys, e.g. ncloc, coverage, bugs",
+                },
+                "sort_by": {
+                    "type": "string",
+                    "description": "Metric to sort by (default: the first metric)",
+                },
+                "ascending": {
+                    "type": "boolean",
+                    "description": "Sort ascending instead of descending",
+                },
+            },
+            "required": ["project_keys"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    if params.project_keys.is_empty() {
+        return Err(Error::InvalidArguments(
+            "project_keys must not be empty".to_string(),
+        ));
+    }
+    let metric_keys = params
+        .metric_keys
+        .unwrap_or_else(|| DEFAULT_METRICS.iter().map(|m| m.to_string()).collect());
+    let sort_by = match params.sort_by {
+        Some(sort_by) if !metric_keys.contains(&sort_by) => {
+            return Err(Error::InvalidArguments(format!(
+                "sort_by {sort_by} is not among the compared metrics ({})",
+                metric_keys.join(", ")
+            )));
+        }
+        Some(sort_by) => sort_by,
+        None => metric_keys[0].clone(),
+    };
+
+    let metrics = &metric_keys;
+    let fetches = params.project_keys.iter().map(|project| async move {
+        let response = super::map_project_not_found(
+            ctx.client.get_measures(project, metrics).await,
+            project,
+        )?;
+        let measures: BTreeMap<String, Option<String>> = response
+            .component
+            .measures
+            .iter()
+            .map(|measure| (measure.metric.clone(), measure.value.clone()))
+            .collect();
+        Ok::<_, Error>(json!({
+            "project": project,
+            "name": response.component.name,
+            "measures": measures,
+        }))
+    });
+    let mut rows = futures::future::try_join_all(fetches).await?;
+    sort_rows(&mut rows, &sort_by, params.ascending.unwrap_or(false));
+
+    super::json_result(
+        ctx,
+        &json!({
+            "metrics": metric_keys,
+            "sorted_by": sort_by,
+            "projects": rows,
+        }),
+    )
+}
+
+/// Sorts the matrix by the chosen metric, comparing numerically where the
+/// values parse; projects missing the metric sink to the end either way.
+fn sort_rows(rows: &mut [Value], sort_by: &str, ascending: bool) {
+    let rank = |row: &Value| -> Option<f64> {
+        row["measures"][sort_by]
+            .as_str()
+            .and_then(|value| value.parse::<f64>().ok())
+    };
+    rows.sort_by(|a, b| match (rank(a), rank(b)) {
+        (Some(a), Some(b)) if ascending => a.total_cmp(&b),
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(project: &str, coverage: Option<&str>) -> Value {
+        json!({"project": project, "measures": {"coverage": coverage}})
+    }
+
+    #[test]
+    fn sorts_numerically_with_missing_values_last() {
+        let mut rows = vec![
+            row("a", Some("9.5")),
+            row("b", None),
+            row("c", Some("73.5")),
+        ];
+        sort_rows(&mut rows, "coverage", false);
+        let order: Vec<&str> = rows.iter().map(|r| r["project"].as_str().unwrap()).collect();
+        assert_eq!(order, ["c", "a", "b"]);
+
+        sort_rows(&mut rows, "coverage", true);
+        let order: Vec<&str> = rows.iter().map(|r| r["project"].as_str().unwrap()).collect();
+        assert_eq!(order, ["a", "c", "b"]);
+    }
+}
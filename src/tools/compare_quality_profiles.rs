@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    /// Language the compared profiles apply to, e.g. `java`.
+    language: String,
+    #[serde(alias = "leftProfileKey")]
+    left_profile_key: Option<String>,
+    #[serde(alias = "leftProjectKey")]
+    left_project_key: Option<String>,
+    #[serde(alias = "rightProfileKey")]
+    right_profile_key: Option<String>,
+    #[serde(alias = "rightProjectKey")]
+    right_project_key: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_compare_quality_profiles".to_string(),
+        description: "Diff the active rules of two quality profiles — given directly by key or \
+                      resolved from two projects' effective profiles — reporting rules only \
+                      active on one side and rules whose severity or parameters differ. Useful \
+                      when harmonizing standards across teams."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "language": {
+                    "type": "string",
+                    "description": "Language key the profiles apply to, e.g. java",
+                },
+                "left_profile_key": {"type": "string", "description": "Quality profile key for the left side"},
+                "left_project_key": {"type": "string", "description": "Project whose effective profile is the left side"},
+                "right_profile_key": {"type": "string", "description": "Quality profile key for the right side"},
+                "right_project_key": {"type": "string", "description": "Project whose effective profile is the right side"},
+            },
+            "required": ["language"],
+        }),
+    }
+}
+
+/// One side of the comparison, resolved to a concrete profile.
+struct Profile {
+    key: String,
+    name: Option<String>,
+}
+
+/// A rule activation within a profile: severity plus parameter overrides.
+#[derive(PartialEq)]
+struct Activation {
+    name: String,
+    severity: String,
+    params: BTreeMap<String, String>,
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let left = resolve_profile(
+        ctx,
+        "left",
+        &params.language,
+        params.left_profile_key.as_deref(),
+        params.left_project_key.as_deref(),
+    )
+    .await?;
+    let right = resolve_profile(
+        ctx,
+        "right",
+        &params.language,
+        params.right_profile_key.as_deref(),
+        params.right_project_key.as_deref(),
+    )
+    .await?;
+
+    let left_rules = active_rules(ctx, &left.key).await?;
+    let right_rules = active_rules(ctx, &right.key).await?;
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (rule, activation) in &right_rules {
+        match left_rules.get(rule) {
+            None => added.push(json!({
+                "rule": rule,
+                "name": activation.name,
+                "severity": activation.severity,
+            })),
+            Some(before) if before != activation => changed.push(json!({
+                "rule": rule,
+                "name": activation.name,
+                "left": {"severity": before.severity, "params": before.params},
+                "right": {"severity": activation.severity, "params": activation.params},
+            })),
+            Some(_) => {}
+        }
+    }
+    let removed: Vec<Value> = left_rules
+        .iter()
+        .filter(|(rule, _)| !right_rules.contains_key(*rule))
+        .map(|(rule, activation)| {
+            json!({
+                "rule": rule,
+                "name": activation.name,
+                "severity": activation.severity,
+            })
+        })
+        .collect();
+
+    super::json_result(
+        ctx,
+        &json!({
+            "language": params.language,
+            "left": {"profile_key": left.key, "name": left.name, "active_rules": left_rules.len()},
+            "right": {"profile_key": right.key, "name": right.name, "active_rules": right_rules.len()},
+            "only_in_right": added,
+            "only_in_left": removed,
+            "changed": changed,
+        }),
+    )
+}
+
+/// Resolves one side to a profile key: taken verbatim when given, otherwise
+/// looked up as the project's effective profile for the language.
+async fn resolve_profile(
+    ctx: &ServerContext,
+    side: &str,
+    language: &str,
+    profile_key: Option<&str>,
+    project_key: Option<&str>,
+) -> Result<Profile> {
+    match (profile_key, project_key) {
+        (Some(key), None) => Ok(Profile {
+            key: key.to_string(),
+            name: None,
+        }),
+        (None, Some(project)) => {
+            let query = vec![
+                ("project", project.to_string()),
+                ("language", language.to_string()),
+            ];
+            let response: Value = super::map_project_not_found(
+                ctx.client.get("/api/qualityprofiles/search", &query).await,
+                project,
+            )?;
+            let profile = response["profiles"]
+                .as_array()
+                .and_then(|profiles| {
+                    profiles
+                        .iter()
+                        .find(|profile| profile["language"] == language)
+                })
+                .ok_or_else(|| {
+                    Error::InvalidArguments(format!(
+                        "project {project} has no quality profile for language {language}"
+                    ))
+                })?;
+            Ok(Profile {
+                key: profile["key"].as_str().unwrap_or_default().to_string(),
+                name: profile["name"].as_str().map(str::to_string),
+            })
+        }
+        _ => Err(Error::InvalidArguments(format!(
+            "give exactly one of {side}_profile_key or {side}_project_key"
+        ))),
+    }
+}
+
+/// Fetches every rule active in a profile, with the activation severity and
+/// parameter overrides, paging through `/api/rules/search`.
+async fn active_rules(
+    ctx: &ServerContext,
+    profile_key: &str,
+) -> Result<BTreeMap<String, Activation>> {
+    let mut rules = BTreeMap::new();
+    let mut page = 1u32;
+    loop {
+        let query = vec![
+            ("qprofile", profile_key.to_string()),
+            ("activation", "true".to_string()),
+            ("f", "name".to_string()),
+            ("p", page.to_string()),
+            ("ps", "500".to_string()),
+        ];
+        let response: Value = ctx.client.get("/api/rules/search", &query).await?;
+        let page_rules = response["rules"].as_array().cloned().unwrap_or_default();
+        if page_rules.is_empty() {
+            break;
+        }
+        for rule in &page_rules {
+            let Some(key) = rule["key"].as_str() else { continue };
+            rules.insert(
+                key.to_string(),
+                Activation {
+                    name: rule["name"].as_str().unwrap_or_default().to_string(),
+                    severity: activation_severity(&response["actives"][key], profile_key),
+                    params: activation_params(&response["actives"][key], profile_key),
+                },
+            );
+        }
+        let total = response["paging"]["total"].as_u64().unwrap_or(0);
+        if rules.len() as u64 >= total || page * 500 >= 10_000 {
+            break;
+        }
+        page += 1;
+    }
+    Ok(rules)
+}
+
+/// The activation entry for a profile within a rule's `actives` list.
+fn activation_for<'a>(actives: &'a Value, profile_key: &str) -> Option<&'a Value> {
+    actives
+        .as_array()?
+        .iter()
+        .find(|active| active["qProfile"] == profile_key)
+}
+
+fn activation_severity(actives: &Value, profile_key: &str) -> String {
+    activation_for(actives, profile_key)
+        .and_then(|active| active["severity"].as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn activation_params(actives: &Value, profile_key: &str) -> BTreeMap<String, String> {
+    activation_for(actives, profile_key)
+        .and_then(|active| active["params"].as_array())
+        .map(|params| {
+            params
+                .iter()
+                .filter_map(|param| {
+                    Some((
+                        param["key"].as_str()?.to_string(),
+                        param["value"].as_str().unwrap_or_default().to_string(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_activation_matching_the_profile() {
+        let actives = json!([
+            {"qProfile": "other", "severity": "INFO", "params": []},
+            {"qProfile": "mine", "severity": "CRITICAL", "params": [
+                {"key": "threshold", "value": "15"},
+            ]},
+        ]);
+        assert_eq!(activation_severity(&actives, "mine"), "CRITICAL");
+        assert_eq!(
+            activation_params(&actives, "mine").get("threshold"),
+            Some(&"15".to_string())
+        );
+        assert_eq!(activation_severity(&actives, "missing"), "");
+        assert!(activation_params(&json!(null), "mine").is_empty());
+    }
+
+    #[test]
+    fn activations_compare_on_severity_and_params() {
+        let activation = |severity: &str, params: BTreeMap<String, String>| Activation {
+            name: "Rule".to_string(),
+            severity: severity.to_string(),
+            params,
+        };
+        assert!(activation("MAJOR", BTreeMap::new()) == activation("MAJOR", BTreeMap::new()));
+        assert!(activation("MAJOR", BTreeMap::new()) != activation("CRITICAL", BTreeMap::new()));
+        let reconfigured = [("max".to_string(), "10".to_string())].into();
+        assert!(activation("MAJOR", BTreeMap::new()) != activation("MAJOR", reconfigured));
+    }
+}
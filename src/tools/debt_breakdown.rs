@@ -0,0 +1,193 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Components listed per level.
+const COMPONENTS_SHOWN: u32 = 50;
+
+/// Rules listed in the by-rule breakdown.
+const RULES_SHOWN: usize = 15;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Break debt down per "directory" (default) or per "file".
+    level: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_debt_breakdown".to_string(),
+        description: "Break a project's technical debt (sqale_index) down per directory or \
+                      file, plus the open issue counts per rule — the inputs for planning a \
+                      remediation sprint."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "level": {
+                    "type": "string",
+                    "enum": ["directory", "file"],
+                    "description": "Granularity of the breakdown (default directory)",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let qualifier = match params.level.as_deref().unwrap_or("directory") {
+        "directory" => "DIR",
+        "file" => "FIL",
+        other => {
+            return Err(Error::InvalidArguments(format!(
+                "unknown level {other} (expected directory or file)"
+            )))
+        }
+    };
+
+    let tree: Value = super::map_project_not_found(
+        ctx.client
+            .get(
+                "/api/measures/component_tree",
+                &[
+                    ("component", params.project_key.clone()),
+                    ("metricKeys", "sqale_index".to_string()),
+                    ("metricSort", "sqale_index".to_string()),
+                    ("metricSortFilter", "withMeasuresOnly".to_string()),
+                    ("s", "metric".to_string()),
+                    ("asc", "false".to_string()),
+                    ("qualifiers", qualifier.to_string()),
+                    ("ps", COMPONENTS_SHOWN.to_string()),
+                ],
+            )
+            .await,
+        &params.project_key,
+    )?;
+    let issues: Value = ctx
+        .client
+        .get(
+            "/api/issues/search",
+            &[
+                ("componentKeys", params.project_key.clone()),
+                ("resolved", "false".to_string()),
+                ("facets", "rules".to_string()),
+                ("ps", "1".to_string()),
+            ],
+        )
+        .await?;
+
+    let components = debt_components(&tree);
+    let total_minutes: u64 = components
+        .iter()
+        .filter_map(|component| component["debt_minutes"].as_u64())
+        .sum();
+    super::json_result(
+        ctx,
+        &json!({
+            "project": params.project_key,
+            "level": params.level.as_deref().unwrap_or("directory"),
+            "total_debt": format_effort(total_minutes),
+            "total_debt_minutes": total_minutes,
+            "components": components,
+            "open_issues_by_rule": rule_counts(&issues),
+        }),
+    )
+}
+
+/// Trims the component tree page to debt rows, keeping the server's
+/// descending order.
+fn debt_components(tree: &Value) -> Vec<Value> {
+    tree["components"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|component| {
+            let minutes: u64 = component["measures"]
+                .as_array()?
+                .iter()
+                .find(|measure| measure["metric"] == "sqale_index")?["value"]
+                .as_str()?
+                .parse()
+                .ok()?;
+            Some(json!({
+                "path": component["path"].as_str().or(component["name"].as_str()),
+                "debt": format_effort(minutes),
+                "debt_minutes": minutes,
+            }))
+        })
+        .collect()
+}
+
+/// Pulls `(rule, open issue count)` pairs from the rules facet, most
+/// violated first.
+fn rule_counts(issues: &Value) -> Vec<Value> {
+    issues["facets"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|facet| facet["property"] == "rules")
+        .flat_map(|facet| facet["values"].as_array().into_iter().flatten())
+        .take(RULES_SHOWN)
+        .map(|value| json!({"rule": value["val"], "open_issues": value["count"]}))
+        .collect()
+}
+
+/// Renders an effort in SonarQube's own notation: 8-hour days, e.g.
+/// "2d 3h 15min".
+fn format_effort(minutes: u64) -> String {
+    let days = minutes / (8 * 60);
+    let hours = minutes % (8 * 60) / 60;
+    let minutes = minutes % 60;
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{days}d"));
+    }
+    if hours > 0 {
+        parts.push(format!("{hours}h"));
+    }
+    if minutes > 0 || parts.is_empty() {
+        parts.push(format!("{minutes}min"));
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_effort_in_eight_hour_days() {
+        assert_eq!(format_effort(0), "0min");
+        assert_eq!(format_effort(45), "45min");
+        assert_eq!(format_effort(60), "1h");
+        assert_eq!(format_effort(8 * 60), "1d");
+        assert_eq!(format_effort(2 * 8 * 60 + 3 * 60 + 15), "2d 3h 15min");
+    }
+
+    #[test]
+    fn extracts_debt_rows_and_rule_counts() {
+        let tree = json!({"components": [
+            {"path": "src/core", "measures": [{"metric": "sqale_index", "value": "510"}]},
+            {"path": "src/util", "measures": [{"metric": "ncloc", "value": "100"}]},
+        ]});
+        let components = debt_components(&tree);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0]["debt"], "1d 30min");
+
+        let issues = json!({"facets": [{"property": "rules", "values": [
+            {"val": "rust:S1135", "count": 12},
+        ]}]});
+        assert_eq!(
+            rule_counts(&issues),
+            vec![json!({"rule": "rust:S1135", "open_issues": 12})]
+        );
+    }
+}
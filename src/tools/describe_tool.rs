@@ -0,0 +1,171 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Extended usage documentation, keyed by tool name. Longer than the schema
+/// descriptions: examples, parameter interactions, and pitfalls that help a
+/// model self-correct a malformed call. Tools without an entry fall back to
+/// their schema alone.
+const EXTENDED_DOCS: &[(&str, &str)] = &[
+    (
+        "sonarqube_get_issues",
+        "Search issues within one project.\n\n\
+         Example: {\"project_key\": \"my-app\", \"severities\": [\"BLOCKER\", \"CRITICAL\"], \
+         \"statuses\": [\"OPEN\"]}\n\n\
+         Notes:\n\
+         - `project_key` is the key, not the display name; find it with \
+           sonarqube_list_projects.\n\
+         - MQR severities (HIGH/MEDIUM/LOW) are accepted on SonarQube 10.4+ and rejected \
+           with a configuration error on older servers.\n\
+         - Results are paginated (`page`, `page_size`); the server caps page_size at 500.\n\
+         - For several projects or the whole organization, use sonarqube_search_issues; \
+           for aggregate counts only, sonarqube_get_issue_facets is far cheaper.",
+    ),
+    (
+        "sonarqube_wait_for_analysis",
+        "Block until a Compute Engine task finishes, then report the quality gate.\n\n\
+         Example: {\"task_id\": \"AYhq...\"} — the id from the scanner's report-task.txt.\n\
+         Example: {\"project_key\": \"my-app\", \"timeout_seconds\": 600}\n\n\
+         Notes:\n\
+         - `task_id` takes precedence over `project_key`; with only a project key the most \
+           recent queued/running task is awaited.\n\
+         - Emits progress notifications when the call carries a progressToken.\n\
+         - A FAILED or CANCELED task returns an error result, not a transport error.\n\
+         - Default timeout is 300 seconds, polling every 5.",
+    ),
+    (
+        "search_issues_by_text",
+        "Scan issue messages for a substring or regex.\n\n\
+         Example: {\"query\": \"hardcoded password\"}\n\
+         Example: {\"query\": \"complexity .* over \\\\d+\", \"regex\": true, \
+         \"projects\": [\"my-app\"]}\n\n\
+         Notes:\n\
+         - Substring matching is case-insensitive unless `case_sensitive` is true.\n\
+         - The scan is bounded (500 issues per page, 20 pages); narrow with `projects` \
+           when an instance is large, and check `truncated` in the result.\n\
+         - The regex dialect is Rust's regex crate: no backreferences or lookaround.",
+    ),
+    (
+        "sonarqube_set_setting",
+        "Set or reset a SonarQube setting — an administrative write.\n\n\
+         Example: {\"key\": \"sonar.exclusions\", \"values\": [\"**/generated/**\"], \
+         \"project_key\": \"my-app\"}\n\
+         Example: {\"key\": \"sonar.exclusions\", \"reset\": true, \"project_key\": \"my-app\"}\n\n\
+         Notes:\n\
+         - Fails unless the server was started with --allow-admin-operations; \
+           sonarqube_whoami shows whether the gate is open.\n\
+         - Use `values` (array) for multi-value settings and `value` for scalars; exactly \
+           one is needed unless `reset` is true.\n\
+         - Omitting `project_key` changes the GLOBAL setting.",
+    ),
+    (
+        "sonarqube_get_issue_facets",
+        "Aggregate issue counts without fetching issues.\n\n\
+         Example: {\"project_key\": \"my-app\", \"facets\": [\"severities\", \"rules\"]}\n\n\
+         Notes:\n\
+         - Each facet returns value/count buckets; totals above 10,000 are exact even \
+           though issue pagination is capped there.\n\
+         - `files` and `authors` buckets are limited to the top entries by count.",
+    ),
+    (
+        "sonarqube_get_security_report",
+        "Security rollup for compliance reviews.\n\n\
+         Example: {\"project_key\": \"my-app\"} or {} for the whole organization.\n\n\
+         Notes:\n\
+         - Organization-wide reports omit hotspots: /api/hotspots/search requires a \
+           project.\n\
+         - OWASP buckets use the 2021 taxonomy; servers older than 9.x may return an \
+           empty rollup because the facet does not exist there.",
+    ),
+    (
+        "sonarqube_get_project_badge",
+        "Fetch a project badge as an image.\n\n\
+         Example: {\"project_key\": \"my-app\", \"metric\": \"coverage\"}\n\n\
+         Notes:\n\
+         - Without `metric`, the quality gate badge is returned.\n\
+         - The result is image content (base64), not JSON — suitable for embedding, not \
+           for parsing.",
+    ),
+    (
+        "sonarqube_find_severity_overrides",
+        "Detect issues whose severity was manually changed.\n\n\
+         Example: {\"project_key\": \"my-app\", \"max_issues\": 50}\n\n\
+         Notes:\n\
+         - Inspects issue changelogs, which costs one request per issue; `max_issues` \
+           bounds the scan and results are cached per process.\n\
+         - Expect this to be slow on the first call for a large project.",
+    ),
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    /// Name of the tool to describe.
+    name: String,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "describe_tool".to_string(),
+        description: "Return extended usage documentation for any registered tool: examples, \
+                      parameter interactions and common pitfalls, beyond the schema \
+                      description."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "description": "Tool name, e.g. sonarqube_get_issues"},
+            },
+            "required": ["name"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let definition = super::definitions()
+        .into_iter()
+        .find(|tool| tool.name == params.name)
+        .ok_or_else(|| {
+            Error::InvalidArguments(format!(
+                "unknown tool: {} (list tools with tools/list)",
+                params.name
+            ))
+        })?;
+    let extended = EXTENDED_DOCS
+        .iter()
+        .find(|(name, _)| *name == params.name)
+        .map(|(_, doc)| *doc);
+    super::json_result(
+        ctx,
+        &json!({
+            "name": definition.name,
+            "description": definition.description,
+            "input_schema": definition.input_schema,
+            "usage": extended,
+            "examples": crate::resources::examples::for_tool(&params.name),
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_docs_only_reference_registered_tools() {
+        let registered: Vec<String> = super::super::definitions()
+            .into_iter()
+            .map(|tool| tool.name)
+            .collect();
+        for (name, doc) in EXTENDED_DOCS {
+            assert!(
+                registered.iter().any(|tool| tool == name),
+                "extended docs reference unregistered tool {name}"
+            );
+            assert!(!doc.trim().is_empty());
+        }
+    }
+}
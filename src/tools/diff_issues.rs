@@ -0,0 +1,248 @@
+use serde_json::{json, Value};
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Page size for the underlying issue searches.
+const PAGE_SIZE: u32 = 500;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Start of the window, as a date or datetime (e.g. 2024-05-01).
+    from: Option<String>,
+    /// End of the window; open-ended when omitted.
+    to: Option<String>,
+    /// Analysis key whose date becomes the start of the window; takes
+    /// precedence over `from`.
+    #[serde(alias = "fromAnalysis")]
+    from_analysis: Option<String>,
+    /// Analysis key whose date becomes the end of the window; takes
+    /// precedence over `to`.
+    #[serde(alias = "toAnalysis")]
+    to_analysis: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_diff_issues".to_string(),
+        description: "Compare issues across a time window given as dates or analysis keys: \
+                      what was introduced in the window, what was fixed in it, and what \
+                      predates it and is still open."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "from": {"type": "string", "description": "Window start, e.g. 2024-05-01"},
+                "to": {"type": "string", "description": "Window end; open-ended when omitted"},
+                "from_analysis": {
+                    "type": "string",
+                    "description": "Analysis key (from sonarqube_get_analysis_history) whose date starts the window",
+                },
+                "to_analysis": {
+                    "type": "string",
+                    "description": "Analysis key whose date ends the window",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let (from, to) = resolve_window(ctx, &params).await?;
+
+    // Introduced in the window: let the server filter on creation date.
+    let mut created_query = vec![
+        ("componentKeys", params.project_key.clone()),
+        ("createdAfter", from.clone()),
+    ];
+    if let Some(to) = &to {
+        created_query.push(("createdBefore", to.clone()));
+    }
+    let new_issues = fetch_issues(ctx, &created_query).await?;
+
+    // Fixed in the window: the API cannot filter on close date, so fetch
+    // resolved issues and keep the ones closed inside the window.
+    let resolved_query = vec![
+        ("componentKeys", params.project_key.clone()),
+        ("resolved", "true".to_string()),
+    ];
+    let fixed: Vec<Value> = fetch_issues(ctx, &resolved_query)
+        .await?
+        .into_iter()
+        .filter(|issue| {
+            issue["closeDate"]
+                .as_str()
+                .is_some_and(|closed| within(closed, &from, to.as_deref()))
+        })
+        .collect();
+
+    // Still open: predates the window and unresolved.
+    let open_query = vec![
+        ("componentKeys", params.project_key.clone()),
+        ("resolved", "false".to_string()),
+        ("createdBefore", from.clone()),
+    ];
+    let still_open = fetch_issues(ctx, &open_query).await?;
+
+    super::json_result(
+        ctx,
+        &json!({
+            "project": params.project_key,
+            "from": from,
+            "to": to,
+            "new": {"count": new_issues.len(), "issues": summaries(&new_issues)},
+            "fixed": {"count": fixed.len(), "issues": summaries(&fixed)},
+            "still_open": {"count": still_open.len(), "issues": summaries(&still_open)},
+        }),
+    )
+}
+
+/// Resolves the comparison window to dates, looking analysis keys up in the
+/// project's analysis history when given.
+async fn resolve_window(ctx: &ServerContext, params: &Params) -> Result<(String, Option<String>)> {
+    let needs_history = params.from_analysis.is_some() || params.to_analysis.is_some();
+    let analyses: Value = if needs_history {
+        ctx.client
+            .get(
+                "/api/project_analyses/search",
+                &[
+                    ("project", params.project_key.clone()),
+                    ("ps", PAGE_SIZE.to_string()),
+                ],
+            )
+            .await?
+    } else {
+        Value::Null
+    };
+    let resolve = |key: &str| {
+        analysis_date(&analyses, key).ok_or_else(|| {
+            Error::InvalidArguments(format!(
+                "analysis {key} not found in the last {PAGE_SIZE} analyses of {}",
+                params.project_key
+            ))
+        })
+    };
+    let from = match (&params.from_analysis, &params.from) {
+        (Some(key), _) => resolve(key)?,
+        (None, Some(from)) => from.clone(),
+        (None, None) => {
+            return Err(Error::InvalidArguments(
+                "either from or from_analysis is required".to_string(),
+            ))
+        }
+    };
+    let to = match (&params.to_analysis, &params.to) {
+        (Some(key), _) => Some(resolve(key)?),
+        (None, to) => to.clone(),
+    };
+    Ok((from, to))
+}
+
+/// Finds the date of an analysis by key in a `project_analyses/search`
+/// response.
+fn analysis_date(analyses: &Value, key: &str) -> Option<String> {
+    analyses["analyses"]
+        .as_array()?
+        .iter()
+        .find(|analysis| analysis["key"] == key)
+        .and_then(|analysis| analysis["date"].as_str())
+        .map(str::to_string)
+}
+
+/// Half-open window check on ISO-8601 strings, which order lexicographically
+/// — a bare date like 2024-05-01 sorts before any datetime on that day.
+fn within(date: &str, from: &str, to: Option<&str>) -> bool {
+    date >= from && to.is_none_or(|to| date < to)
+}
+
+/// Fetches every page of an issue search, capped like the all_pages tool
+/// option and by the server's 10k pagination window.
+async fn fetch_issues(ctx: &ServerContext, query: &[(&str, String)]) -> Result<Vec<Value>> {
+    let cap = ctx.config.max_all_pages_results as usize;
+    let mut issues: Vec<Value> = Vec::new();
+    let mut page: u32 = 1;
+    loop {
+        let mut query = query.to_vec();
+        query.push(("ps", PAGE_SIZE.to_string()));
+        query.push(("p", page.to_string()));
+        let response: Value = super::map_project_not_found(
+            ctx.client.get("/api/issues/search", &query).await,
+            &query[0].1,
+        )?;
+        let total = response["paging"]["total"].as_u64().unwrap_or(0);
+        match response["issues"].as_array() {
+            Some(batch) if !batch.is_empty() => issues.extend(batch.iter().cloned()),
+            _ => break,
+        }
+        if issues.len() as u64 >= total
+            || issues.len() >= cap
+            || page * PAGE_SIZE >= 10_000
+        {
+            break;
+        }
+        page += 1;
+    }
+    issues.truncate(cap);
+    Ok(issues)
+}
+
+/// Trims issues to the fields a diff reader needs.
+fn summaries(issues: &[Value]) -> Vec<Value> {
+    issues
+        .iter()
+        .map(|issue| {
+            json!({
+                "key": issue["key"],
+                "rule": issue["rule"],
+                "severity": issue["severity"],
+                "type": issue["type"],
+                "message": issue["message"],
+                "component": issue["component"],
+                "creation_date": issue["creationDate"],
+                "close_date": issue["closeDate"],
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_check_is_half_open_and_handles_bare_dates() {
+        assert!(within("2024-05-01T10:00:00+0000", "2024-05-01", None));
+        assert!(within(
+            "2024-05-01T10:00:00+0000",
+            "2024-05-01",
+            Some("2024-05-02"),
+        ));
+        assert!(!within(
+            "2024-05-02T00:00:01+0000",
+            "2024-05-01",
+            Some("2024-05-02"),
+        ));
+        assert!(!within("2024-04-30T23:59:59+0000", "2024-05-01", None));
+    }
+
+    #[test]
+    fn looks_analysis_dates_up_by_key() {
+        let analyses = json!({"analyses": [
+            {"key": "AX1", "date": "2024-05-01T08:00:00+0000"},
+            {"key": "AX2", "date": "2024-05-08T08:00:00+0000"},
+        ]});
+        assert_eq!(
+            analysis_date(&analyses, "AX2"),
+            Some("2024-05-08T08:00:00+0000".to_string())
+        );
+        assert_eq!(analysis_date(&analyses, "AX9"), None);
+    }
+}
@@ -0,0 +1,153 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::{IssueStatus, IssueType, Severity, SonarQubeIssuesRequest};
+
+/// Columns of the export, in order. Values are taken from the serialized
+/// issue, so they match what the issues tools return.
+const COLUMNS: &[&str] = &[
+    "key",
+    "rule",
+    "severity",
+    "type",
+    "status",
+    "resolution",
+    "component",
+    "line",
+    "message",
+    "assignee",
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    severities: Option<Vec<Severity>>,
+    types: Option<Vec<IssueType>>,
+    statuses: Option<Vec<IssueStatus>>,
+    /// Relative path under the configured export root; when unset, the CSV
+    /// comes back as text content.
+    #[serde(alias = "outputPath")]
+    output_path: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_export_issues_csv".to_string(),
+        description: "Export all matching issues of a project as CSV, returned as text or \
+                      written to a file under the configured export root."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "severities": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": Severity::VALUES},
+                },
+                "types": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": IssueType::VALUES},
+                },
+                "statuses": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": IssueStatus::VALUES},
+                },
+                "output_path": {
+                    "type": "string",
+                    "description": "Relative file path under the server's --export-root; omit to get the CSV inline",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let request = SonarQubeIssuesRequest::builder(params.project_key)
+        .severities(params.severities)
+        .types(params.types)
+        .statuses(params.statuses)
+        .build();
+    let response = super::map_project_not_found(
+        ctx.client
+            .search_issues_all_pages(&request, ctx.config.max_all_pages_results)
+            .await,
+        &request.project_key,
+    )?;
+
+    let mut csv = String::new();
+    csv.push_str(&csv_row(COLUMNS.iter().map(|c| c.to_string())));
+    for issue in &response.issues {
+        let issue = serde_json::to_value(issue)?;
+        csv.push_str(&csv_row(
+            COLUMNS.iter().map(|column| csv_value(&issue[column])),
+        ));
+    }
+
+    match params.output_path {
+        Some(output_path) => {
+            let path = super::write_export(ctx, &output_path, &csv)?;
+            super::json_result(
+                ctx,
+                &json!({
+                    "path": path,
+                    "rows": response.issues.len(),
+                    "total": response.paging.total,
+                }),
+            )
+        }
+        None => Ok(CallToolResult::text(csv)),
+    }
+}
+
+/// One CSV record: fields quoted where needed, terminated with CRLF per
+/// RFC 4180.
+fn csv_row(fields: impl Iterator<Item = String>) -> String {
+    let mut row = fields
+        .map(|field| csv_escape(&field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// A CSV field for a JSON value: strings verbatim, null empty, everything
+/// else via its JSON form.
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains(['"', ',', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_fields_per_rfc_4180() {
+        let row = csv_row(
+            ["plain", "with,comma", "with \"quotes\"", "multi\nline"]
+                .into_iter()
+                .map(String::from),
+        );
+        assert_eq!(
+            row,
+            "plain,\"with,comma\",\"with \"\"quotes\"\"\",\"multi\nline\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::{IssueType, Severity, SonarQubeIssuesRequest};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    severities: Option<Vec<Severity>>,
+    types: Option<Vec<IssueType>>,
+    /// Also include security hotspots as results.
+    #[serde(alias = "includeHotspots")]
+    include_hotspots: Option<bool>,
+    /// Relative path under the configured export root; when unset, the
+    /// document comes back as text content.
+    #[serde(alias = "outputPath")]
+    output_path: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_export_sarif".to_string(),
+        description: "Export all matching issues of a project (optionally plus security \
+                      hotspots) as a SARIF 2.1.0 document, for ingestion by code scanning \
+                      services and IDEs."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "severities": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": Severity::VALUES},
+                },
+                "types": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": IssueType::VALUES},
+                },
+                "include_hotspots": {
+                    "type": "boolean",
+                    "description": "Also include security hotspots as results",
+                },
+                "output_path": {
+                    "type": "string",
+                    "description": "Relative file path under the server's --export-root; omit to get the document inline",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let request = SonarQubeIssuesRequest::builder(params.project_key)
+        .severities(params.severities)
+        .types(params.types)
+        .build();
+    let response = super::map_project_not_found(
+        ctx.client
+            .search_issues_all_pages(&request, ctx.config.max_all_pages_results)
+            .await,
+        &request.project_key,
+    )?;
+
+    let mut results = Vec::new();
+    for issue in &response.issues {
+        results.push(issue_result(&serde_json::to_value(issue)?));
+    }
+    if params.include_hotspots.unwrap_or(false) {
+        let hotspots: Value = ctx
+            .client
+            .get(
+                "/api/hotspots/search",
+                &[
+                    ("projectKey", request.project_key.clone()),
+                    ("ps", "500".to_string()),
+                ],
+            )
+            .await?;
+        for hotspot in hotspots["hotspots"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+            results.push(hotspot_result(hotspot));
+        }
+    }
+    let sarif = document(&ctx.config.sonarqube_url, results);
+
+    match params.output_path {
+        Some(output_path) => {
+            let contents = serde_json::to_string_pretty(&sarif)?;
+            let path = super::write_export(ctx, &output_path, &contents)?;
+            super::json_result(
+                ctx,
+                &json!({
+                    "path": path,
+                    "results": sarif["runs"][0]["results"].as_array().map(Vec::len),
+                }),
+            )
+        }
+        None => Ok(CallToolResult::text(serde_json::to_string_pretty(&sarif)?)),
+    }
+}
+
+/// Wraps results into a single-run SARIF 2.1.0 log, deriving the rule table
+/// from the rule ids the results reference.
+fn document(base_url: &str, results: Vec<Value>) -> Value {
+    let rules: BTreeSet<&str> = results
+        .iter()
+        .filter_map(|result| result["ruleId"].as_str())
+        .collect();
+    let rules: Vec<Value> = rules.into_iter().map(|id| json!({"id": id})).collect();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "SonarQube",
+                    "informationUri": base_url,
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn issue_result(issue: &Value) -> Value {
+    let mut result = json!({
+        "ruleId": issue["rule"],
+        "level": level(issue["severity"].as_str().unwrap_or_default()),
+        "message": {"text": issue["message"]},
+        "locations": [location(
+            issue["component"].as_str().unwrap_or_default(),
+            issue["line"].as_u64(),
+        )],
+        "partialFingerprints": {"sonarqube/issueKey": issue["key"]},
+    });
+    result["properties"] = json!({
+        "sonarqube/type": issue["type"],
+        "sonarqube/severity": issue["severity"],
+    });
+    result
+}
+
+fn hotspot_result(hotspot: &Value) -> Value {
+    // Hotspots carry a review probability rather than a severity; map it to
+    // the closest SARIF level so scanners triage HIGH ones first.
+    let level = match hotspot["vulnerabilityProbability"].as_str() {
+        Some("HIGH") => "error",
+        Some("MEDIUM") => "warning",
+        _ => "note",
+    };
+    json!({
+        "ruleId": hotspot["ruleKey"],
+        "level": level,
+        "message": {"text": hotspot["message"]},
+        "locations": [location(
+            hotspot["component"].as_str().unwrap_or_default(),
+            hotspot["line"].as_u64(),
+        )],
+        "partialFingerprints": {"sonarqube/hotspotKey": hotspot["key"]},
+        "properties": {
+            "sonarqube/type": "SECURITY_HOTSPOT",
+            "sonarqube/securityCategory": hotspot["securityCategory"],
+        },
+    })
+}
+
+/// Maps SonarQube severities (classic and MQR) onto SARIF's three levels.
+fn level(severity: &str) -> &'static str {
+    match severity {
+        "BLOCKER" | "CRITICAL" | "HIGH" => "error",
+        "MAJOR" | "MEDIUM" => "warning",
+        _ => "note",
+    }
+}
+
+/// A physical location for a component key like "project:src/Main.java".
+/// The region is omitted for file-level findings without a line.
+fn location(component: &str, line: Option<u64>) -> Value {
+    let uri = component
+        .split_once(':')
+        .map(|(_, path)| path)
+        .unwrap_or(component);
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": {"uri": uri},
+        },
+    });
+    if let Some(line) = line {
+        location["physicalLocation"]["region"] = json!({"startLine": line});
+    }
+    location
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_severities_onto_sarif_levels() {
+        assert_eq!(level("BLOCKER"), "error");
+        assert_eq!(level("CRITICAL"), "error");
+        assert_eq!(level("MAJOR"), "warning");
+        assert_eq!(level("MINOR"), "note");
+        assert_eq!(level("INFO"), "note");
+        // MQR severities map too.
+        assert_eq!(level("HIGH"), "error");
+        assert_eq!(level("LOW"), "note");
+    }
+
+    #[test]
+    fn builds_a_single_run_document_with_a_derived_rule_table() {
+        let issue = json!({
+            "key": "AX1", "rule": "java:S2076", "severity": "BLOCKER",
+            "type": "VULNERABILITY", "message": "Fix this",
+            "component": "demo:src/Main.java", "line": 42,
+        });
+        let sarif = document("https://sonar.example.com", vec![issue_result(&issue)]);
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"], json!([{"id": "java:S2076"}]));
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/Main.java"
+        );
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            42
+        );
+        assert_eq!(result["partialFingerprints"]["sonarqube/issueKey"], "AX1");
+    }
+
+    #[test]
+    fn file_level_findings_have_no_region() {
+        let hotspot = json!({
+            "key": "H1", "ruleKey": "java:S4790", "message": "Review this",
+            "component": "demo:src/Hash.java", "vulnerabilityProbability": "MEDIUM",
+        });
+        let result = hotspot_result(&hotspot);
+        assert_eq!(result["level"], "warning");
+        assert!(result["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::resources::report;
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    format: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_generate_report".to_string(),
+        description: "Compose quality gate status, overall and new-code measures and the top \
+                      issues of a project into one rendered report document. Also available \
+                      as the sonarqube://report/{key}.md resource."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "format": {
+                    "type": "string",
+                    "enum": ["markdown", "html"],
+                    "description": "Report format (default markdown)",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let report = super::map_project_not_found(
+        report::gather(ctx, &params.project_key).await,
+        &params.project_key,
+    )?;
+    let rendered = match params.format.as_deref().unwrap_or("markdown") {
+        "markdown" => report::render_markdown(&report),
+        "html" => report::render_html(&report),
+        other => {
+            return Err(Error::InvalidArguments(format!(
+                "unknown format: {other} (expected markdown or html)"
+            )))
+        }
+    };
+    Ok(CallToolResult::text(rendered))
+}
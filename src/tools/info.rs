@@ -0,0 +1,32 @@
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_info".to_string(),
+        description: "Describe this MCP server and the SonarQube instance it is connected to."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    let info = json!({
+        "server": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "sonarqube": {
+            "url": ctx.config.sonarqube_url,
+            "organization": ctx.config.organization,
+        },
+        "tools": super::definitions().iter().map(|t| t.name.clone()).collect::<Vec<_>>(),
+    });
+    super::json_result(ctx, &info)
+}
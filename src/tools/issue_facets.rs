@@ -0,0 +1,86 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Facets the Web API can aggregate issue counts by.
+const VALID_FACETS: &[&str] = &[
+    "severities",
+    "types",
+    "statuses",
+    "resolutions",
+    "rules",
+    "files",
+    "authors",
+    "tags",
+    "languages",
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    facets: Vec<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_issue_facets".to_string(),
+        description: "Aggregate issue counts by the requested facets (severities, rules, \
+                      files, authors, tags, ...) without downloading the issues themselves — \
+                      cheap statistics for large projects."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "facets": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": VALID_FACETS},
+                    "description": "Facets to aggregate by",
+                },
+            },
+            "required": ["project_key", "facets"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    if params.facets.is_empty() {
+        return Err(Error::InvalidArguments(
+            "at least one facet is required".to_string(),
+        ));
+    }
+    for facet in &params.facets {
+        if !VALID_FACETS.contains(&facet.as_str()) {
+            return Err(Error::InvalidArguments(format!(
+                "invalid facet: {facet} (expected one of {})",
+                VALID_FACETS.join(", ")
+            )));
+        }
+    }
+    // ps=1 keeps the payload to the facet buckets plus a single issue stub.
+    let response: Value = super::map_project_not_found(
+        ctx.client
+            .get(
+                "/api/issues/search",
+                &[
+                    ("componentKeys", params.project_key.clone()),
+                    ("facets", params.facets.join(",")),
+                    ("ps", "1".to_string()),
+                ],
+            )
+            .await,
+        &params.project_key,
+    )?;
+    super::json_result(
+        ctx,
+        &json!({
+            "total": response["paging"]["total"],
+            "facets": response["facets"],
+        }),
+    )
+}
@@ -0,0 +1,264 @@
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::{IssueStatus, IssueType, Paging, Severity, SonarQubeIssuesRequest};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    severities: Option<Vec<Severity>>,
+    types: Option<Vec<IssueType>>,
+    statuses: Option<Vec<IssueStatus>>,
+    #[serde(alias = "cleanCodeAttributeCategories")]
+    clean_code_attribute_categories: Option<Vec<String>>,
+    #[serde(alias = "impactSeverities")]
+    impact_severities: Option<Vec<String>>,
+    page: Option<u32>,
+    #[serde(alias = "pageSize")]
+    page_size: Option<u32>,
+    #[serde(alias = "allPages")]
+    all_pages: Option<bool>,
+    /// Opaque continuation token from a previous result's `next_cursor`.
+    cursor: Option<String>,
+    fields: Option<Vec<String>>,
+    summary: Option<bool>,
+    /// Issues included alongside a summary; default 5.
+    top: Option<u32>,
+    format: Option<String>,
+}
+
+/// Fields kept on the issues shown in a summary.
+const SUMMARY_ISSUE_FIELDS: &[&str] = &["key", "rule", "severity", "type", "message", "component"];
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_issues".to_string(),
+        description: "Search issues in a project, optionally filtered by severity, type and status."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "severities": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": Severity::VALUES},
+                    "description": "Classic severities work everywhere; LOW/MEDIUM/HIGH are MQR-only (SonarQube 10.4+)",
+                },
+                "types": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": IssueType::VALUES},
+                },
+                "statuses": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": IssueStatus::VALUES},
+                },
+                "clean_code_attribute_categories": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": ["ADAPTABLE", "CONSISTENT", "INTENTIONAL", "RESPONSIBLE"]},
+                    "description": "Clean Code attribute categories (SonarQube 10.2+)",
+                },
+                "impact_severities": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": ["INFO", "LOW", "MEDIUM", "HIGH", "BLOCKER"]},
+                    "description": "MQR impact severities (SonarQube 10.4+)",
+                },
+                "page": {"type": "integer"},
+                "page_size": {"type": "integer"},
+                "all_pages": {
+                    "type": "boolean",
+                    "description": "Fetch and merge every page (up to the configured cap); page and page_size are ignored",
+                },
+                "cursor": {
+                    "type": "string",
+                    "description": "Opaque next_cursor from a previous result; continues that listing in bounded chunks",
+                },
+                "fields": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Keep only these fields on each issue, e.g. [\"key\", \"severity\", \"message\"]",
+                },
+                "summary": {
+                    "type": "boolean",
+                    "description": "Return per-severity/type/rule counts plus the top issues instead of the full list",
+                },
+                "top": {
+                    "type": "integer",
+                    "description": "Issues included alongside a summary (default 5)",
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["json", "markdown"],
+                    "description": "Output format (default json); markdown renders the listing as a table",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let mut params: Params = super::parse_args(args)?;
+    // A cursor resumes a chunked listing: it carries the pagination state
+    // and overrides page, page_size and all_pages.
+    if let Some(cursor) = &params.cursor {
+        let state = decode_cursor(cursor)?;
+        params.page = state.0;
+        params.page_size = state.1;
+        params.all_pages = Some(false);
+    }
+    // Summary mode answers "how bad is it?" in one bounded response: the
+    // facet counts carry the statistics, a handful of issues the flavour.
+    if params.summary.unwrap_or(false) {
+        let request = SonarQubeIssuesRequest::builder(params.project_key)
+            .severities(params.severities)
+            .types(params.types)
+            .statuses(params.statuses)
+            .clean_code_attribute_categories(params.clean_code_attribute_categories)
+            .impact_severities(params.impact_severities)
+            .facets(vec![
+                "severities".to_string(),
+                "types".to_string(),
+                "rules".to_string(),
+            ])
+            .page_size(params.top.unwrap_or(5).clamp(1, 50))
+            .build();
+        let response = super::map_project_not_found(
+            ctx.client.search_issues(&request).await,
+            &request.project_key,
+        )?;
+        let mut top_issues = serde_json::to_value(&response.issues)?;
+        let fields: Vec<String> = SUMMARY_ISSUE_FIELDS.iter().map(|f| f.to_string()).collect();
+        super::retain_fields(&mut top_issues, &params.fields.unwrap_or(fields));
+        return super::json_result(
+            ctx,
+            &json!({
+                "total": response.paging.total,
+                "facets": response.facets,
+                "top_issues": top_issues,
+            }),
+        );
+    }
+
+    let request = SonarQubeIssuesRequest::builder(params.project_key)
+        .severities(params.severities)
+        .types(params.types)
+        .statuses(params.statuses)
+        .clean_code_attribute_categories(params.clean_code_attribute_categories)
+        .impact_severities(params.impact_severities)
+        .page(params.page)
+        .page_size(params.page_size)
+        .build();
+    let all_pages = params.all_pages.unwrap_or(false);
+    let result = if all_pages {
+        ctx.client
+            .search_issues_all_pages(&request, ctx.config.max_all_pages_results)
+            .await
+    } else {
+        ctx.client.search_issues(&request).await
+    };
+    let response = super::map_project_not_found(result, &request.project_key)?;
+    let mut value = serde_json::to_value(&response)?;
+    if let Some(fields) = &params.fields {
+        super::retain_fields(&mut value["issues"], fields);
+    }
+    if !all_pages {
+        if let Some(cursor) = next_cursor(&response.paging) {
+            value["next_cursor"] = Value::String(cursor);
+        }
+    }
+    super::formatted_result(ctx, &value, params.format.as_deref(), crate::mcp::render::issues)
+}
+
+/// Continuation token for the page after `paging`, or None when the listing
+/// is exhausted (or the server's 10k window is). The token is a base64ed
+/// page/page-size pair — opaque to clients, but stable across calls.
+fn next_cursor(paging: &Paging) -> Option<String> {
+    let served = paging.page_index.checked_mul(paging.page_size)?;
+    if served >= paging.total || served >= 10_000 {
+        return None;
+    }
+    let state = json!({"p": paging.page_index + 1, "ps": paging.page_size});
+    Some(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(state.to_string()))
+}
+
+/// Inverse of [`next_cursor`]; malformed tokens are an argument error.
+fn decode_cursor(cursor: &str) -> Result<(Option<u32>, Option<u32>)> {
+    let invalid = || Error::InvalidArguments("cursor is not a valid continuation token".to_string());
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|_| invalid())?;
+    let state: Value = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+    let page = state["p"].as_u64().ok_or_else(invalid)?;
+    let page_size = state["ps"].as_u64().ok_or_else(invalid)?;
+    Ok((Some(page as u32), Some(page_size as u32)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_camel_case_and_snake_case_arguments() {
+        let camel: Params = super::super::parse_args(json!({
+            "projectKey": "demo",
+            "pageSize": 10,
+        }))
+        .unwrap();
+        assert_eq!(camel.project_key, "demo");
+        assert_eq!(camel.page_size, Some(10));
+
+        let snake: Params = super::super::parse_args(json!({
+            "project_key": "demo",
+            "page_size": 10,
+        }))
+        .unwrap();
+        assert_eq!(snake.project_key, camel.project_key);
+        assert_eq!(snake.page_size, camel.page_size);
+    }
+
+    #[test]
+    fn cursors_round_trip_and_stop_at_the_end() {
+        let paging = Paging {
+            page_index: 2,
+            page_size: 100,
+            total: 450,
+            extra: Default::default(),
+        };
+        let cursor = next_cursor(&paging).expect("more pages remain");
+        assert_eq!(decode_cursor(&cursor).unwrap(), (Some(3), Some(100)));
+
+        let last = Paging {
+            page_index: 5,
+            page_size: 100,
+            total: 450,
+            extra: Default::default(),
+        };
+        assert!(next_cursor(&last).is_none());
+        // The server refuses to page past 10k results.
+        let window = Paging {
+            page_index: 20,
+            page_size: 500,
+            total: 30_000,
+            extra: Default::default(),
+        };
+        assert!(next_cursor(&window).is_none());
+    }
+
+    #[test]
+    fn malformed_cursors_are_an_argument_error() {
+        assert!(matches!(
+            decode_cursor("not base64!"),
+            Err(Error::InvalidArguments(_))
+        ));
+        let not_state = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode("{}");
+        assert!(matches!(
+            decode_cursor(&not_state),
+            Err(Error::InvalidArguments(_))
+        ));
+    }
+}
@@ -0,0 +1,23 @@
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_list_languages".to_string(),
+        description: "List the languages supported by the SonarQube instance, as accepted by \
+                      the languages filter of other tools."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    let response: Value = ctx.client.get("/api/languages/list", &[]).await?;
+    super::json_result(ctx, &response)
+}
@@ -0,0 +1,304 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::Severity;
+
+/// Risk contributed by a failing quality gate; it alone pushes the
+/// assessment to "high".
+const GATE_FAILURE_RISK: f64 = 50.0;
+
+/// Risk per unreviewed security hotspot touched by the change.
+const HOTSPOT_RISK: f64 = 5.0;
+
+/// New-code coverage below this percentage adds one risk point per missing
+/// point of coverage.
+const COVERAGE_TARGET: f64 = 80.0;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    branch: Option<String>,
+    #[serde(alias = "pullRequest")]
+    pull_request: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "assess_merge_risk".to_string(),
+        description: "Compose quality gate status, new issues by severity, security hotspots \
+                      and new-code coverage/duplication for a branch or pull request into one \
+                      risk score with reasons — a single call for the \"should we merge?\" \
+                      picture. Severity weights come from the effective scoring configuration."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "branch": {"type": "string", "description": "Branch name"},
+                "pull_request": {"type": "string", "description": "Pull request id; takes precedence over branch"},
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    if params.branch.is_none() && params.pull_request.is_none() {
+        return Err(Error::InvalidArguments(
+            "give branch or pull_request to assess".to_string(),
+        ));
+    }
+    // (parameter name, value) appended to every query below.
+    let target: (&str, String) = match (&params.pull_request, &params.branch) {
+        (Some(pull_request), _) => ("pullRequest", pull_request.clone()),
+        (None, Some(branch)) => ("branch", branch.clone()),
+        (None, None) => unreachable!("validated above"),
+    };
+    let project = &params.project_key;
+
+    let gate: Value = super::map_project_not_found(
+        ctx.client
+            .get(
+                "/api/qualitygates/project_status",
+                &[("projectKey", project.clone()), target.clone()],
+            )
+            .await,
+        project,
+    )?;
+    let issues: Value = ctx
+        .client
+        .get(
+            "/api/issues/search",
+            &[
+                ("componentKeys", project.clone()),
+                ("inNewCodePeriod", "true".to_string()),
+                ("resolved", "false".to_string()),
+                ("facets", "severities".to_string()),
+                ("ps", "1".to_string()),
+                target.clone(),
+            ],
+        )
+        .await?;
+    let hotspots: Value = ctx
+        .client
+        .get(
+            "/api/hotspots/search",
+            &[
+                ("projectKey", project.clone()),
+                ("status", "TO_REVIEW".to_string()),
+                ("ps", "1".to_string()),
+                target.clone(),
+            ],
+        )
+        .await?;
+    let measures = ctx
+        .client
+        .get::<Value>(
+            "/api/measures/component",
+            &[
+                ("component", project.clone()),
+                (
+                    "metricKeys",
+                    "new_coverage,new_duplicated_lines_density".to_string(),
+                ),
+                target.clone(),
+            ],
+        )
+        .await?;
+
+    let mut score = 0.0;
+    let mut reasons = Vec::new();
+
+    let gate_status = gate["projectStatus"]["status"].as_str().unwrap_or("NONE");
+    if gate_status == "ERROR" {
+        score += GATE_FAILURE_RISK;
+        for condition in failing_conditions(&gate) {
+            reasons.push(format!("quality gate condition failing: {condition}"));
+        }
+        if reasons.is_empty() {
+            reasons.push("quality gate is failing".to_string());
+        }
+    }
+
+    let mut new_issues = Vec::new();
+    for (severity, count) in severity_counts(&issues) {
+        if count == 0 {
+            continue;
+        }
+        score += count as f64 * ctx.scoring.weight(severity);
+        new_issues.push(json!({"severity": severity, "count": count}));
+        reasons.push(format!("{count} new {severity} issue(s)"));
+    }
+
+    let unreviewed_hotspots = hotspots["paging"]["total"].as_u64().unwrap_or(0);
+    if unreviewed_hotspots > 0 {
+        score += unreviewed_hotspots as f64 * HOTSPOT_RISK;
+        reasons.push(format!(
+            "{unreviewed_hotspots} security hotspot(s) to review"
+        ));
+    }
+
+    let new_coverage = measure_value(&measures, "new_coverage");
+    if let Some(coverage) = new_coverage {
+        if coverage < COVERAGE_TARGET {
+            score += COVERAGE_TARGET - coverage;
+            reasons.push(format!(
+                "new code coverage {coverage:.1}% is below {COVERAGE_TARGET:.0}%"
+            ));
+        }
+    }
+    let new_duplication = measure_value(&measures, "new_duplicated_lines_density");
+    if let Some(duplication) = new_duplication {
+        if duplication > 3.0 {
+            score += duplication;
+            reasons.push(format!("{duplication:.1}% duplicated lines on new code"));
+        }
+    }
+
+    super::json_result(
+        ctx,
+        &json!({
+            "project": project,
+            "target": {"parameter": target.0, "value": target.1},
+            "risk_score": (score * 10.0).round() / 10.0,
+            "risk_level": risk_level(score),
+            "quality_gate": gate_status,
+            "new_issues": new_issues,
+            "unreviewed_hotspots": unreviewed_hotspots,
+            "new_coverage": new_coverage,
+            "new_duplicated_lines_density": new_duplication,
+            "reasons": reasons,
+        }),
+    )
+}
+
+/// Buckets of the severities facet, as typed severities with counts.
+fn severity_counts(issues: &Value) -> Vec<(Severity, u64)> {
+    issues["facets"]
+        .as_array()
+        .and_then(|facets| {
+            facets
+                .iter()
+                .find(|facet| facet["property"] == "severities")?["values"]
+                .as_array()
+        })
+        .map(|buckets| {
+            buckets
+                .iter()
+                .filter_map(|bucket| {
+                    let severity =
+                        serde_json::from_value::<Severity>(bucket["val"].clone()).ok()?;
+                    Some((severity, bucket["count"].as_u64()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Human summaries of failing quality gate conditions.
+fn failing_conditions(gate: &Value) -> Vec<String> {
+    gate["projectStatus"]["conditions"]
+        .as_array()
+        .map(|conditions| {
+            conditions
+                .iter()
+                .filter(|condition| condition["status"] == "ERROR")
+                .map(|condition| {
+                    format!(
+                        "{} is {} (threshold {})",
+                        condition["metricKey"].as_str().unwrap_or("?"),
+                        condition["actualValue"].as_str().unwrap_or("?"),
+                        condition["errorThreshold"].as_str().unwrap_or("?"),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn measure_value(measures: &Value, metric: &str) -> Option<f64> {
+    measures["component"]["measures"]
+        .as_array()?
+        .iter()
+        .find(|measure| measure["metric"] == metric)?
+        .pointer("/period/value")
+        .or_else(|| {
+            measures["component"]["measures"]
+                .as_array()?
+                .iter()
+                .find(|measure| measure["metric"] == metric)?
+                .get("value")
+        })?
+        .as_str()?
+        .parse()
+        .ok()
+}
+
+fn risk_level(score: f64) -> &'static str {
+    if score >= 50.0 {
+        "high"
+    } else if score >= 15.0 {
+        "moderate"
+    } else {
+        "low"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_severity_facet_buckets_as_typed_severities() {
+        let issues = json!({
+            "facets": [{
+                "property": "severities",
+                "values": [
+                    {"val": "BLOCKER", "count": 2},
+                    {"val": "INFO", "count": 0},
+                ],
+            }],
+        });
+        let counts = severity_counts(&issues);
+        assert_eq!(counts, vec![(Severity::Blocker, 2), (Severity::Info, 0)]);
+        assert!(severity_counts(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn summarizes_failing_gate_conditions() {
+        let gate = json!({"projectStatus": {"conditions": [
+            {"metricKey": "new_coverage", "status": "ERROR",
+             "actualValue": "62.0", "errorThreshold": "80"},
+            {"metricKey": "new_bugs", "status": "OK",
+             "actualValue": "0", "errorThreshold": "0"},
+        ]}});
+        let reasons = failing_conditions(&gate);
+        assert_eq!(reasons, vec!["new_coverage is 62.0 (threshold 80)"]);
+    }
+
+    #[test]
+    fn reads_new_code_measures_from_the_period_block() {
+        let measures = json!({"component": {"measures": [
+            {"metric": "new_coverage", "period": {"value": "72.5"}},
+            {"metric": "new_duplicated_lines_density", "value": "1.2"},
+        ]}});
+        assert_eq!(measure_value(&measures, "new_coverage"), Some(72.5));
+        assert_eq!(
+            measure_value(&measures, "new_duplicated_lines_density"),
+            Some(1.2)
+        );
+        assert_eq!(measure_value(&measures, "missing"), None);
+    }
+
+    #[test]
+    fn risk_levels_follow_the_score() {
+        assert_eq!(risk_level(0.0), "low");
+        assert_eq!(risk_level(20.0), "moderate");
+        assert_eq!(risk_level(GATE_FAILURE_RISK), "high");
+    }
+}
@@ -0,0 +1,241 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Glyphs used for the ASCII sparkline, lowest to highest.
+const SPARKS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Metric to analyze, e.g. coverage.
+    metric: String,
+    /// Only consider history from this date on, e.g. 2024-01-01.
+    from: Option<String>,
+    to: Option<String>,
+    /// Include an ASCII sparkline ("ascii", default) or a Mermaid chart
+    /// ("mermaid"); "none" omits the visualization.
+    sparkline: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_analyze_metric_trend".to_string(),
+        description: "Analyze a metric's history: period-over-period deltas, percent changes \
+                      and a linear trend, with an optional sparkline for chat rendering."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "metric": {"type": "string", "description": "Metric key, e.g. coverage"},
+                "from": {"type": "string", "description": "Start date, e.g. 2024-01-01"},
+                "to": {"type": "string", "description": "End date"},
+                "sparkline": {
+                    "type": "string",
+                    "enum": ["ascii", "mermaid", "none"],
+                    "description": "Visualization to include (default ascii)",
+                },
+            },
+            "required": ["project_key", "metric"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let mut query = vec![
+        ("component", params.project_key.clone()),
+        ("metrics", params.metric.clone()),
+        ("ps", "1000".to_string()),
+    ];
+    if let Some(from) = &params.from {
+        query.push(("from", from.clone()));
+    }
+    if let Some(to) = &params.to {
+        query.push(("to", to.clone()));
+    }
+    let history: Value = super::map_project_not_found(
+        ctx.client.get("/api/measures/search_history", &query).await,
+        &params.project_key,
+    )?;
+    let points = series(&history, &params.metric);
+    if points.is_empty() {
+        return Err(Error::InvalidArguments(format!(
+            "no history for metric {} on {}",
+            params.metric, params.project_key
+        )));
+    }
+
+    let values: Vec<f64> = points.iter().map(|(_, value)| *value).collect();
+    let steps = deltas(&points);
+    let slope = slope_per_step(&values);
+    let mut result = json!({
+        "project": params.project_key,
+        "metric": params.metric,
+        "points": points.len(),
+        "first": {"date": points[0].0, "value": points[0].1},
+        "last": {"date": points[points.len() - 1].0, "value": points[points.len() - 1].1},
+        "total_change": values[values.len() - 1] - values[0],
+        "trend_slope_per_analysis": slope,
+        "trend": if slope > 1e-9 { "rising" } else if slope < -1e-9 { "falling" } else { "flat" },
+        "steps": steps,
+    });
+    match params.sparkline.as_deref().unwrap_or("ascii") {
+        "ascii" => result["sparkline"] = Value::String(sparkline(&values)),
+        "mermaid" => result["mermaid"] = Value::String(mermaid(&params.metric, &points)),
+        "none" => {}
+        other => {
+            return Err(Error::InvalidArguments(format!(
+                "unknown sparkline {other} (expected ascii, mermaid or none)"
+            )))
+        }
+    }
+    super::json_result(ctx, &result)
+}
+
+/// Extracts `(date, value)` points for the metric, oldest first (the order
+/// search_history returns them).
+fn series(history: &Value, metric: &str) -> Vec<(String, f64)> {
+    history["measures"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|measure| measure["metric"] == metric)
+        .flat_map(|measure| measure["history"].as_array().into_iter().flatten())
+        .filter_map(|point| {
+            Some((
+                point["date"].as_str()?.to_string(),
+                point["value"].as_str()?.parse::<f64>().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Period-over-period changes between consecutive analyses.
+fn deltas(points: &[(String, f64)]) -> Vec<Value> {
+    points
+        .windows(2)
+        .map(|pair| {
+            let delta = pair[1].1 - pair[0].1;
+            let mut step = json!({
+                "date": pair[1].0,
+                "value": pair[1].1,
+                "delta": delta,
+            });
+            if pair[0].1 != 0.0 {
+                step["percent_change"] = json!(delta / pair[0].1 * 100.0);
+            }
+            step
+        })
+        .collect()
+}
+
+/// Least-squares slope over analysis index — positive means the metric is
+/// rising by that much per analysis on average.
+fn slope_per_step(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (index, value) in values.iter().enumerate() {
+        let dx = index as f64 - mean_x;
+        numerator += dx * (value - mean_y);
+        denominator += dx * dx;
+    }
+    numerator / denominator
+}
+
+/// Renders values as a block-glyph sparkline, scaled to their own range.
+fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values
+        .iter()
+        .map(|value| {
+            let position = if range == 0.0 {
+                0.0
+            } else {
+                (value - min) / range
+            };
+            let index = (position * (SPARKS.len() - 1) as f64).round() as usize;
+            SPARKS[index]
+        })
+        .collect()
+}
+
+/// Renders the series as a Mermaid xychart block.
+fn mermaid(metric: &str, points: &[(String, f64)]) -> String {
+    let dates: Vec<String> = points
+        .iter()
+        .map(|(date, _)| format!("\"{}\"", date.split('T').next().unwrap_or(date)))
+        .collect();
+    let values: Vec<String> = points.iter().map(|(_, value)| value.to_string()).collect();
+    format!(
+        "xychart-beta\n    title \"{metric}\"\n    x-axis [{}]\n    line [{}]\n",
+        dates.join(", "),
+        values.join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<(String, f64)> {
+        vec![
+            ("2024-05-01".to_string(), 70.0),
+            ("2024-05-08".to_string(), 72.0),
+            ("2024-05-15".to_string(), 71.0),
+            ("2024-05-22".to_string(), 76.0),
+        ]
+    }
+
+    #[test]
+    fn computes_deltas_and_percent_changes() {
+        let steps = deltas(&points());
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0]["delta"], json!(2.0));
+        assert!((steps[0]["percent_change"].as_f64().unwrap() - 2.857).abs() < 0.01);
+        assert_eq!(steps[1]["delta"], json!(-1.0));
+    }
+
+    #[test]
+    fn fits_a_linear_trend() {
+        assert!(slope_per_step(&[70.0, 72.0, 71.0, 76.0]) > 0.0);
+        assert!(slope_per_step(&[9.0, 7.0, 5.0, 3.0]) < 0.0);
+        assert_eq!(slope_per_step(&[5.0]), 0.0);
+        // A perfect line recovers its slope exactly.
+        assert!((slope_per_step(&[1.0, 3.0, 5.0, 7.0]) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sparkline_scales_to_the_value_range() {
+        assert_eq!(sparkline(&[0.0, 3.0, 7.0]), "▁▄█");
+        assert_eq!(sparkline(&[5.0, 5.0]), "▁▁");
+    }
+
+    #[test]
+    fn extracts_the_series_for_the_requested_metric() {
+        let history = json!({"measures": [
+            {"metric": "coverage", "history": [
+                {"date": "2024-05-01T08:00:00+0000", "value": "70.0"},
+                {"date": "2024-05-08T08:00:00+0000", "value": "72.0"},
+                {"date": "2024-05-15T08:00:00+0000"},
+            ]},
+            {"metric": "bugs", "history": [{"date": "2024-05-01", "value": "9"}]},
+        ]});
+        let series = series(&history, "coverage");
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[1].1, 72.0);
+    }
+}
@@ -0,0 +1,168 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::QualityGateCondition;
+
+/// Metrics fetched when the caller does not ask for specific ones.
+const DEFAULT_METRICS: &[&str] = &[
+    "ncloc",
+    "bugs",
+    "vulnerabilities",
+    "code_smells",
+    "coverage",
+    "duplicated_lines_density",
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    #[serde(alias = "metricKeys")]
+    metric_keys: Option<Vec<String>>,
+    cache: Option<bool>,
+    fields: Option<Vec<String>>,
+    format: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_metrics".to_string(),
+        description: "Fetch measures for a project, defaulting to a standard set of metrics."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "metric_keys": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Metric keys, e.g. ncloc, coverage, bugs",
+                },
+                "cache": {"type": "boolean", "description": "Set false to bypass the response cache"},
+                "fields": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Keep only these fields on each measure, e.g. [\"metric\", \"value\"]",
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["json", "markdown"],
+                    "description": "Output format (default json); markdown renders the measures as a table",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let metric_keys = params
+        .metric_keys
+        .unwrap_or_else(|| DEFAULT_METRICS.iter().map(|m| m.to_string()).collect());
+    let response = super::map_project_not_found(
+        ctx.client
+            .get_measures_with_cache(&params.project_key, &metric_keys, params.cache.unwrap_or(true))
+            .await,
+        &params.project_key,
+    )?;
+
+    // Gate conditions double as per-metric thresholds; merging them here
+    // saves clients a second call and a join. A missing gate (e.g. never
+    // analyzed) just means no annotations.
+    let conditions = match ctx.client.quality_gate_status(&params.project_key).await {
+        Ok(gate) => gate.project_status.conditions,
+        Err(_) => Vec::new(),
+    };
+
+    let mut measures = Vec::new();
+    for measure in &response.component.measures {
+        let mut entry = serde_json::to_value(measure)?;
+        if let Some(annotation) = annotate(&measure.metric, measure.value.as_deref(), &conditions) {
+            entry["quality_gate"] = annotation;
+        }
+        measures.push(entry);
+    }
+    let mut measures = Value::Array(measures);
+    if let Some(fields) = &params.fields {
+        super::retain_fields(&mut measures, fields);
+    }
+    super::formatted_result(
+        ctx,
+        &json!({
+            "component": response.component.key,
+            "measures": measures,
+        }),
+        params.format.as_deref(),
+        crate::mcp::render::measures,
+    )
+}
+
+/// Builds the gate annotation for one metric: its threshold, comparator,
+/// condition status, and how far the actual value sits from the threshold.
+fn annotate(
+    metric: &str,
+    value: Option<&str>,
+    conditions: &[QualityGateCondition],
+) -> Option<Value> {
+    let condition = conditions.iter().find(|c| {
+        // Gates commonly constrain the new-code variant of a metric.
+        c.metric_key == metric || c.metric_key == format!("new_{metric}")
+    })?;
+    let mut annotation = json!({
+        "metric": condition.metric_key,
+        "comparator": condition.comparator,
+        "threshold": condition.error_threshold,
+        "status": condition.status,
+    });
+    if let (Some(actual), Some(threshold)) = (
+        value.and_then(|v| v.parse::<f64>().ok()),
+        condition
+            .error_threshold
+            .as_deref()
+            .and_then(|t| t.parse::<f64>().ok()),
+    ) {
+        annotation["margin"] = json!(actual - threshold);
+    }
+    Some(annotation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotates_metrics_with_matching_gate_conditions() {
+        let conditions = vec![QualityGateCondition {
+            status: "ERROR".to_string(),
+            metric_key: "coverage".to_string(),
+            comparator: "LT".to_string(),
+            error_threshold: Some("80".to_string()),
+            actual_value: Some("73.5".to_string()),
+            extra: Default::default(),
+        }];
+        let annotation = annotate("coverage", Some("73.5"), &conditions).unwrap();
+        assert_eq!(annotation["threshold"], "80");
+        assert_eq!(annotation["status"], "ERROR");
+        assert_eq!(annotation["margin"], json!(-6.5));
+        assert!(annotate("ncloc", Some("1000"), &conditions).is_none());
+    }
+
+    #[test]
+    fn falls_back_to_the_new_code_variant() {
+        let conditions = vec![QualityGateCondition {
+            status: "OK".to_string(),
+            metric_key: "new_coverage".to_string(),
+            comparator: "LT".to_string(),
+            error_threshold: Some("80".to_string()),
+            actual_value: Some("91".to_string()),
+            extra: Default::default(),
+        }];
+        let annotation = annotate("coverage", None, &conditions).unwrap();
+        assert_eq!(annotation["metric"], "new_coverage");
+        assert!(annotation.get("margin").is_none());
+    }
+}
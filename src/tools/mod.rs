@@ -0,0 +1,555 @@
+pub mod accepted_debt;
+pub mod analysis;
+pub mod apply_quality_gate;
+pub mod assignee_report;
+pub mod badges;
+pub mod branches;
+pub mod compare_projects;
+pub mod compare_quality_profiles;
+pub mod debt_breakdown;
+pub mod describe_tool;
+pub mod diff_issues;
+pub mod export_issues_csv;
+pub mod export_sarif;
+pub mod generate_report;
+pub mod info;
+pub mod issue_facets;
+pub mod issues;
+pub mod languages;
+pub mod merge_risk;
+pub mod metric_trend;
+pub mod metrics;
+pub mod new_code_periods;
+pub mod projects;
+pub mod quality_gate_history;
+pub mod quality_gates;
+pub mod search_issues;
+pub mod search_issues_by_text;
+pub mod security_report;
+pub mod server_stats;
+pub mod settings;
+pub mod severity_overrides;
+pub mod show_effective_scoring;
+pub mod stale_issues;
+pub mod support_bundle;
+pub mod system;
+pub mod top_files;
+pub mod triage_board;
+pub mod validate_auth;
+pub mod verify_release;
+pub mod watchlist;
+pub mod whoami;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// All tools advertised in `tools/list`, in a stable order. Every tool
+/// additionally accepts an `instance` argument when named instances are
+/// configured; the property is added here rather than in each definition.
+pub fn definitions() -> Vec<ToolDefinition> {
+    let mut definitions = vec![
+        info::definition(),
+        projects::definition(),
+        issues::definition(),
+        metrics::definition(),
+        quality_gates::definition(),
+        branches::definition(),
+        analysis::definition(),
+        triage_board::definition(),
+        new_code_periods::get_definition(),
+        new_code_periods::set_definition(),
+        accepted_debt::definition(),
+        languages::definition(),
+        severity_overrides::definition(),
+        badges::definition(),
+        support_bundle::definition(),
+        settings::definition(),
+        system::status_definition(),
+        system::health_definition(),
+        server_stats::definition(),
+        validate_auth::definition(),
+        whoami::definition(),
+        watchlist::definition(),
+        issue_facets::definition(),
+        search_issues::definition(),
+        search_issues_by_text::definition(),
+        security_report::definition(),
+        describe_tool::definition(),
+        show_effective_scoring::definition(),
+        compare_quality_profiles::definition(),
+        apply_quality_gate::definition(),
+        merge_risk::definition(),
+        verify_release::definition(),
+        export_issues_csv::definition(),
+        export_sarif::definition(),
+        generate_report::definition(),
+        diff_issues::definition(),
+        compare_projects::definition(),
+        quality_gate_history::definition(),
+        metric_trend::definition(),
+        top_files::definition(),
+        debt_breakdown::definition(),
+        assignee_report::definition(),
+        stale_issues::definition(),
+    ];
+    for definition in &mut definitions {
+        if let Some(properties) = definition
+            .input_schema
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+        {
+            properties.insert(
+                "instance".to_string(),
+                serde_json::json!({
+                    "type": "string",
+                    "description": "Named SonarQube instance from --instances-config (default: --default-instance, else the primary configuration)",
+                }),
+            );
+            properties.insert(
+                "credentials".to_string(),
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "url": {"type": "string"},
+                        "token": {"type": "string"},
+                        "organization": {"type": "string"},
+                    },
+                    "description": "Ad-hoc SonarQube credentials for this call only (requires --allow-credential-override)",
+                }),
+            );
+        }
+    }
+    definitions
+}
+
+/// Routes a `tools/call` to its handler, first resolving the `instance`
+/// argument (or the configured default) to the matching named context so
+/// one process can serve several SonarQube servers.
+pub async fn dispatch(
+    ctx: &ServerContext,
+    name: &str,
+    args: Value,
+    progress_token: Option<Value>,
+) -> Result<CallToolResult> {
+    let audit_args = ctx.audit.as_ref().map(|_| crate::audit::sanitize(&args));
+    let started = std::time::Instant::now();
+    // Tenant-scoped contexts carry a project allowlist; refusals are
+    // audited like any other failed call.
+    let policy = ctx
+        .tenant
+        .as_ref()
+        .map_or(Ok(()), |tenant| tenant.check_project_args(&args));
+    let result = match policy {
+        Ok(()) => route(ctx, name, args, progress_token).await,
+        Err(err) => Err(err),
+    };
+    if let Some(audit) = &ctx.audit {
+        audit.record(
+            name,
+            audit_args.unwrap_or(Value::Null),
+            ctx.client.impersonated_user(),
+            started.elapsed().as_millis(),
+            result.as_ref().err().map(|err| err.to_string()),
+        );
+    }
+    result
+}
+
+async fn route(
+    ctx: &ServerContext,
+    name: &str,
+    mut args: Value,
+    progress_token: Option<Value>,
+) -> Result<CallToolResult> {
+    let instance = match args.as_object_mut().and_then(|map| map.remove("instance")) {
+        Some(Value::String(instance)) => Some(instance),
+        Some(Value::Null) | None => None,
+        Some(other) => {
+            return Err(Error::InvalidArguments(format!(
+                "instance must be a string, got {other}"
+            )))
+        }
+    };
+    let instance = instance.or_else(|| ctx.config.default_instance.clone());
+    let credentials = credential_override(ctx, &mut args)?;
+    if let Some(instance) = instance {
+        let registry = ctx.instances.as_ref().ok_or_else(|| {
+            Error::InvalidArguments(
+                "no named instances configured; start with --instances-config".to_string(),
+            )
+        })?;
+        let resolved = registry.resolve(ctx, &instance)?;
+        if let Some(credentials) = credentials {
+            let transient = resolved.with_session_credentials(&credentials)?;
+            return dispatch_tool(&transient, name, args, progress_token).await;
+        }
+        return dispatch_tool(&resolved, name, args, progress_token).await;
+    }
+    if let Some(credentials) = credentials {
+        let transient = ctx.with_session_credentials(&credentials)?;
+        return dispatch_tool(&transient, name, args, progress_token).await;
+    }
+    dispatch_tool(ctx, name, args, progress_token).await
+}
+
+/// Extracts a per-call `credentials` argument, if any. The override is
+/// gated behind --allow-credential-override; the transient client it
+/// produces lives for this one call and shares nothing with the primary
+/// client's cache.
+fn credential_override(
+    ctx: &ServerContext,
+    args: &mut Value,
+) -> Result<Option<crate::server_context::SessionCredentials>> {
+    let raw = match args.as_object_mut().and_then(|map| map.remove("credentials")) {
+        Some(Value::Null) | None => return Ok(None),
+        Some(raw) => raw,
+    };
+    if !ctx.config.allow_credential_override {
+        return Err(Error::InvalidArguments(
+            "per-call credential override is disabled; restart the server with --allow-credential-override to enable it".to_string(),
+        ));
+    }
+    let credentials: crate::server_context::SessionCredentials = serde_json::from_value(raw)
+        .map_err(|err| Error::InvalidArguments(format!("invalid credentials: {err}")))?;
+    if credentials.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(credentials))
+}
+
+async fn dispatch_tool(
+    ctx: &ServerContext,
+    name: &str,
+    args: Value,
+    progress_token: Option<Value>,
+) -> Result<CallToolResult> {
+    match name {
+        "sonarqube_get_info" => info::run(ctx, args).await,
+        "sonarqube_list_projects" => projects::run(ctx, args).await,
+        "sonarqube_get_issues" => issues::run(ctx, args).await,
+        "sonarqube_get_metrics" => metrics::run(ctx, args).await,
+        "sonarqube_get_quality_gate_status" => quality_gates::run(ctx, args).await,
+        "sonarqube_list_branches" => branches::run(ctx, args).await,
+        "sonarqube_wait_for_analysis" => analysis::run(ctx, args, progress_token).await,
+        "sonarqube_get_triage_board" => triage_board::run(ctx, args).await,
+        "sonarqube_get_new_code_period" => new_code_periods::get(ctx, args).await,
+        "sonarqube_set_new_code_period" => new_code_periods::set(ctx, args).await,
+        "sonarqube_get_accepted_issues_report" => accepted_debt::run(ctx, args).await,
+        "sonarqube_list_languages" => languages::run(ctx, args).await,
+        "sonarqube_find_severity_overrides" => severity_overrides::run(ctx, args).await,
+        "sonarqube_get_project_badge" => badges::run(ctx, args).await,
+        "generate_support_bundle" => support_bundle::run(ctx, args).await,
+        "sonarqube_set_setting" => settings::run(ctx, args).await,
+        "sonarqube_system_status" => system::status(ctx, args).await,
+        "sonarqube_system_health" => system::health(ctx, args).await,
+        "get_server_stats" => server_stats::run(ctx, args).await,
+        "sonarqube_validate_auth" => validate_auth::run(ctx, args).await,
+        "sonarqube_whoami" => whoami::run(ctx, args).await,
+        "sonarqube_get_watchlist" => watchlist::run(ctx, args).await,
+        "sonarqube_get_issue_facets" => issue_facets::run(ctx, args).await,
+        "sonarqube_search_issues" => search_issues::run(ctx, args).await,
+        "search_issues_by_text" => search_issues_by_text::run(ctx, args).await,
+        "sonarqube_get_security_report" => security_report::run(ctx, args).await,
+        "describe_tool" => describe_tool::run(ctx, args).await,
+        "show_effective_scoring" => show_effective_scoring::run(ctx, args).await,
+        "sonarqube_compare_quality_profiles" => compare_quality_profiles::run(ctx, args).await,
+        "sonarqube_apply_quality_gate" => apply_quality_gate::run(ctx, args).await,
+        "assess_merge_risk" => merge_risk::run(ctx, args).await,
+        "verify_release" => verify_release::run(ctx, args).await,
+        "sonarqube_export_issues_csv" => export_issues_csv::run(ctx, args).await,
+        "sonarqube_export_sarif" => export_sarif::run(ctx, args).await,
+        "sonarqube_generate_report" => generate_report::run(ctx, args).await,
+        "sonarqube_diff_issues" => diff_issues::run(ctx, args).await,
+        "sonarqube_compare_projects" => compare_projects::run(ctx, args).await,
+        "sonarqube_quality_gate_history" => quality_gate_history::run(ctx, args).await,
+        "sonarqube_analyze_metric_trend" => metric_trend::run(ctx, args).await,
+        "sonarqube_top_files_by_metric" => top_files::run(ctx, args).await,
+        "sonarqube_debt_breakdown" => debt_breakdown::run(ctx, args).await,
+        "sonarqube_assignee_report" => assignee_report::run(ctx, args).await,
+        "sonarqube_stale_issues" => stale_issues::run(ctx, args).await,
+        other => Err(Error::UnknownTool(other.to_string())),
+    }
+}
+
+/// Deserializes tool arguments, mapping failures to `InvalidArguments`.
+pub(crate) fn parse_args<T: DeserializeOwned>(args: Value) -> Result<T> {
+    serde_json::from_value(args).map_err(|err| Error::InvalidArguments(err.to_string()))
+}
+
+/// Renders a serializable value as pretty-printed JSON text content. All tool
+/// results funnel through here so output policies like code redaction apply
+/// uniformly.
+pub(crate) fn json_result<T: Serialize>(ctx: &ServerContext, value: &T) -> Result<CallToolResult> {
+    let mut value = serde_json::to_value(value)?;
+    apply_output_policies(ctx, &mut value);
+    Ok(CallToolResult::text(serde_json::to_string_pretty(&value)?))
+}
+
+/// Like [`json_result`], but honouring a `format` argument: the default
+/// `json`, or `markdown` rendered through the given renderer. Output
+/// policies apply to both formats.
+pub(crate) fn formatted_result<T: Serialize>(
+    ctx: &ServerContext,
+    value: &T,
+    format: Option<&str>,
+    render: fn(&Value) -> String,
+) -> Result<CallToolResult> {
+    match format.unwrap_or("json") {
+        "json" => json_result(ctx, value),
+        "markdown" => {
+            let mut value = serde_json::to_value(value)?;
+            apply_output_policies(ctx, &mut value);
+            Ok(CallToolResult::text(render(&value)))
+        }
+        other => Err(Error::InvalidArguments(format!(
+            "unknown format: {other} (expected json or markdown)"
+        ))),
+    }
+}
+
+fn apply_output_policies(ctx: &ServerContext, value: &mut Value) {
+    if ctx.config.redact_code {
+        crate::redaction::redact_code(value);
+    }
+    if !ctx.severity_map.is_empty() {
+        crate::severity_map::annotate(value, &ctx.severity_map);
+    }
+}
+
+/// Gate for tools that write to SonarQube.
+pub(crate) fn ensure_admin_allowed(ctx: &ServerContext) -> Result<()> {
+    if ctx.config.allow_admin_operations {
+        Ok(())
+    } else {
+        Err(Error::AdminOperationsDisabled)
+    }
+}
+
+/// Client-side field projection: prunes each object in `items` to the
+/// requested fields. Full SonarQube objects carry dozens of fields a model
+/// rarely needs; trimming them here shrinks responses without another
+/// server round trip. Does nothing when `items` is not an array.
+pub(crate) fn retain_fields(items: &mut Value, fields: &[String]) {
+    let Some(items) = items.as_array_mut() else {
+        return;
+    };
+    for item in items {
+        if let Some(object) = item.as_object_mut() {
+            object.retain(|key, _| fields.iter().any(|field| field == key));
+        }
+    }
+}
+
+/// Days between an ISO date (or datetime) and now, from its date prefix.
+pub(crate) fn age_in_days(date: &str) -> Option<i64> {
+    let today = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs() as i64
+        / 86_400;
+    Some(today - days_from_civil(date)?)
+}
+
+/// Days since the Unix epoch for a YYYY-MM-DD prefix (Gregorian calendar).
+fn days_from_civil(date: &str) -> Option<i64> {
+    let year: i64 = date.get(0..4)?.parse().ok()?;
+    let month: i64 = date.get(5..7)?.parse().ok()?;
+    let day: i64 = date.get(8..10)?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+/// Writes an export file under the configured export root and returns its
+/// full path. File output must be enabled explicitly with --export-root, and
+/// the relative path must stay inside the root, so a client cannot direct
+/// the server to write anywhere else on disk.
+pub(crate) fn write_export(
+    ctx: &ServerContext,
+    output_path: &str,
+    contents: &str,
+) -> Result<std::path::PathBuf> {
+    use std::path::{Component, Path};
+
+    let Some(root) = &ctx.config.export_root else {
+        return Err(Error::InvalidArguments(
+            "output_path requires the server to be started with --export-root".to_string(),
+        ));
+    };
+    let relative = Path::new(output_path);
+    let escapes_root = relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+    if escapes_root {
+        return Err(Error::InvalidArguments(
+            "output_path must be a relative path without .. components".to_string(),
+        ));
+    }
+    let path = root.join(relative);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Maps a 404 from a project-scoped endpoint to `ProjectNotFound`, so tools
+/// return a clear error instead of a raw HTTP failure. Handlers call their
+/// target endpoint directly and translate the failure here, instead of
+/// paying for an existence pre-check round trip first.
+pub(crate) fn map_project_not_found<T>(result: Result<T>, project_key: &str) -> Result<T> {
+    match result {
+        Err(Error::Api { status: 404, .. }) => {
+            Err(Error::ProjectNotFound(project_key.to_string()))
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use serde_json::json;
+
+    use super::*;
+    use crate::config::Config;
+
+    fn context(extra_args: &[&str]) -> ServerContext {
+        let mut args = vec![
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "https://sonar.example.com",
+            "--sonarqube-token",
+            "token",
+        ];
+        args.extend_from_slice(extra_args);
+        ServerContext::new(Config::parse_from(args)).expect("context")
+    }
+
+    #[test]
+    fn every_tool_advertises_the_instance_argument() {
+        for definition in definitions() {
+            let instance = &definition.input_schema["properties"]["instance"];
+            assert_eq!(instance["type"], "string", "{}", definition.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn instance_selection_requires_a_configured_registry() {
+        let ctx = context(&[]);
+        let err = dispatch(
+            &ctx,
+            "sonarqube_get_info",
+            json!({"instance": "prod"}),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments(_)));
+        assert!(err.to_string().contains("--instances-config"));
+
+        let err = dispatch(&ctx, "sonarqube_get_info", json!({"instance": 3}), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments(_)));
+    }
+
+    #[tokio::test]
+    async fn credential_overrides_are_gated_behind_the_config_flag() {
+        let ctx = context(&[]);
+        let err = dispatch(
+            &ctx,
+            "sonarqube_get_info",
+            json!({"credentials": {"url": "http://elsewhere:9000", "token": "t"}}),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments(_)));
+        assert!(err.to_string().contains("--allow-credential-override"));
+
+        // With the flag on, malformed credentials are still refused.
+        let ctx = context(&["--allow-credential-override"]);
+        let err = dispatch(
+            &ctx,
+            "sonarqube_get_info",
+            json!({"credentials": {"url": 42}}),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidArguments(_)));
+    }
+
+    #[test]
+    fn exports_require_an_export_root_and_stay_inside_it() {
+        let without_root = context(&[]);
+        assert!(matches!(
+            write_export(&without_root, "issues.csv", ""),
+            Err(Error::InvalidArguments(_))
+        ));
+
+        let root = std::env::temp_dir().join(format!("export-root-{}", std::process::id()));
+        let root_flag = root.to_str().unwrap();
+        let with_root = context(&["--export-root", root_flag]);
+        assert!(matches!(
+            write_export(&with_root, "../escape.csv", ""),
+            Err(Error::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            write_export(&with_root, "/etc/passwd", ""),
+            Err(Error::InvalidArguments(_))
+        ));
+
+        let path = write_export(&with_root, "demo/issues.csv", "key\r\n").unwrap();
+        assert_eq!(path, root.join("demo/issues.csv"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "key\r\n");
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn converts_civil_dates_to_epoch_days() {
+        assert_eq!(days_from_civil("1970-01-01"), Some(0));
+        assert_eq!(days_from_civil("1970-01-02"), Some(1));
+        assert_eq!(days_from_civil("2000-03-01"), Some(11_017));
+        // Datetimes work through their date prefix; garbage does not.
+        assert_eq!(
+            days_from_civil("2024-05-01T10:00:00+0000"),
+            days_from_civil("2024-05-01")
+        );
+        assert_eq!(days_from_civil("not a date"), None);
+    }
+
+    #[test]
+    fn retain_fields_prunes_each_object_to_the_projection() {
+        let mut items = json!([
+            {"key": "a", "severity": "MAJOR", "flows": [1, 2, 3]},
+            {"key": "b", "message": "m"},
+            "not an object",
+        ]);
+        retain_fields(&mut items, &["key".to_string(), "severity".to_string()]);
+        assert_eq!(
+            items,
+            json!([
+                {"key": "a", "severity": "MAJOR"},
+                {"key": "b"},
+                "not an object",
+            ])
+        );
+
+        let mut not_an_array = json!({"key": "a"});
+        retain_fields(&mut not_an_array, &["key".to_string()]);
+        assert_eq!(not_an_array, json!({"key": "a"}));
+    }
+}
@@ -0,0 +1,134 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// New code definition types accepted by `/api/new_code_periods/set`.
+const VALID_TYPES: &[&str] = &[
+    "PREVIOUS_VERSION",
+    "NUMBER_OF_DAYS",
+    "SPECIFIC_ANALYSIS",
+    "REFERENCE_BRANCH",
+];
+
+#[derive(Debug, Deserialize)]
+struct GetParams {
+    #[serde(alias = "projectKey")]
+    project_key: Option<String>,
+    branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetParams {
+    #[serde(alias = "projectKey")]
+    project_key: Option<String>,
+    branch: Option<String>,
+    #[serde(rename = "type")]
+    period_type: String,
+    value: Option<String>,
+}
+
+pub fn get_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_new_code_period".to_string(),
+        description: "Show the new code definition. Without arguments, returns the global \
+                      default; with a project key, lists the definitions of all its branches; \
+                      with a branch, shows that branch's effective definition."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string"},
+                "branch": {"type": "string"},
+            },
+        }),
+    }
+}
+
+pub fn set_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_set_new_code_period".to_string(),
+        description: "Set the new code definition globally, for a project, or for a branch. \
+                      Requires administration permission."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string"},
+                "branch": {"type": "string"},
+                "type": {"type": "string", "enum": VALID_TYPES},
+                "value": {
+                    "type": "string",
+                    "description": "Days, analysis id or branch name, depending on type",
+                },
+            },
+            "required": ["type"],
+        }),
+    }
+}
+
+pub async fn get(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: GetParams = super::parse_args(args)?;
+    if params.branch.is_some() && params.project_key.is_none() {
+        return Err(Error::InvalidArguments(
+            "branch requires project_key".to_string(),
+        ));
+    }
+    let response: Value = match (&params.project_key, &params.branch) {
+        (Some(project), None) => {
+            ctx.client
+                .get(
+                    "/api/new_code_periods/list",
+                    &[("project", project.clone())],
+                )
+                .await?
+        }
+        (Some(project), Some(branch)) => {
+            ctx.client
+                .get(
+                    "/api/new_code_periods/show",
+                    &[("project", project.clone()), ("branch", branch.clone())],
+                )
+                .await?
+        }
+        _ => ctx.client.get("/api/new_code_periods/show", &[]).await?,
+    };
+    super::json_result(ctx, &response)
+}
+
+pub async fn set(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    super::ensure_admin_allowed(ctx)?;
+    let params: SetParams = super::parse_args(args)?;
+    if !VALID_TYPES.contains(&params.period_type.as_str()) {
+        return Err(Error::InvalidArguments(format!(
+            "invalid new code period type: {} (expected one of {})",
+            params.period_type,
+            VALID_TYPES.join(", ")
+        )));
+    }
+    if params.branch.is_some() && params.project_key.is_none() {
+        return Err(Error::InvalidArguments(
+            "branch requires project_key".to_string(),
+        ));
+    }
+    let mut form = vec![("type", params.period_type.clone())];
+    if let Some(project) = &params.project_key {
+        form.push(("project", project.clone()));
+    }
+    if let Some(branch) = &params.branch {
+        form.push(("branch", branch.clone()));
+    }
+    if let Some(value) = &params.value {
+        form.push(("value", value.clone()));
+    }
+    ctx.client.post("/api/new_code_periods/set", &form).await?;
+    super::json_result(ctx, &json!({
+        "updated": true,
+        "type": params.period_type,
+        "project": params.project_key,
+        "branch": params.branch,
+        "value": params.value,
+    }))
+}
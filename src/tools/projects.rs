@@ -0,0 +1,75 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    query: Option<String>,
+    page: Option<u32>,
+    #[serde(alias = "pageSize")]
+    page_size: Option<u32>,
+    cache: Option<bool>,
+    #[serde(alias = "allPages")]
+    all_pages: Option<bool>,
+    fields: Option<Vec<String>>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_list_projects".to_string(),
+        description: "List the projects visible to the configured token.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Limit to projects whose name or key contains this text",
+                },
+                "page": {"type": "integer", "description": "1-based page number"},
+                "page_size": {"type": "integer", "description": "Results per page (max 500)"},
+                "cache": {"type": "boolean", "description": "Set false to bypass the response cache"},
+                "all_pages": {
+                    "type": "boolean",
+                    "description": "Fetch and merge every page (up to the configured cap); page and page_size are ignored",
+                },
+                "fields": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Keep only these fields on each project, e.g. [\"key\", \"name\"]",
+                },
+            },
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let response = if params.all_pages.unwrap_or(false) {
+        ctx.client
+            .list_projects_all_pages(
+                params.query.as_deref(),
+                None,
+                params.cache.unwrap_or(true),
+                ctx.config.max_all_pages_results,
+            )
+            .await?
+    } else {
+        ctx.client
+            .list_projects_filtered(
+                params.query.as_deref(),
+                None,
+                params.page,
+                params.page_size,
+                params.cache.unwrap_or(true),
+            )
+            .await?
+    };
+    let mut value = serde_json::to_value(&response)?;
+    if let Some(fields) = &params.fields {
+        super::retain_fields(&mut value["components"], fields);
+    }
+    super::json_result(ctx, &value)
+}
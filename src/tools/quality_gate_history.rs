@@ -0,0 +1,198 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Only consider analyses from this date on, e.g. 2024-01-01.
+    from: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_quality_gate_history".to_string(),
+        description: "Reconstruct when a project's quality gate flipped between OK and ERROR \
+                      from its analysis history, including the conditions each failure \
+                      recorded."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "from": {
+                    "type": "string",
+                    "description": "Only consider analyses from this date on, e.g. 2024-01-01",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let mut query = vec![
+        ("project", params.project_key.clone()),
+        ("category", "QUALITY_GATE".to_string()),
+        ("ps", "500".to_string()),
+    ];
+    if let Some(from) = &params.from {
+        query.push(("from", from.clone()));
+    }
+    let analyses: Value = super::map_project_not_found(
+        ctx.client.get("/api/project_analyses/search", &query).await,
+        &params.project_key,
+    )?;
+
+    let events = gate_events(&analyses);
+    let flips = flips(&events);
+    super::json_result(
+        ctx,
+        &json!({
+            "project": params.project_key,
+            "current_status": events.last().map(|event| event.status.clone()),
+            "gate_events": events.len(),
+            "flips": flips,
+        }),
+    )
+}
+
+/// One QUALITY_GATE event from the analysis history.
+#[derive(Debug)]
+struct GateEvent {
+    analysis: String,
+    date: String,
+    status: String,
+    /// The event description, which lists the failing conditions on a
+    /// failure, e.g. "Coverage on New Code < 80".
+    description: Option<String>,
+}
+
+/// Extracts QUALITY_GATE events in chronological order (the API returns
+/// analyses newest-first).
+fn gate_events(analyses: &Value) -> Vec<GateEvent> {
+    let mut events: Vec<GateEvent> = analyses["analyses"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+        .iter()
+        .flat_map(|analysis| {
+            let key = analysis["key"].as_str().unwrap_or_default();
+            let date = analysis["date"].as_str().unwrap_or_default();
+            analysis["events"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or(&[])
+                .iter()
+                .filter(|event| event["category"] == "QUALITY_GATE")
+                .map(|event| GateEvent {
+                    analysis: key.to_string(),
+                    date: date.to_string(),
+                    status: event_status(event["name"].as_str().unwrap_or_default()),
+                    description: event["description"].as_str().map(str::to_string),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    events.reverse();
+    events
+}
+
+/// Maps the event name onto a gate status. Names vary across server
+/// versions ("Passed", "Failed", "Green (was Red)"); unknown names pass
+/// through untouched rather than being guessed at.
+fn event_status(name: &str) -> String {
+    let lowered = name.to_ascii_lowercase();
+    if lowered.starts_with("passed") || lowered.starts_with("green") || lowered.starts_with("ok") {
+        "OK".to_string()
+    } else if lowered.starts_with("failed")
+        || lowered.starts_with("red")
+        || lowered.starts_with("error")
+    {
+        "ERROR".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Collapses the event stream to status changes, attributing each failure
+/// to the conditions its event recorded.
+fn flips(events: &[GateEvent]) -> Vec<Value> {
+    let mut flips = Vec::new();
+    let mut previous: Option<&str> = None;
+    for event in events {
+        if previous != Some(event.status.as_str()) {
+            let mut flip = json!({
+                "date": event.date,
+                "analysis": event.analysis,
+                "from": previous,
+                "to": event.status,
+            });
+            if event.status == "ERROR" {
+                if let Some(description) = &event.description {
+                    flip["failing_conditions"] = json!(description
+                        .split(", ")
+                        .map(str::trim)
+                        .collect::<Vec<_>>());
+                }
+            }
+            flips.push(flip);
+        }
+        previous = Some(event.status.as_str());
+    }
+    flips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> Value {
+        // Newest first, as the API returns them.
+        json!({"analyses": [
+            {"key": "A3", "date": "2024-05-15T08:00:00+0000", "events": [
+                {"category": "QUALITY_GATE", "name": "Passed"},
+            ]},
+            {"key": "A2", "date": "2024-05-08T08:00:00+0000", "events": [
+                {"category": "QUALITY_GATE", "name": "Failed",
+                 "description": "Coverage on New Code < 80, Duplicated Lines > 3"},
+                {"category": "VERSION", "name": "2.4.0"},
+            ]},
+            {"key": "A1", "date": "2024-05-01T08:00:00+0000", "events": [
+                {"category": "QUALITY_GATE", "name": "Passed"},
+            ]},
+        ]})
+    }
+
+    #[test]
+    fn reconstructs_flips_in_chronological_order() {
+        let events = gate_events(&history());
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].analysis, "A1");
+
+        let flips = flips(&events);
+        assert_eq!(flips.len(), 3);
+        assert_eq!(flips[1]["from"], "OK");
+        assert_eq!(flips[1]["to"], "ERROR");
+        assert_eq!(
+            flips[1]["failing_conditions"],
+            json!(["Coverage on New Code < 80", "Duplicated Lines > 3"])
+        );
+        assert_eq!(flips[2]["to"], "OK");
+        assert!(flips[2].get("failing_conditions").is_none());
+    }
+
+    #[test]
+    fn normalizes_event_names_across_server_versions() {
+        assert_eq!(event_status("Passed"), "OK");
+        assert_eq!(event_status("Green (was Red)"), "OK");
+        assert_eq!(event_status("Failed"), "ERROR");
+        assert_eq!(event_status("Red (was Green)"), "ERROR");
+        assert_eq!(event_status("Orange"), "Orange");
+    }
+}
@@ -0,0 +1,47 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    format: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_quality_gate_status".to_string(),
+        description: "Get the quality gate status and failing conditions for a project."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "format": {
+                    "type": "string",
+                    "enum": ["json", "markdown"],
+                    "description": "Output format (default json); markdown renders the verdict and conditions",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let response = super::map_project_not_found(
+        ctx.client.quality_gate_status(&params.project_key).await,
+        &params.project_key,
+    )?;
+    super::formatted_result(
+        ctx,
+        &response,
+        params.format.as_deref(),
+        crate::mcp::render::quality_gate,
+    )
+}
@@ -0,0 +1,96 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::{IssueStatus, IssueType, IssuesResponse, Severity};
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    /// Project keys to restrict the search to; all visible projects when
+    /// omitted.
+    projects: Option<Vec<String>>,
+    severities: Option<Vec<Severity>>,
+    types: Option<Vec<IssueType>>,
+    statuses: Option<Vec<IssueStatus>>,
+    resolutions: Option<Vec<String>>,
+    page: Option<u32>,
+    #[serde(alias = "pageSize")]
+    page_size: Option<u32>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_search_issues".to_string(),
+        description: "Search issues across any number of projects — or the whole organization \
+                      when none are given — with the same severity/type/status filters as \
+                      sonarqube_get_issues. Answers org-wide questions like \"all open BLOCKER \
+                      vulnerabilities\"."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "projects": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Project keys; omit to search all visible projects",
+                },
+                "severities": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": Severity::CLASSIC_VALUES},
+                },
+                "types": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": IssueType::VALUES},
+                },
+                "statuses": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": IssueStatus::VALUES},
+                },
+                "resolutions": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": ["FIXED", "WONTFIX", "FALSE-POSITIVE", "REMOVED"]},
+                },
+                "page": {"type": "integer"},
+                "page_size": {"type": "integer"},
+            },
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let mut query: Vec<(&str, String)> = Vec::new();
+    if let Some(projects) = &params.projects {
+        if !projects.is_empty() {
+            query.push(("componentKeys", projects.join(",")));
+        }
+    }
+    if let Some(severities) = &params.severities {
+        let severities: Vec<_> = severities.iter().map(|s| s.as_str()).collect();
+        query.push(("severities", severities.join(",")));
+    }
+    if let Some(types) = &params.types {
+        let types: Vec<_> = types.iter().map(|t| t.as_str()).collect();
+        query.push(("types", types.join(",")));
+    }
+    if let Some(statuses) = &params.statuses {
+        let statuses: Vec<_> = statuses.iter().map(|s| s.as_str()).collect();
+        query.push(("statuses", statuses.join(",")));
+    }
+    if let Some(resolutions) = &params.resolutions {
+        query.push(("resolutions", resolutions.join(",")));
+    }
+    if let Some(page) = params.page {
+        query.push(("p", page.to_string()));
+    }
+    if let Some(page_size) = params.page_size {
+        query.push(("ps", page_size.to_string()));
+    }
+    if let Some(organization) = &ctx.config.organization {
+        query.push(("organization", organization.clone()));
+    }
+    let response: IssuesResponse = ctx.client.get("/api/issues/search", &query).await?;
+    super::json_result(ctx, &response)
+}
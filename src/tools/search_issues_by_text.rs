@@ -0,0 +1,180 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::{Issue, IssuesResponse};
+
+/// Page size used while scanning; the Web API maximum.
+const SCAN_PAGE_SIZE: u32 = 500;
+/// Upper bound on scanned pages, so a broad query on a huge instance cannot
+/// run away. 10,000 issues is also the Web API's pagination ceiling.
+const MAX_SCAN_PAGES: u32 = 20;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    /// Substring (default) or regular expression matched against issue
+    /// messages.
+    query: String,
+    /// Interpret `query` as a regular expression.
+    #[serde(default)]
+    regex: bool,
+    /// Match case-sensitively; substring matching is case-insensitive by
+    /// default.
+    #[serde(default)]
+    case_sensitive: bool,
+    /// Project keys to restrict the scan to; all visible projects when
+    /// omitted.
+    projects: Option<Vec<String>>,
+    /// Stop after this many matching issues.
+    #[serde(alias = "maxResults")]
+    max_results: Option<usize>,
+}
+
+enum Matcher {
+    Substring { needle: String, case_sensitive: bool },
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    fn build(params: &Params) -> Result<Self> {
+        if params.regex {
+            let pattern = if params.case_sensitive {
+                params.query.clone()
+            } else {
+                format!("(?i){}", params.query)
+            };
+            let regex = regex::Regex::new(&pattern)
+                .map_err(|err| Error::InvalidArguments(format!("invalid regex: {err}")))?;
+            Ok(Self::Regex(regex))
+        } else {
+            Ok(Self::Substring {
+                needle: if params.case_sensitive {
+                    params.query.clone()
+                } else {
+                    params.query.to_lowercase()
+                },
+                case_sensitive: params.case_sensitive,
+            })
+        }
+    }
+
+    fn matches(&self, message: &str) -> bool {
+        match self {
+            Self::Substring {
+                needle,
+                case_sensitive: true,
+            } => message.contains(needle.as_str()),
+            Self::Substring { needle, .. } => message.to_lowercase().contains(needle.as_str()),
+            Self::Regex(regex) => regex.is_match(message),
+        }
+    }
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "search_issues_by_text".to_string(),
+        description: "Find issues whose message matches a substring or regex, scanning \
+                      paginated search results server-side. Useful for locating every \
+                      instance of a specific finding phrase across projects."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string", "description": "Substring or regex to match against issue messages"},
+                "regex": {"type": "boolean", "description": "Interpret query as a regular expression"},
+                "case_sensitive": {"type": "boolean"},
+                "projects": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Project keys; omit to scan all visible projects",
+                },
+                "max_results": {"type": "integer", "description": "Stop after this many matches (default 100)"},
+            },
+            "required": ["query"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let matcher = Matcher::build(&params)?;
+    let max_results = params.max_results.unwrap_or(100);
+
+    let mut base_query: Vec<(&str, String)> = Vec::new();
+    if let Some(projects) = &params.projects {
+        if !projects.is_empty() {
+            base_query.push(("componentKeys", projects.join(",")));
+        }
+    }
+    if let Some(organization) = &ctx.config.organization {
+        base_query.push(("organization", organization.clone()));
+    }
+
+    let mut matches: Vec<Issue> = Vec::new();
+    let mut scanned: u32 = 0;
+    let mut total = 0;
+    for page in 1..=MAX_SCAN_PAGES {
+        let mut query = base_query.clone();
+        query.push(("p", page.to_string()));
+        query.push(("ps", SCAN_PAGE_SIZE.to_string()));
+        let response: IssuesResponse = ctx.client.get("/api/issues/search", &query).await?;
+        total = response.paging.total;
+        scanned += response.issues.len() as u32;
+        for issue in response.issues {
+            if matcher.matches(&issue.message) {
+                matches.push(issue);
+                if matches.len() >= max_results {
+                    break;
+                }
+            }
+        }
+        if matches.len() >= max_results || page * SCAN_PAGE_SIZE >= total {
+            break;
+        }
+    }
+    super::json_result(
+        ctx,
+        &json!({
+            "matched": matches.len(),
+            "scanned": scanned,
+            "total_candidates": total,
+            "truncated": matches.len() >= max_results || scanned < total.min(MAX_SCAN_PAGES * SCAN_PAGE_SIZE),
+            "issues": matches,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(query: &str, regex: bool, case_sensitive: bool) -> Params {
+        Params {
+            query: query.to_string(),
+            regex,
+            case_sensitive,
+            projects: None,
+            max_results: None,
+        }
+    }
+
+    #[test]
+    fn substring_matching_ignores_case_by_default() {
+        let matcher = Matcher::build(&params("unused import", false, false)).unwrap();
+        assert!(matcher.matches("Remove this Unused Import."));
+        let sensitive = Matcher::build(&params("Unused", false, true)).unwrap();
+        assert!(!sensitive.matches("remove this unused import"));
+    }
+
+    #[test]
+    fn regex_matching_validates_the_pattern() {
+        let matcher = Matcher::build(&params(r"cognitive complexity .* \d+", true, false)).unwrap();
+        assert!(matcher.matches("Refactor: Cognitive Complexity is over 15"));
+        assert!(matches!(
+            Matcher::build(&params(r"[unclosed", true, false)),
+            Err(Error::InvalidArguments(_))
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// OWASP Top 10 2021 category names, keyed as the Web API facet values them.
+const OWASP_TOP_10_2021: &[(&str, &str)] = &[
+    ("a1", "Broken Access Control"),
+    ("a2", "Cryptographic Failures"),
+    ("a3", "Injection"),
+    ("a4", "Insecure Design"),
+    ("a5", "Security Misconfiguration"),
+    ("a6", "Vulnerable and Outdated Components"),
+    ("a7", "Identification and Authentication Failures"),
+    ("a8", "Software and Data Integrity Failures"),
+    ("a9", "Security Logging and Monitoring Failures"),
+    ("a10", "Server-Side Request Forgery (SSRF)"),
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    /// Project to report on; the whole organization when omitted (hotspots
+    /// are then skipped, as their API requires a project).
+    #[serde(alias = "projectKey")]
+    project_key: Option<String>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_security_report".to_string(),
+        description: "Produce a security rollup grouped by OWASP Top 10 2021 and CWE from \
+                      vulnerability facets, plus security hotspots when a project is given — \
+                      one call for a compliance review instead of several scripted queries."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {
+                    "type": "string",
+                    "description": "Project key; omit for an organization-wide report",
+                },
+            },
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let mut query: Vec<(&str, String)> = vec![
+        ("types", "VULNERABILITY".to_string()),
+        ("facets", "owaspTop10-2021,cwe,severities".to_string()),
+        ("ps", "1".to_string()),
+    ];
+    if let Some(project) = &params.project_key {
+        query.push(("componentKeys", project.clone()));
+    }
+    if let Some(organization) = &ctx.config.organization {
+        query.push(("organization", organization.clone()));
+    }
+    let issues = ctx.client.get::<Value>("/api/issues/search", &query).await;
+    let issues: Value = match &params.project_key {
+        Some(project) => super::map_project_not_found(issues, project)?,
+        None => issues?,
+    };
+
+    let hotspots = match &params.project_key {
+        Some(project) => {
+            let response: Value = super::map_project_not_found(
+                ctx.client
+                    .get(
+                        "/api/hotspots/search",
+                        &[
+                            ("projectKey", project.clone()),
+                            ("ps", "100".to_string()),
+                        ],
+                    )
+                    .await,
+                project,
+            )?;
+            let summary: Vec<Value> = response["hotspots"]
+                .as_array()
+                .map(|hotspots| {
+                    hotspots
+                        .iter()
+                        .map(|h| {
+                            json!({
+                                "key": h["key"],
+                                "security_category": h["securityCategory"],
+                                "vulnerability_probability": h["vulnerabilityProbability"],
+                                "status": h["status"],
+                                "component": h["component"],
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            json!({
+                "total": response["paging"]["total"],
+                "hotspots": summary,
+            })
+        }
+        None => json!(null),
+    };
+
+    super::json_result(
+        ctx,
+        &json!({
+            "scope": params.project_key.as_deref().unwrap_or("organization"),
+            "total_vulnerabilities": issues["paging"]["total"],
+            "owasp_top_10_2021": owasp_rollup(&issues),
+            "cwe": facet_buckets(&issues, "cwe"),
+            "severities": facet_buckets(&issues, "severities"),
+            "hotspots": hotspots,
+        }),
+    )
+}
+
+/// Translates the owaspTop10-2021 facet into named categories, keeping
+/// categories with zero findings out of the report.
+fn owasp_rollup(issues: &Value) -> Vec<Value> {
+    facet_buckets(issues, "owaspTop10-2021")
+        .into_iter()
+        .filter_map(|bucket| {
+            let key = bucket["val"].as_str()?.to_string();
+            let count = bucket["count"].as_u64()?;
+            if count == 0 {
+                return None;
+            }
+            let name = OWASP_TOP_10_2021
+                .iter()
+                .find(|(category, _)| *category == key)
+                .map(|(_, name)| *name)
+                .unwrap_or("Unknown category");
+            Some(json!({"category": key, "name": name, "count": count}))
+        })
+        .collect()
+}
+
+/// Extracts one facet's buckets from an issues search response.
+fn facet_buckets(issues: &Value, property: &str) -> Vec<Value> {
+    issues["facets"]
+        .as_array()
+        .and_then(|facets| {
+            facets
+                .iter()
+                .find(|facet| facet["property"] == property)?["values"]
+                .as_array()
+                .cloned()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_up_owasp_facet_with_category_names() {
+        let issues = json!({
+            "facets": [{
+                "property": "owaspTop10-2021",
+                "values": [
+                    {"val": "a3", "count": 4},
+                    {"val": "a1", "count": 0},
+                    {"val": "a99", "count": 1},
+                ],
+            }],
+        });
+        let rollup = owasp_rollup(&issues);
+        assert_eq!(rollup.len(), 2);
+        assert_eq!(rollup[0]["name"], "Injection");
+        assert_eq!(rollup[1]["name"], "Unknown category");
+    }
+
+    #[test]
+    fn missing_facets_yield_empty_buckets() {
+        assert!(facet_buckets(&json!({}), "cwe").is_empty());
+    }
+}
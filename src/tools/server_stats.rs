@@ -0,0 +1,23 @@
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "get_server_stats".to_string(),
+        description: "Report this MCP server's own runtime statistics: uptime, SonarQube \
+                      request and failure counts, recent failures, and any deprecation \
+                      notices SonarQube has attached to responses."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    super::json_result(ctx, &ctx.diagnostics.snapshot())
+}
@@ -0,0 +1,124 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    key: String,
+    value: Option<String>,
+    /// Multi-value settings, e.g. exclusion patterns.
+    values: Option<Vec<String>>,
+    /// Project to scope the setting to; global when omitted.
+    #[serde(alias = "projectKey")]
+    project_key: Option<String>,
+    /// Reset the setting to its default instead of setting it.
+    #[serde(default)]
+    reset: bool,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_set_setting".to_string(),
+        description: "Set or reset a SonarQube setting, globally or for a project, e.g. \
+                      sonar.exclusions. Requires the server to run with \
+                      --allow-admin-operations."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "key": {"type": "string", "description": "Setting key, e.g. sonar.exclusions"},
+                "value": {"type": "string", "description": "Single value"},
+                "values": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Values for multi-value settings",
+                },
+                "project_key": {"type": "string", "description": "Project scope; global if omitted"},
+                "reset": {"type": "boolean", "description": "Reset to default instead of setting"},
+            },
+            "required": ["key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    super::ensure_admin_allowed(ctx)?;
+    let params: Params = super::parse_args(args)?;
+
+    if params.reset {
+        let mut form = vec![("keys", params.key.clone())];
+        if let Some(project) = &params.project_key {
+            form.push(("component", project.clone()));
+        }
+        ctx.client.post("/api/settings/reset", &form).await?;
+        return super::json_result(ctx, &json!({"reset": true, "key": params.key}));
+    }
+
+    if params.value.is_none() && params.values.is_none() {
+        return Err(Error::InvalidArguments(
+            "either value or values is required unless reset is true".to_string(),
+        ));
+    }
+    let mut form = vec![("key", params.key.clone())];
+    if let Some(value) = &params.value {
+        form.push(("value", value.clone()));
+    }
+    if let Some(values) = &params.values {
+        for value in values {
+            form.push(("values", value.clone()));
+        }
+    }
+    if let Some(project) = &params.project_key {
+        form.push(("component", project.clone()));
+    }
+    ctx.client.post("/api/settings/set", &form).await?;
+    super::json_result(
+        ctx,
+        &json!({
+            "updated": true,
+            "key": params.key,
+            "project": params.project_key,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    fn context(allow_admin: bool) -> ServerContext {
+        let mut args = vec![
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+        ];
+        if allow_admin {
+            args.push("--allow-admin-operations");
+        }
+        ServerContext::new(Config::parse_from(args)).expect("context")
+    }
+
+    #[tokio::test]
+    async fn refuses_writes_unless_admin_operations_are_enabled() {
+        let ctx = context(false);
+        let err = run(&ctx, json!({"key": "sonar.exclusions", "value": "x"}))
+            .await
+            .expect_err("gate should refuse");
+        assert!(matches!(err, Error::AdminOperationsDisabled));
+    }
+
+    #[tokio::test]
+    async fn requires_a_value_or_reset() {
+        let ctx = context(true);
+        let err = run(&ctx, json!({"key": "sonar.exclusions"}))
+            .await
+            .expect_err("missing value should be refused");
+        assert!(matches!(err, Error::InvalidArguments(_)));
+    }
+}
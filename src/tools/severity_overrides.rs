@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+const DEFAULT_MAX_ISSUES: u32 = 100;
+
+/// Changelog lookups issued concurrently per batch.
+const LOOKUP_BATCH_SIZE: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeverityOverride {
+    pub from: String,
+    pub to: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+/// Per-issue changelog verdicts, cached for the lifetime of the process:
+/// changelogs of closed-over changes do not mutate retroactively, and the
+/// endpoint costs one call per issue.
+fn cache() -> &'static Mutex<HashMap<String, Option<SeverityOverride>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<SeverityOverride>>>> = OnceLock::new();
+    CACHE.get_or_init(Mutex::default)
+}
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    #[serde(alias = "maxIssues")]
+    max_issues: Option<u32>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_find_severity_overrides".to_string(),
+        description: "Report issues whose severity was manually changed away from the rule \
+                      default, detected from issue changelogs. Flags potential gaming of the \
+                      quality gate."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "max_issues": {
+                    "type": "integer",
+                    "description": "Unresolved issues to inspect, most recent first (default 100)",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let max_issues = params.max_issues.unwrap_or(DEFAULT_MAX_ISSUES).clamp(1, 500);
+
+    let response: Value = super::map_project_not_found(
+        ctx.client
+            .get(
+                "/api/issues/search",
+                &[
+                    ("componentKeys", params.project_key.clone()),
+                    ("resolved", "false".to_string()),
+                    ("s", "UPDATE_DATE".to_string()),
+                    ("asc", "false".to_string()),
+                    ("ps", max_issues.to_string()),
+                ],
+            )
+            .await,
+        &params.project_key,
+    )?;
+    let issues: Vec<Value> = response["issues"].as_array().cloned().unwrap_or_default();
+
+    let mut pending: Vec<String> = Vec::new();
+    for issue in &issues {
+        if let Some(key) = issue["key"].as_str() {
+            if !cache().lock().expect("cache poisoned").contains_key(key) {
+                pending.push(key.to_string());
+            }
+        }
+    }
+
+    for batch in pending.chunks(LOOKUP_BATCH_SIZE) {
+        let lookups = batch.iter().map(|key| async move {
+            let changelog: Result<Value> = ctx
+                .client
+                .get("/api/issues/changelog", &[("issue", key.clone())])
+                .await;
+            (key.clone(), changelog)
+        });
+        for (key, changelog) in futures::future::join_all(lookups).await {
+            let verdict = find_severity_override(&changelog?);
+            cache().lock().expect("cache poisoned").insert(key, verdict);
+        }
+    }
+
+    let mut overrides = Vec::new();
+    for issue in &issues {
+        let Some(key) = issue["key"].as_str() else {
+            continue;
+        };
+        let cached = cache().lock().expect("cache poisoned").get(key).cloned();
+        if let Some(Some(details)) = cached {
+            overrides.push(json!({
+                "issue": key,
+                "rule": issue["rule"],
+                "component": issue["component"],
+                "current_severity": issue["severity"],
+                "override": details,
+            }));
+        }
+    }
+
+    super::json_result(ctx, &json!({
+        "project": params.project_key,
+        "issues_inspected": issues.len(),
+        "override_count": overrides.len(),
+        "overrides": overrides,
+    }))
+}
+
+/// Finds the most recent manual severity change in an issue changelog.
+fn find_severity_override(changelog: &Value) -> Option<SeverityOverride> {
+    changelog["changelog"]
+        .as_array()?
+        .iter()
+        .rev()
+        .find_map(|entry| {
+            entry["diffs"].as_array()?.iter().find_map(|diff| {
+                if diff["key"] != "severity" {
+                    return None;
+                }
+                Some(SeverityOverride {
+                    from: diff["oldValue"].as_str().unwrap_or("").to_string(),
+                    to: diff["newValue"].as_str().unwrap_or("").to_string(),
+                    user: entry["user"].as_str().map(str::to_string),
+                    date: entry["creationDate"].as_str().map(str::to_string),
+                })
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_manual_severity_change() {
+        let changelog = json!({
+            "changelog": [
+                {"user": "alice", "creationDate": "2026-08-01T09:00:00+0000", "diffs": [
+                    {"key": "severity", "oldValue": "CRITICAL", "newValue": "MINOR"},
+                ]},
+            ],
+        });
+        let result = find_severity_override(&changelog).unwrap();
+        assert_eq!(result.from, "CRITICAL");
+        assert_eq!(result.to, "MINOR");
+        assert_eq!(result.user.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn ignores_unrelated_changes() {
+        let changelog = json!({
+            "changelog": [
+                {"user": "bob", "diffs": [{"key": "assignee", "newValue": "carol"}]},
+            ],
+        });
+        assert!(find_severity_override(&changelog).is_none());
+    }
+}
@@ -0,0 +1,20 @@
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "show_effective_scoring".to_string(),
+        description: "Show the prioritization weights in effect (severity weights and issue \
+                      type multipliers), after merging any --scoring-config overrides over \
+                      the defaults."
+            .to_string(),
+        input_schema: json!({"type": "object", "properties": {}}),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    super::json_result(ctx, &ctx.scoring)
+}
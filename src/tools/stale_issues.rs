@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::{Issue, IssueStatus, SonarQubeIssuesRequest};
+
+/// Default SLA per severity, in days, covering classic and MQR severities.
+const DEFAULT_SLA_DAYS: &[(&str, i64)] = &[
+    ("BLOCKER", 7),
+    ("CRITICAL", 14),
+    ("HIGH", 14),
+    ("MAJOR", 30),
+    ("MEDIUM", 30),
+    ("MINOR", 90),
+    ("LOW", 90),
+    ("INFO", 180),
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Overrides per severity, in days, e.g. {"BLOCKER": 3}. Severities not
+    /// listed keep their default.
+    #[serde(alias = "slaDays")]
+    sla_days: Option<BTreeMap<String, i64>>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_stale_issues".to_string(),
+        description: "Find open issues older than their severity's SLA (default: BLOCKER 7 \
+                      days, CRITICAL 14, MAJOR 30, MINOR 90, INFO 180) and group the \
+                      breaches by severity."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "sla_days": {
+                    "type": "object",
+                    "additionalProperties": {"type": "integer"},
+                    "description": "SLA overrides per severity in days, e.g. {\"BLOCKER\": 3}",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let sla = sla_table(params.sla_days.as_ref());
+    let request = SonarQubeIssuesRequest::builder(params.project_key.clone())
+        .statuses(vec![
+            IssueStatus::Open,
+            IssueStatus::Confirmed,
+            IssueStatus::Reopened,
+        ])
+        .build();
+    let response = super::map_project_not_found(
+        ctx.client
+            .search_issues_all_pages(&request, ctx.config.max_all_pages_results)
+            .await,
+        &request.project_key,
+    )?;
+
+    let mut by_severity: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for issue in &response.issues {
+        if let Some(breach) = breach(issue, &sla) {
+            by_severity
+                .entry(issue.severity.as_str().to_string())
+                .or_default()
+                .push(breach);
+        }
+    }
+    let breaches: Vec<Value> = by_severity
+        .into_iter()
+        .map(|(severity, mut issues)| {
+            issues.sort_by_key(|issue| std::cmp::Reverse(issue["age_days"].as_i64()));
+            json!({
+                "severity": severity,
+                "sla_days": sla.get(severity.as_str()),
+                "count": issues.len(),
+                "issues": issues,
+            })
+        })
+        .collect();
+
+    super::json_result(
+        ctx,
+        &json!({
+            "project": params.project_key,
+            "open_issues_checked": response.issues.len(),
+            "total_breaches": breaches
+                .iter()
+                .filter_map(|group| group["count"].as_u64())
+                .sum::<u64>(),
+            "breaches": breaches,
+        }),
+    )
+}
+
+/// The effective SLA table: defaults overlaid with the caller's overrides.
+fn sla_table(overrides: Option<&BTreeMap<String, i64>>) -> BTreeMap<&'static str, i64> {
+    let mut table: BTreeMap<&'static str, i64> = DEFAULT_SLA_DAYS.iter().copied().collect();
+    if let Some(overrides) = overrides {
+        for (severity, days) in overrides {
+            if let Some(entry) = DEFAULT_SLA_DAYS
+                .iter()
+                .find(|(known, _)| known == severity)
+            {
+                table.insert(entry.0, *days);
+            }
+        }
+    }
+    table
+}
+
+/// The breach record for an issue past its severity's SLA, or None while it
+/// is within SLA (or undatable).
+fn breach(issue: &Issue, sla: &BTreeMap<&'static str, i64>) -> Option<Value> {
+    let limit = *sla.get(issue.severity.as_str())?;
+    let created = issue.creation_date.as_deref()?;
+    let age = super::age_in_days(created)?;
+    if age <= limit {
+        return None;
+    }
+    Some(json!({
+        "key": issue.key,
+        "component": issue.component,
+        "message": issue.message,
+        "created": created,
+        "age_days": age,
+        "days_over_sla": age - limit,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sonarqube::types::{IssueType, Severity};
+
+    fn issue(severity: Severity, created: &str) -> Issue {
+        serde_json::from_value(json!({
+            "key": "AX1",
+            "rule": "rust:S1135",
+            "severity": severity.as_str(),
+            "component": "demo:src/main.rs",
+            "project": "demo",
+            "message": "Fix this",
+            "type": IssueType::CodeSmell.as_str(),
+            "status": "OPEN",
+            "creationDate": created,
+        }))
+        .expect("issue")
+    }
+
+    #[test]
+    fn flags_issues_past_their_severity_sla() {
+        let sla = sla_table(None);
+        let old = issue(Severity::Blocker, "2020-01-01T00:00:00+0000");
+        let breach = breach(&old, &sla).expect("breach");
+        assert!(breach["age_days"].as_i64().unwrap() > 365);
+        assert_eq!(
+            breach["days_over_sla"].as_i64().unwrap(),
+            breach["age_days"].as_i64().unwrap() - 7
+        );
+
+        // An INFO issue of the same age is within its much longer SLA only
+        // if younger than 180 days; this one is not, so it still breaches.
+        assert!(breach_exists(Severity::Info, "2020-01-01"));
+        // A fresh blocker is within SLA.
+        let fresh = Issue {
+            creation_date: None,
+            ..old
+        };
+        assert!(super::breach(&fresh, &sla).is_none());
+    }
+
+    fn breach_exists(severity: Severity, created: &str) -> bool {
+        breach(&issue(severity, created), &sla_table(None)).is_some()
+    }
+
+    #[test]
+    fn overrides_replace_known_severities_only() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("BLOCKER".to_string(), 1);
+        overrides.insert("MADE_UP".to_string(), 99);
+        let table = sla_table(Some(&overrides));
+        assert_eq!(table.get("BLOCKER"), Some(&1));
+        assert_eq!(table.get("CRITICAL"), Some(&14));
+        assert!(!table.contains_key("MADE_UP"));
+    }
+}
@@ -0,0 +1,95 @@
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// URI under which the same bundle is readable as a resource.
+pub const BUNDLE_URI: &str = "sonarqube://support/bundle.json";
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "generate_support_bundle".to_string(),
+        description: "Collect everything needed to file a bug against this server in one step: \
+                      redacted configuration, server stats, and recent failing SonarQube \
+                      request traces. Secrets are never included."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    // Not routed through json_result: the bundle is diagnostic output with a
+    // fixed shape and must not be altered by output policies.
+    Ok(CallToolResult::text(serde_json::to_string_pretty(
+        &bundle(ctx),
+    )?))
+}
+
+/// Builds the bundle; also served via `resources/read` at [`BUNDLE_URI`].
+pub fn bundle(ctx: &ServerContext) -> Value {
+    json!({
+        "server": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "config": redacted_config(&ctx.config),
+        "stats": ctx.diagnostics.snapshot(),
+    })
+}
+
+/// Marks a secret as present or absent without ever including its value.
+fn secret_status(present: bool) -> &'static str {
+    if present {
+        "[redacted]"
+    } else {
+        "[unset]"
+    }
+}
+
+fn redacted_config(config: &Config) -> Value {
+    json!({
+        "sonarqube_url": config.sonarqube_url,
+        "sonarqube_token": secret_status(!config.sonarqube_token.is_empty()),
+        "auth_provider": format!("{:?}", config.auth_provider),
+        "token_file": config.token_file,
+        "token_command": config.token_command.is_some(),
+        "oauth_token_url": config.oauth_token_url,
+        "oauth_client_id": config.oauth_client_id,
+        "oauth_client_secret": secret_status(config.oauth_client_secret.is_some()),
+        "organization": config.organization,
+        "extra_headers": config.extra_headers.len(),
+        "impersonation_header": config.impersonation_header,
+        "outbound_allowlist": config.outbound_allowlist,
+        "redact_code": config.redact_code,
+        "webhook_listen": config.webhook_listen.map(|addr| addr.to_string()),
+        "webhook_secret": secret_status(config.webhook_secret.is_some()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn redacted_config_never_contains_secrets() {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "https://sonar.example.com",
+            "--sonarqube-token",
+            "squ_supersecret",
+            "--webhook-secret",
+            "hooksecret",
+        ]);
+        let redacted = serde_json::to_string(&redacted_config(&config)).unwrap();
+        assert!(!redacted.contains("supersecret"));
+        assert!(!redacted.contains("hooksecret"));
+        assert!(redacted.contains("[redacted]"));
+    }
+}
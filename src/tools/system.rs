@@ -0,0 +1,49 @@
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn status_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_system_status".to_string(),
+        description: "Check whether the SonarQube instance is up and which version it runs, \
+                      from /api/system/status and /api/server/version. Useful as a first step \
+                      when other calls fail."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub fn health_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_system_health".to_string(),
+        description: "Report the instance health (GREEN/YELLOW/RED) and its causes from \
+                      /api/system/health. Requires administration permission or the system \
+                      passcode."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub async fn status(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    let mut status: Value = ctx.client.get("/api/system/status", &[]).await?;
+    // /api/server/version is plain text and reachable without authentication;
+    // it fills in the version on editions where status omits it.
+    if status["version"].is_null() {
+        let (body, _) = ctx.client.get_bytes("/api/server/version", &[]).await?;
+        status["version"] = Value::String(String::from_utf8_lossy(&body).trim().to_string());
+    }
+    super::json_result(ctx, &status)
+}
+
+pub async fn health(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    let health: Value = ctx.client.get("/api/system/health", &[]).await?;
+    super::json_result(ctx, &health)
+}
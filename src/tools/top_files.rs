@@ -0,0 +1,123 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+/// Files returned when the caller does not say how many.
+const DEFAULT_TOP: u32 = 10;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Metric to rank files by, e.g. complexity.
+    metric: String,
+    /// Files to return; default 10, capped at 100.
+    top: Option<u32>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_top_files_by_metric".to_string(),
+        description: "Rank the files of a project by a metric (e.g. complexity, \
+                      duplicated_lines_density) and return the worst offenders — the usual \
+                      starting point for refactoring prioritization."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "metric": {
+                    "type": "string",
+                    "description": "Metric to rank by, e.g. complexity, duplicated_lines_density, ncloc",
+                },
+                "top": {
+                    "type": "integer",
+                    "description": "Files to return (default 10, max 100)",
+                },
+            },
+            "required": ["project_key", "metric"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let top = params.top.unwrap_or(DEFAULT_TOP).clamp(1, 100);
+    // The server does the ranking: sort the component tree by the metric,
+    // descending, files only, skipping components without a measure.
+    let tree: Value = super::map_project_not_found(
+        ctx.client
+            .get(
+                "/api/measures/component_tree",
+                &[
+                    ("component", params.project_key.clone()),
+                    ("metricKeys", params.metric.clone()),
+                    ("metricSort", params.metric.clone()),
+                    ("metricSortFilter", "withMeasuresOnly".to_string()),
+                    ("s", "metric".to_string()),
+                    ("asc", "false".to_string()),
+                    ("qualifiers", "FIL".to_string()),
+                    ("ps", top.to_string()),
+                ],
+            )
+            .await,
+        &params.project_key,
+    )?;
+
+    super::json_result(
+        ctx,
+        &json!({
+            "project": params.project_key,
+            "metric": params.metric,
+            "files": ranked_files(&tree, &params.metric),
+        }),
+    )
+}
+
+/// Trims the component tree page to `(path, value)` rows, keeping the
+/// server's descending order.
+fn ranked_files(tree: &Value, metric: &str) -> Vec<Value> {
+    tree["components"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|component| {
+            let value = component["measures"]
+                .as_array()?
+                .iter()
+                .find(|measure| measure["metric"] == metric)?["value"]
+                .as_str()?;
+            Some(json!({
+                "key": component["key"],
+                "path": component["path"].as_str().or(component["name"].as_str()),
+                "value": value,
+            }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_the_tree_to_path_value_rows() {
+        let tree = json!({"components": [
+            {"key": "demo:src/big.rs", "path": "src/big.rs",
+             "measures": [{"metric": "complexity", "value": "120"}]},
+            {"key": "demo:src/other.rs", "name": "other.rs",
+             "measures": [{"metric": "ncloc", "value": "40"}]},
+            {"key": "demo:src/small.rs", "path": "src/small.rs",
+             "measures": [{"metric": "complexity", "value": "3"}]},
+        ]});
+        let files = ranked_files(&tree, "complexity");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["path"], "src/big.rs");
+        assert_eq!(files[0]["value"], "120");
+        // Components without the requested measure are dropped.
+        assert_eq!(files[1]["path"], "src/small.rs");
+    }
+}
@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::IssuesResponse;
+
+const DEFAULT_LIMIT_PER_COLUMN: u32 = 5;
+
+/// The kanban columns and the issue filters backing each of them.
+const COLUMNS: &[(&str, &str, Option<&str>)] = &[
+    ("open", "OPEN,REOPENED", None),
+    ("confirmed", "CONFIRMED", None),
+    ("accepted", "RESOLVED", Some("WONTFIX")),
+    ("resolved", "RESOLVED", Some("FIXED")),
+];
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    #[serde(alias = "limitPerColumn")]
+    limit_per_column: Option<u32>,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_triage_board".to_string(),
+        description: "Kanban-style view of a project's issues grouped by status \
+                      (open/confirmed/accepted/resolved) with counts and the most recently \
+                      updated issues in each column."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "limit_per_column": {
+                    "type": "integer",
+                    "description": "Issues shown per column (default 5)",
+                },
+            },
+            "required": ["project_key"],
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let limit = params
+        .limit_per_column
+        .unwrap_or(DEFAULT_LIMIT_PER_COLUMN)
+        .clamp(1, 100);
+
+    let mut columns = Vec::new();
+    for (name, statuses, resolutions) in COLUMNS {
+        let mut query = vec![
+            ("componentKeys", params.project_key.clone()),
+            ("statuses", statuses.to_string()),
+            ("s", "UPDATE_DATE".to_string()),
+            ("asc", "false".to_string()),
+            ("ps", limit.to_string()),
+        ];
+        if let Some(resolutions) = resolutions {
+            query.push(("resolutions", resolutions.to_string()));
+        }
+        let response: IssuesResponse = super::map_project_not_found(
+            ctx.client.get("/api/issues/search", &query).await,
+            &params.project_key,
+        )?;
+        columns.push(json!({
+            "column": name,
+            "total": response.paging.total,
+            "recent": response.issues,
+        }));
+    }
+
+    super::json_result(ctx, &json!({
+        "project": params.project_key,
+        "columns": columns,
+    }))
+}
@@ -0,0 +1,48 @@
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_validate_auth".to_string(),
+        description: "Verify that the configured credential is accepted by SonarQube and \
+                      report which user it authenticates as, from \
+                      /api/authentication/validate and /api/users/current."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+/// Checks the configured credential, returning the authenticated login.
+/// Shared between the tool and the optional startup check so both fail with
+/// the same message.
+pub async fn check(ctx: &ServerContext) -> Result<Value> {
+    let validation: Value = ctx
+        .client
+        .get("/api/authentication/validate", &[])
+        .await?;
+    if validation["valid"] != json!(true) {
+        return Err(Error::Config(
+            "SonarQube rejected the configured credential; check SONARQUBE_TOKEN \
+             (or the configured auth provider)"
+                .to_string(),
+        ));
+    }
+    let user: Value = ctx.client.get("/api/users/current", &[]).await?;
+    Ok(json!({
+        "valid": true,
+        "login": user["login"],
+        "name": user["name"],
+        "permissions": user["permissions"],
+    }))
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    let report = check(ctx).await?;
+    super::json_result(ctx, &report)
+}
@@ -0,0 +1,228 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::{Error, Result};
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+use crate::sonarqube::types::QualityGateCondition;
+
+#[derive(Debug, Deserialize)]
+struct Params {
+    #[serde(alias = "projectKey")]
+    project_key: String,
+    /// Version string as recorded by the analysis VERSION event.
+    version: String,
+}
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "verify_release".to_string(),
+        description: "Check that the analysis recording a given version passed its quality \
+                      gate, and report gate conditions that changed versus the previous \
+                      version — a single call for release checklists."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "project_key": {"type": "string", "description": "Project key"},
+                "version": {
+                    "type": "string",
+                    "description": "Version string from the analysis events, e.g. 2.4.0",
+                },
+            },
+            "required": ["project_key", "version"],
+        }),
+    }
+}
+
+/// An analysis that recorded a VERSION event.
+struct VersionedAnalysis {
+    key: String,
+    version: String,
+    date: String,
+}
+
+pub async fn run(ctx: &ServerContext, args: Value) -> Result<CallToolResult> {
+    let params: Params = super::parse_args(args)?;
+    let analyses: Value = super::map_project_not_found(
+        ctx.client
+            .get(
+                "/api/project_analyses/search",
+                &[
+                    ("project", params.project_key.clone()),
+                    ("ps", "500".to_string()),
+                ],
+            )
+            .await,
+        &params.project_key,
+    )?;
+    let versions = versioned_analyses(&analyses);
+    let position = versions
+        .iter()
+        .position(|analysis| analysis.version == params.version)
+        .ok_or_else(|| {
+            Error::InvalidArguments(format!(
+                "no analysis of {} records version {:?}",
+                params.project_key, params.version
+            ))
+        })?;
+    let current = &versions[position];
+    // Analyses come newest-first, so the previous release is the next entry.
+    let previous = versions.get(position + 1);
+
+    let gate = ctx
+        .client
+        .quality_gate_status_for_analysis(&current.key)
+        .await?;
+    let previous_gate = match previous {
+        Some(analysis) => Some(
+            ctx.client
+                .quality_gate_status_for_analysis(&analysis.key)
+                .await?,
+        ),
+        None => None,
+    };
+
+    let failing: Vec<&QualityGateCondition> = gate
+        .project_status
+        .conditions
+        .iter()
+        .filter(|condition| condition.status == "ERROR")
+        .collect();
+    let changes = previous_gate
+        .as_ref()
+        .map(|previous| {
+            condition_changes(
+                &previous.project_status.conditions,
+                &gate.project_status.conditions,
+            )
+        })
+        .unwrap_or_default();
+
+    super::json_result(
+        ctx,
+        &json!({
+            "project": params.project_key,
+            "version": current.version,
+            "analysis_date": current.date,
+            "passed": gate.project_status.status == "OK",
+            "status": gate.project_status.status,
+            "failing_conditions": failing,
+            "previous_version": previous.map(|analysis| analysis.version.clone()),
+            "changed_conditions": changes,
+        }),
+    )
+}
+
+/// Analyses carrying a VERSION event, newest first as the API returns them.
+fn versioned_analyses(analyses: &Value) -> Vec<VersionedAnalysis> {
+    analyses["analyses"]
+        .as_array()
+        .map(|analyses| {
+            analyses
+                .iter()
+                .filter_map(|analysis| {
+                    let version = analysis["events"]
+                        .as_array()?
+                        .iter()
+                        .find(|event| event["category"] == "VERSION")?["name"]
+                        .as_str()?;
+                    Some(VersionedAnalysis {
+                        key: analysis["key"].as_str()?.to_string(),
+                        version: version.to_string(),
+                        date: analysis["date"].as_str().unwrap_or_default().to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Conditions whose status changed between releases, plus conditions only
+/// present on one side (a reconfigured gate).
+fn condition_changes(
+    previous: &[QualityGateCondition],
+    current: &[QualityGateCondition],
+) -> Vec<Value> {
+    let mut changes = Vec::new();
+    for condition in current {
+        match previous
+            .iter()
+            .find(|p| p.metric_key == condition.metric_key)
+        {
+            None => changes.push(json!({
+                "metric": condition.metric_key,
+                "change": "condition added",
+                "status": condition.status,
+            })),
+            Some(before) if before.status != condition.status => changes.push(json!({
+                "metric": condition.metric_key,
+                "change": format!("{} -> {}", before.status, condition.status),
+                "previous_value": before.actual_value,
+                "value": condition.actual_value,
+            })),
+            Some(_) => {}
+        }
+    }
+    for condition in previous {
+        if !current.iter().any(|c| c.metric_key == condition.metric_key) {
+            changes.push(json!({
+                "metric": condition.metric_key,
+                "change": "condition removed",
+            }));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(metric: &str, status: &str, actual: &str) -> QualityGateCondition {
+        QualityGateCondition {
+            status: status.to_string(),
+            metric_key: metric.to_string(),
+            comparator: "LT".to_string(),
+            error_threshold: Some("80".to_string()),
+            actual_value: Some(actual.to_string()),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn extracts_versioned_analyses_newest_first() {
+        let analyses = json!({"analyses": [
+            {"key": "A3", "date": "2024-06-01", "events": [
+                {"category": "VERSION", "name": "2.1.0"},
+            ]},
+            {"key": "A2", "date": "2024-05-20", "events": [
+                {"category": "QUALITY_GATE", "name": "Failed"},
+            ]},
+            {"key": "A1", "date": "2024-05-01", "events": [
+                {"category": "VERSION", "name": "2.0.0"},
+            ]},
+        ]});
+        let versions = versioned_analyses(&analyses);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version, "2.1.0");
+        assert_eq!(versions[1].key, "A1");
+    }
+
+    #[test]
+    fn reports_status_flips_and_gate_reconfiguration() {
+        let previous = vec![
+            condition("coverage", "OK", "85.0"),
+            condition("duplicated_lines_density", "OK", "1.0"),
+        ];
+        let current = vec![
+            condition("coverage", "ERROR", "61.0"),
+            condition("new_bugs", "OK", "0"),
+        ];
+        let changes = condition_changes(&previous, &current);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0]["change"], "OK -> ERROR");
+        assert_eq!(changes[1]["change"], "condition added");
+        assert_eq!(changes[2]["change"], "condition removed");
+    }
+}
@@ -0,0 +1,34 @@
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_get_watchlist".to_string(),
+        description: "List the projects this server watches: explicitly configured keys plus \
+                      any resolved from the configured name pattern or tag filter."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    // A dynamic watchlist that has never been resolved (e.g. before the
+    // first refresher tick) is resolved on demand so the answer is complete.
+    if ctx.watchlist.is_dynamic() && ctx.watchlist.resolved_at_unix() == 0 {
+        ctx.watchlist.resolve(&ctx.client).await?;
+    }
+    super::json_result(
+        ctx,
+        &json!({
+            "projects": ctx.watchlist.projects(),
+            "dynamic": ctx.watchlist.is_dynamic(),
+            "resolved_at_unix": ctx.watchlist.resolved_at_unix(),
+        }),
+    )
+}
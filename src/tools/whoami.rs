@@ -0,0 +1,34 @@
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::mcp::protocol::{CallToolResult, ToolDefinition};
+use crate::server_context::ServerContext;
+
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "sonarqube_whoami".to_string(),
+        description: "Report the authenticated user's login, groups and global permissions, \
+                      plus whether this server allows administrative write tools — useful to \
+                      explain up-front why a write tool would fail."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+        }),
+    }
+}
+
+pub async fn run(ctx: &ServerContext, _args: Value) -> Result<CallToolResult> {
+    let user: Value = ctx.client.get("/api/users/current", &[]).await?;
+    let report = json!({
+        "login": user["login"],
+        "name": user["name"],
+        "email": user["email"],
+        "groups": user["groups"],
+        "global_permissions": user["permissions"]["global"],
+        // Server-side gate, independent of SonarQube permissions: write
+        // tools fail without it even for an admin credential.
+        "admin_operations_enabled": ctx.config.allow_admin_operations,
+    });
+    super::json_result(ctx, &report)
+}
@@ -0,0 +1,146 @@
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::Result;
+use crate::server_context::ServerContext;
+use crate::sonarqube::client::SonarQubeClient;
+
+/// Page size used when resolving the watch filter.
+const RESOLVE_PAGE_SIZE: u32 = 500;
+
+/// The set of projects this server watches. Explicit keys come straight from
+/// configuration; on top of those, a name pattern and/or tag filter is
+/// resolved against `/api/projects/search` periodically, so projects created
+/// after startup are picked up without a restart. The organization scope, when
+/// configured, applies to the search like every other request.
+pub struct Watchlist {
+    explicit: Vec<String>,
+    pattern: Option<String>,
+    tags: Option<String>,
+    resolved: RwLock<Vec<String>>,
+    resolved_at_unix: AtomicU64,
+}
+
+impl Watchlist {
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self {
+            explicit: config.watch_projects.clone(),
+            pattern: config.watch_pattern.clone(),
+            tags: config.watch_tags.clone(),
+            resolved: RwLock::new(Vec::new()),
+            resolved_at_unix: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether the watchlist has a filter that needs periodic resolution.
+    pub fn is_dynamic(&self) -> bool {
+        self.pattern.is_some() || self.tags.is_some()
+    }
+
+    /// All watched project keys: explicit ones plus the latest resolution of
+    /// the filter, deduplicated and sorted.
+    pub fn projects(&self) -> Vec<String> {
+        let mut keys: BTreeSet<String> = self.explicit.iter().cloned().collect();
+        keys.extend(self.resolved.read().expect("lock poisoned").iter().cloned());
+        keys.into_iter().collect()
+    }
+
+    pub fn resolved_at_unix(&self) -> u64 {
+        self.resolved_at_unix.load(Ordering::Relaxed)
+    }
+
+    /// Resolves the filter against the server, replacing the dynamic part of
+    /// the watchlist. Returns how many projects the filter matched.
+    pub async fn resolve(&self, client: &SonarQubeClient) -> Result<usize> {
+        if !self.is_dynamic() {
+            return Ok(0);
+        }
+        let mut matched = Vec::new();
+        let mut page = 1;
+        loop {
+            let response = client
+                .list_projects_filtered(
+                    self.pattern.as_deref(),
+                    self.tags.as_deref(),
+                    Some(page),
+                    Some(RESOLVE_PAGE_SIZE),
+                    // Resolution is the freshness mechanism itself; a cached
+                    // page would defeat the refresh interval.
+                    false,
+                )
+                .await?;
+            matched.extend(response.components.iter().map(|c| c.key.clone()));
+            if page * RESOLVE_PAGE_SIZE >= response.paging.total {
+                break;
+            }
+            page += 1;
+        }
+        let count = matched.len();
+        *self.resolved.write().expect("lock poisoned") = matched;
+        self.resolved_at_unix.store(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            Ordering::Relaxed,
+        );
+        Ok(count)
+    }
+}
+
+/// Background loop keeping a dynamic watchlist fresh. Errors are logged and
+/// retried on the next tick; a failed resolution keeps the previous set.
+pub async fn run_refresher(ctx: Arc<ServerContext>) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        ctx.config.watch_refresh_seconds.max(1),
+    ));
+    loop {
+        interval.tick().await;
+        match ctx.watchlist.resolve(&ctx.client).await {
+            Ok(count) => tracing::debug!("watchlist filter matched {count} projects"),
+            Err(err) => tracing::warn!("watchlist refresh failed: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn merges_and_deduplicates_explicit_and_resolved_keys() {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+            "--watch-project",
+            "beta,alpha",
+            "--watch-pattern",
+            "payments-",
+        ]);
+        let watchlist = Watchlist::from_config(&config);
+        assert!(watchlist.is_dynamic());
+        *watchlist.resolved.write().unwrap() =
+            vec!["alpha".to_string(), "gamma".to_string()];
+        assert_eq!(watchlist.projects(), vec!["alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn explicit_only_watchlists_are_static() {
+        let config = Config::parse_from([
+            "sonarqube-mcp-server",
+            "--sonarqube-url",
+            "http://localhost:9000",
+            "--watch-project",
+            "alpha",
+        ]);
+        let watchlist = Watchlist::from_config(&config);
+        assert!(!watchlist.is_dynamic());
+        assert_eq!(watchlist.projects(), vec!["alpha"]);
+    }
+}
@@ -0,0 +1,135 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use hmac::{Hmac, KeyInit, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::error::Result;
+use crate::server_context::ServerContext;
+
+/// Header carrying the hex-encoded HMAC-SHA256 signature of the payload,
+/// computed by SonarQube with the webhook secret.
+const SIGNATURE_HEADER: &str = "x-sonar-webhook-hmac-sha256";
+
+/// Runs the optional webhook listener. SonarQube webhook deliveries are
+/// validated against the configured secret and forwarded to the connected MCP
+/// client as logging notifications.
+pub async fn serve(ctx: Arc<ServerContext>, addr: SocketAddr) -> Result<()> {
+    let app = Router::new()
+        .route("/webhook", post(handle))
+        .with_state(ctx);
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("webhook listener bound on {addr}");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn handle(
+    State(ctx): State<Arc<ServerContext>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if let Some(secret) = &ctx.config.webhook_secret {
+        let signature = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok());
+        match signature {
+            Some(signature) if verify_signature(secret, &body, signature) => {}
+            _ => {
+                tracing::warn!("rejected webhook delivery with missing or bad signature");
+                return StatusCode::UNAUTHORIZED;
+            }
+        }
+    }
+    let payload: Value = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(err) => {
+            tracing::warn!("rejected webhook delivery with invalid JSON: {err}");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+    forward(&ctx, &payload);
+    StatusCode::NO_CONTENT
+}
+
+/// Emits the delivery as an MCP logging notification, summarising the fields
+/// SonarQube sends for analysis-completed and gate-changed events.
+fn forward(ctx: &ServerContext, payload: &Value) {
+    let project = payload
+        .get("project")
+        .and_then(|p| p.get("key"))
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let status = payload.get("status").and_then(Value::as_str);
+    let gate_status = payload
+        .get("qualityGate")
+        .and_then(|g| g.get("status"))
+        .and_then(Value::as_str);
+    tracing::info!(
+        "webhook delivery for project {project}: analysis={:?} gate={:?}",
+        status,
+        gate_status
+    );
+    // Coalesced per project: a burst of deliveries for the same project
+    // reaches the client as one notification carrying the latest state.
+    ctx.notifier.notify_coalesced(
+        project,
+        "notifications/message",
+        json!({
+            "level": "info",
+            "logger": "sonarqube.webhook",
+            "data": {
+                "project": project,
+                "analysisStatus": status,
+                "qualityGateStatus": gate_status,
+                "payload": payload,
+            },
+        }),
+        std::time::Duration::from_secs(ctx.config.notify_batch_seconds),
+    );
+}
+
+/// Constant-time check of the hex signature against HMAC-SHA256(secret, body).
+fn verify_signature(secret: &str, body: &[u8], signature_hex: &str) -> bool {
+    let Ok(signature) = hex::decode(signature_hex) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn accepts_valid_signature() {
+        let body = br#"{"status":"SUCCESS"}"#;
+        let signature = sign("s3cret", body);
+        assert!(verify_signature("s3cret", body, &signature));
+    }
+
+    #[test]
+    fn rejects_tampered_body_and_bad_hex() {
+        let body = br#"{"status":"SUCCESS"}"#;
+        let signature = sign("s3cret", body);
+        assert!(!verify_signature("s3cret", br#"{"status":"FAILED"}"#, &signature));
+        assert!(!verify_signature("other", body, &signature));
+        assert!(!verify_signature("s3cret", body, "not-hex"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Contract tests against the SonarQube Web API self-description.
+//!
+//! `/api/webservices/list` describes every endpoint and its parameters. The
+//! manifest below records each endpoint this crate calls together with every
+//! query or form parameter it may send; the test checks each one against the
+//! live description so renamed or removed parameters show up as test
+//! failures instead of silently-empty filters.
+//!
+//! Ignored by default because it needs a live server:
+//!
+//! ```sh
+//! SONARQUBE_URL=http://localhost:9000 SONARQUBE_TOKEN=squ_... \
+//!     cargo test --test api_contract -- --ignored
+//! ```
+
+use std::sync::Arc;
+
+use clap::Parser;
+use serde_json::Value;
+
+use sonarqube_mcp_server::config::Config;
+use sonarqube_mcp_server::server_context::ServerContext;
+
+/// Every endpoint the crate calls, with the parameters it may send. Keep in
+/// sync with `sonarqube::client` and the tool modules when adding calls.
+const SENT_PARAMETERS: &[(&str, &[&str])] = &[
+    (
+        "/api/issues/search",
+        &[
+            "componentKeys",
+            "severities",
+            "impactSeverities",
+            "cleanCodeAttributeCategories",
+            "types",
+            "statuses",
+            "resolutions",
+            "resolved",
+            "assignees",
+            "assigned",
+            "createdAfter",
+            "createdBefore",
+            "inNewCodePeriod",
+            "branch",
+            "pullRequest",
+            "facets",
+            "s",
+            "asc",
+            "p",
+            "ps",
+        ],
+    ),
+    ("/api/issues/changelog", &["issue"]),
+    ("/api/hotspots/search", &["projectKey", "status", "branch", "pullRequest", "ps"]),
+    ("/api/projects/search", &["q", "tags", "p", "ps"]),
+    ("/api/components/show", &["component"]),
+    ("/api/measures/component", &["component", "metricKeys", "branch", "pullRequest"]),
+    (
+        "/api/measures/component_tree",
+        &["component", "metricKeys", "metricSort", "metricSortFilter", "s", "asc", "qualifiers", "ps"],
+    ),
+    ("/api/measures/search_history", &["component", "metrics", "from", "to", "ps"]),
+    ("/api/project_analyses/search", &["project", "category", "from", "ps"]),
+    ("/api/qualitygates/project_status", &["projectKey", "analysisId", "branch", "pullRequest"]),
+    ("/api/qualitygates/list", &[]),
+    ("/api/qualitygates/select", &["gateName", "projectKey"]),
+    ("/api/ce/task", &["id"]),
+    ("/api/ce/component", &["component"]),
+    ("/api/project_branches/list", &["project"]),
+    ("/api/qualityprofiles/search", &["project", "language"]),
+    ("/api/rules/search", &["p", "ps", "f", "qprofile", "activation"]),
+    ("/api/rules/show", &["key"]),
+    ("/api/languages/list", &[]),
+    ("/api/new_code_periods/show", &["project", "branch"]),
+    ("/api/new_code_periods/list", &["project"]),
+    ("/api/new_code_periods/set", &["project", "branch", "type", "value"]),
+    ("/api/project_badges/measure", &["project", "branch", "metric"]),
+    ("/api/project_badges/quality_gate", &["project", "branch"]),
+    ("/api/settings/set", &["key", "value", "values", "component"]),
+    ("/api/settings/reset", &["keys", "component"]),
+];
+
+fn live_context() -> Option<Arc<ServerContext>> {
+    let url = std::env::var("SONARQUBE_URL").ok()?;
+    let token = std::env::var("SONARQUBE_TOKEN").ok()?;
+    let config = Config::parse_from([
+        "sonarqube-mcp-server",
+        "--sonarqube-url",
+        &url,
+        "--sonarqube-token",
+        &token,
+    ]);
+    Some(Arc::new(ServerContext::new(config).expect("context")))
+}
+
+/// Looks up an action in the webservices listing by its request path, e.g.
+/// `/api/issues/search` -> service `api/issues`, action `search`.
+fn find_action<'a>(webservices: &'a Value, endpoint: &str) -> Option<&'a Value> {
+    let (service_path, action_key) = endpoint.trim_start_matches('/').rsplit_once('/')?;
+    webservices["webServices"]
+        .as_array()?
+        .iter()
+        .find(|service| service["path"] == service_path)?["actions"]
+        .as_array()?
+        .iter()
+        .find(|action| action["key"] == action_key)
+}
+
+fn declared_params(action: &Value) -> Vec<String> {
+    action["params"]
+        .as_array()
+        .map(|params| {
+            params
+                .iter()
+                .filter_map(|param| param["key"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+#[ignore = "requires a live SonarQube instance"]
+async fn sent_parameters_exist_in_the_api_description() {
+    let ctx = live_context().expect("SONARQUBE_URL and SONARQUBE_TOKEN must be set");
+    let webservices: Value = ctx
+        .client
+        .get("/api/webservices/list", &[])
+        .await
+        .expect("webservices listing");
+
+    let mut drift = Vec::new();
+    for (endpoint, params) in SENT_PARAMETERS {
+        let Some(action) = find_action(&webservices, endpoint) else {
+            drift.push(format!("endpoint no longer described: {endpoint}"));
+            continue;
+        };
+        let declared = declared_params(action);
+        for param in *params {
+            if !declared.iter().any(|key| key == param) {
+                drift.push(format!(
+                    "{endpoint}: parameter `{param}` not declared (server has: {})",
+                    declared.join(", ")
+                ));
+            }
+        }
+        if action["deprecatedSince"].is_string() {
+            drift.push(format!(
+                "{endpoint}: deprecated since {}",
+                action["deprecatedSince"]
+            ));
+        }
+    }
+
+    assert!(drift.is_empty(), "API contract drift:\n{}", drift.join("\n"));
+}
+
+#[test]
+fn manifest_endpoints_are_well_formed() {
+    for (endpoint, _) in SENT_PARAMETERS {
+        assert!(
+            endpoint.starts_with("/api/") && endpoint.trim_start_matches('/').contains('/'),
+            "malformed endpoint in manifest: {endpoint}"
+        );
+    }
+}
+
+#[test]
+fn find_action_resolves_paths() {
+    let webservices = serde_json::json!({
+        "webServices": [{
+            "path": "api/issues",
+            "actions": [{"key": "search", "params": [{"key": "componentKeys"}]}],
+        }],
+    });
+    let action = find_action(&webservices, "/api/issues/search").expect("action");
+    assert_eq!(declared_params(action), vec!["componentKeys"]);
+    assert!(find_action(&webservices, "/api/issues/missing").is_none());
+    assert!(find_action(&webservices, "/api/other/search").is_none());
+}